@@ -0,0 +1,4 @@
+-- Add expiry and last-used tracking to api_keys
+ALTER TABLE api_keys ADD COLUMN expires_at DATETIME;
+ALTER TABLE api_keys ADD COLUMN last_used_at DATETIME;
+ALTER TABLE api_keys ADD COLUMN last_used_ip TEXT;
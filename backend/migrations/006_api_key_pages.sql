@@ -0,0 +1,9 @@
+-- Restrict API keys to specific pages. A key with no rows here is unrestricted.
+CREATE TABLE IF NOT EXISTS api_key_pages (
+    api_key_id TEXT NOT NULL REFERENCES api_keys(id) ON DELETE CASCADE,
+    page_id TEXT NOT NULL REFERENCES pages(id) ON DELETE CASCADE,
+    read_only BOOLEAN NOT NULL DEFAULT 0,
+    PRIMARY KEY (api_key_id, page_id)
+);
+
+CREATE INDEX idx_api_key_pages_page_id ON api_key_pages(page_id);
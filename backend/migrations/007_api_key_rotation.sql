@@ -0,0 +1,5 @@
+-- Keep the previous token hash valid for a grace period after rotation
+ALTER TABLE api_keys ADD COLUMN previous_token_hash TEXT;
+ALTER TABLE api_keys ADD COLUMN previous_token_expires_at DATETIME;
+
+CREATE INDEX idx_api_keys_previous_token_hash ON api_keys(previous_token_hash);
@@ -0,0 +1,45 @@
+-- External login identities, so a user can sign in with more than one provider.
+-- `users.twitch_id` becomes optional (non-Twitch accounts have none), which in SQLite
+-- means rebuilding the table. Migrations run with foreign keys off (see main.rs), so
+-- dropping the old table doesn't cascade and child tables keep referencing `users`.
+
+CREATE TABLE users_new (
+    id TEXT PRIMARY KEY,
+    twitch_id TEXT UNIQUE,
+    username TEXT NOT NULL,
+    display_name TEXT,
+    profile_image_url TEXT,
+    email TEXT,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO users_new (id, twitch_id, username, display_name, profile_image_url, email, created_at, updated_at)
+SELECT id, twitch_id, username, display_name, profile_image_url, email, created_at, updated_at FROM users;
+
+DROP TABLE users;
+ALTER TABLE users_new RENAME TO users;
+
+CREATE INDEX idx_users_twitch_id ON users(twitch_id);
+
+CREATE TRIGGER update_users_updated_at AFTER UPDATE ON users
+BEGIN
+    UPDATE users SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
+END;
+
+CREATE TABLE IF NOT EXISTS user_identities (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    provider TEXT NOT NULL, -- e.g. 'twitch', 'oidc'
+    subject TEXT NOT NULL, -- the provider's stable user id
+    username TEXT,
+    email TEXT,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(provider, subject)
+);
+
+CREATE INDEX idx_user_identities_user_id ON user_identities(user_id);
+
+-- Existing accounts all signed in with Twitch
+INSERT INTO user_identities (id, user_id, provider, subject, username, email, created_at)
+SELECT lower(hex(randomblob(16))), id, 'twitch', twitch_id, username, email, created_at FROM users;
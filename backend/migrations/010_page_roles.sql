@@ -0,0 +1,6 @@
+-- Named roles replace the single can_edit flag: viewer, checker, editor, manager
+ALTER TABLE page_permissions ADD COLUMN role TEXT NOT NULL DEFAULT 'viewer';
+
+UPDATE page_permissions SET role = CASE WHEN can_edit THEN 'editor' ELSE 'viewer' END;
+
+ALTER TABLE page_permissions DROP COLUMN can_edit;
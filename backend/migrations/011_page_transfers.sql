@@ -0,0 +1,10 @@
+-- Pending ownership transfers; at most one per page, applied when the recipient accepts
+CREATE TABLE IF NOT EXISTS page_transfers (
+    id TEXT PRIMARY KEY,
+    page_id TEXT NOT NULL UNIQUE REFERENCES pages(id) ON DELETE CASCADE,
+    from_user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    to_user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_page_transfers_to_user_id ON page_transfers(to_user_id);
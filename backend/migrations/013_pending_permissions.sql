@@ -0,0 +1,14 @@
+-- Shares addressed to a provider login that hasn't signed in yet. Turned into
+-- page_permissions rows when a matching identity first logs in.
+CREATE TABLE IF NOT EXISTS pending_permissions (
+    id TEXT PRIMARY KEY,
+    page_id TEXT NOT NULL REFERENCES pages(id) ON DELETE CASCADE,
+    provider TEXT NOT NULL,
+    login TEXT NOT NULL COLLATE NOCASE,
+    role TEXT NOT NULL DEFAULT 'viewer',
+    granted_by TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(page_id, provider, login)
+);
+
+CREATE INDEX idx_pending_permissions_login ON pending_permissions(provider, login);
@@ -0,0 +1,49 @@
+-- Teams: named groups of users that pages can be shared with as a whole
+CREATE TABLE IF NOT EXISTS teams (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    created_by TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TRIGGER update_teams_updated_at AFTER UPDATE ON teams
+BEGIN
+    UPDATE teams SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
+END;
+
+-- role: member, admin or owner
+CREATE TABLE IF NOT EXISTS team_members (
+    id TEXT PRIMARY KEY,
+    team_id TEXT NOT NULL REFERENCES teams(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role TEXT NOT NULL DEFAULT 'member',
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(team_id, user_id)
+);
+
+CREATE INDEX idx_team_members_user_id ON team_members(user_id);
+
+-- A permission now targets either a user or a team
+CREATE TABLE page_permissions_new (
+    id TEXT PRIMARY KEY,
+    page_id TEXT NOT NULL REFERENCES pages(id) ON DELETE CASCADE,
+    user_id TEXT REFERENCES users(id) ON DELETE CASCADE,
+    team_id TEXT REFERENCES teams(id) ON DELETE CASCADE,
+    role TEXT NOT NULL DEFAULT 'viewer',
+    granted_by TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK ((user_id IS NULL) <> (team_id IS NULL)),
+    UNIQUE(page_id, user_id),
+    UNIQUE(page_id, team_id)
+);
+
+INSERT INTO page_permissions_new (id, page_id, user_id, role, granted_by, created_at)
+SELECT id, page_id, user_id, role, granted_by, created_at FROM page_permissions;
+
+DROP TABLE page_permissions;
+ALTER TABLE page_permissions_new RENAME TO page_permissions;
+
+CREATE INDEX idx_page_permissions_page_id ON page_permissions(page_id);
+CREATE INDEX idx_page_permissions_user_id ON page_permissions(user_id);
+CREATE INDEX idx_page_permissions_team_id ON page_permissions(team_id);
@@ -0,0 +1,16 @@
+-- Requests for access to a page, decided by its owner or a manager
+CREATE TABLE IF NOT EXISTS access_requests (
+    id TEXT PRIMARY KEY,
+    page_id TEXT NOT NULL REFERENCES pages(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    message TEXT,
+    status TEXT NOT NULL DEFAULT 'pending', -- pending, approved, denied
+    role TEXT, -- role granted on approval
+    decided_by TEXT REFERENCES users(id) ON DELETE SET NULL,
+    decided_at DATETIME,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_access_requests_page_id ON access_requests(page_id, status);
+-- At most one open request per user and page
+CREATE UNIQUE INDEX idx_access_requests_pending ON access_requests(page_id, user_id) WHERE status = 'pending';
@@ -0,0 +1,4 @@
+-- Time-limited sharing: expired permissions stop granting access and are purged periodically
+ALTER TABLE page_permissions ADD COLUMN expires_at DATETIME;
+
+CREATE INDEX idx_page_permissions_expires_at ON page_permissions(expires_at);
@@ -0,0 +1,7 @@
+-- Pages a user keeps access to but doesn't want on their dashboard
+CREATE TABLE IF NOT EXISTS hidden_pages (
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    page_id TEXT NOT NULL REFERENCES pages(id) ON DELETE CASCADE,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_id, page_id)
+);
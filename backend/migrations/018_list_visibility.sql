@@ -0,0 +1,13 @@
+-- Per-list access inside a page: 'page' follows the page (and its public link),
+-- 'restricted' is limited to a minimum role and/or listed members, 'private' to the owner
+ALTER TABLE lists ADD COLUMN visibility TEXT NOT NULL DEFAULT 'page';
+ALTER TABLE lists ADD COLUMN min_role TEXT;
+
+CREATE TABLE IF NOT EXISTS list_members (
+    list_id TEXT NOT NULL REFERENCES lists(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (list_id, user_id)
+);
+
+CREATE INDEX idx_list_members_user_id ON list_members(user_id);
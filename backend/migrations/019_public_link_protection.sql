@@ -0,0 +1,3 @@
+-- Optional password and expiry for public links; both are cleared with the slug
+ALTER TABLE pages ADD COLUMN public_password_hash TEXT;
+ALTER TABLE pages ADD COLUMN public_expires_at DATETIME;
@@ -0,0 +1,18 @@
+-- Lists can let anonymous visitors of the public link tick items and suggest new ones
+ALTER TABLE lists ADD COLUMN public_checking BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE lists ADD COLUMN public_suggestions BOOLEAN NOT NULL DEFAULT 0;
+
+-- Items suggested through the public link, waiting for an editor
+CREATE TABLE IF NOT EXISTS item_suggestions (
+    id TEXT PRIMARY KEY,
+    list_id TEXT NOT NULL REFERENCES lists(id) ON DELETE CASCADE,
+    content TEXT NOT NULL,
+    author_name TEXT,
+    status TEXT NOT NULL DEFAULT 'pending', -- pending, approved, rejected
+    item_id TEXT REFERENCES list_items(id) ON DELETE SET NULL, -- item created on approval
+    decided_by TEXT REFERENCES users(id) ON DELETE SET NULL,
+    decided_at DATETIME,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_item_suggestions_list_id ON item_suggestions(list_id, status);
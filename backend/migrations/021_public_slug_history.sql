@@ -0,0 +1,9 @@
+-- Slugs a page used before, so old public links keep resolving to its current slug.
+-- `released_at` starts the cooldown before another page may claim the slug.
+CREATE TABLE public_slug_history (
+    slug TEXT PRIMARY KEY,
+    page_id TEXT NOT NULL REFERENCES pages(id) ON DELETE CASCADE,
+    released_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_public_slug_history_page_id ON public_slug_history(page_id);
@@ -0,0 +1,34 @@
+-- A page counts as updated when its lists or items change, so anything keyed on
+-- pages.updated_at (like public preview images) notices new content.
+CREATE TRIGGER touch_page_on_list_insert AFTER INSERT ON lists
+BEGIN
+    UPDATE pages SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.page_id;
+END;
+
+CREATE TRIGGER touch_page_on_list_update AFTER UPDATE ON lists
+BEGIN
+    UPDATE pages SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.page_id;
+END;
+
+CREATE TRIGGER touch_page_on_list_delete AFTER DELETE ON lists
+BEGIN
+    UPDATE pages SET updated_at = CURRENT_TIMESTAMP WHERE id = OLD.page_id;
+END;
+
+CREATE TRIGGER touch_page_on_item_insert AFTER INSERT ON list_items
+BEGIN
+    UPDATE pages SET updated_at = CURRENT_TIMESTAMP
+    WHERE id = (SELECT page_id FROM lists WHERE id = NEW.list_id);
+END;
+
+CREATE TRIGGER touch_page_on_item_update AFTER UPDATE ON list_items
+BEGIN
+    UPDATE pages SET updated_at = CURRENT_TIMESTAMP
+    WHERE id = (SELECT page_id FROM lists WHERE id = NEW.list_id);
+END;
+
+CREATE TRIGGER touch_page_on_item_delete AFTER DELETE ON list_items
+BEGIN
+    UPDATE pages SET updated_at = CURRENT_TIMESTAMP
+    WHERE id = (SELECT page_id FROM lists WHERE id = OLD.list_id);
+END;
@@ -0,0 +1,18 @@
+-- Keys created before scopes were enforced carry the old `read` / `write` values,
+-- which match none of the named scopes. Expand them to the scopes they stood for;
+-- key management was never part of either and stays with browser sessions.
+UPDATE api_keys
+SET scopes = TRIM(
+    REPLACE(
+        REPLACE(
+            ',' || REPLACE(scopes, ' ', '') || ',',
+            ',read,',
+            ',pages:read,lists:read,users:read,'
+        ),
+        ',write,',
+        ',pages:write,lists:write,users:write,sharing:admin,'
+    ),
+    ','
+)
+WHERE ',' || REPLACE(scopes, ' ', '') || ',' LIKE '%,read,%'
+   OR ',' || REPLACE(scopes, ' ', '') || ',' LIKE '%,write,%';
@@ -0,0 +1,6 @@
+-- Whether a key was created for specific pages. Bound pages are removed along with the
+-- page, so an empty api_key_pages set must not turn a bound key into an unrestricted one.
+ALTER TABLE api_keys ADD COLUMN page_bound BOOLEAN NOT NULL DEFAULT 0;
+
+UPDATE api_keys SET page_bound = 1
+WHERE id IN (SELECT api_key_id FROM api_key_pages);
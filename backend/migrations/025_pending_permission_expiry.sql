@@ -0,0 +1,3 @@
+-- Shares by login can be temporary too; the expiry carries over to the permission
+-- created when that login first signs in.
+ALTER TABLE pending_permissions ADD COLUMN expires_at DATETIME;
@@ -0,0 +1,3 @@
+-- Shares by login are only kept for providers with unique logins; pending shares
+-- addressed to OIDC usernames could be claimed by the wrong account.
+DELETE FROM pending_permissions WHERE provider != 'twitch';
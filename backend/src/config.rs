@@ -6,10 +6,82 @@ pub struct Config {
     pub server_host: String,
     pub server_port: u16,
     pub jwt_secret: String,
+    /// `HS256` signs access tokens with `jwt_secret`; `EdDSA` and `RS256` use `jwt_private_key_file`.
+    #[serde(default = "default_jwt_algorithm")]
+    pub jwt_algorithm: String,
+    /// `kid` header of newly issued tokens. Change it whenever the signing key changes.
+    #[serde(default = "default_jwt_key_id")]
+    pub jwt_key_id: String,
+    /// PEM (PKCS#8, or PKCS#1 for RSA) private key for asymmetric algorithms.
+    #[serde(default)]
+    pub jwt_private_key_file: Option<String>,
+    /// JSON array of verification-only keys: `{"kid": .., "secret": ..}` for old HS256
+    /// secrets, or an old public JWK as served by `/api/auth/jwks.json`.
+    #[serde(default)]
+    pub jwt_previous_keys: Option<String>,
     pub twitch_client_id: String,
     pub twitch_client_secret: String,
     pub twitch_redirect_uri: String,
     pub frontend_url: String,
+    /// Generic OpenID Connect login, enabled when issuer, client id and secret are all set.
+    #[serde(default)]
+    pub oidc_issuer: Option<String>,
+    #[serde(default)]
+    pub oidc_client_id: Option<String>,
+    #[serde(default)]
+    pub oidc_client_secret: Option<String>,
+    /// Defaults to `twitch_redirect_uri`: every provider returns to `/api/auth/callback`.
+    #[serde(default)]
+    pub oidc_redirect_uri: Option<String>,
+    /// Label for the login button.
+    #[serde(default = "default_oidc_display_name")]
+    pub oidc_display_name: String,
+    /// Offer the offline "dev" login where any username can sign in. For local setups and CI only.
+    #[serde(default)]
+    pub dev_login_enabled: bool,
+    /// How long the old secret stays valid after `POST /settings/api-keys/:id/rotate`.
+    #[serde(default = "default_api_key_rotation_grace_secs")]
+    pub api_key_rotation_grace_secs: i64,
+    /// How often expired page permissions are deleted.
+    #[serde(default = "default_permission_purge_interval_secs")]
+    pub permission_purge_interval_secs: u64,
+    /// How long a released public slug stays reserved for the page that used it.
+    #[serde(default = "default_public_slug_cooldown_secs")]
+    pub public_slug_cooldown_secs: i64,
+    /// Comma-separated proxy addresses or CIDR ranges allowed to set `X-Forwarded-For`.
+    #[serde(default)]
+    pub trusted_proxies: String,
+    /// Anonymous writes (ticking, suggestions) allowed per client IP per minute.
+    #[serde(default = "default_public_rate_limit_per_minute")]
+    pub public_rate_limit_per_minute: u32,
+}
+
+fn default_jwt_algorithm() -> String {
+    "HS256".to_string()
+}
+
+fn default_jwt_key_id() -> String {
+    crate::services::auth::DEFAULT_JWT_KEY_ID.to_string()
+}
+
+fn default_api_key_rotation_grace_secs() -> i64 {
+    24 * 60 * 60
+}
+
+fn default_permission_purge_interval_secs() -> u64 {
+    60 * 60
+}
+
+fn default_public_slug_cooldown_secs() -> i64 {
+    30 * 24 * 60 * 60
+}
+
+fn default_public_rate_limit_per_minute() -> u32 {
+    30
+}
+
+fn default_oidc_display_name() -> String {
+    "Single sign-on".to_string()
 }
 
 impl Config {
@@ -1,5 +1,6 @@
+use crate::models::Scope;
 use axum::{
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{IntoResponse, Json},
 };
 use serde::Serialize;
@@ -10,6 +11,18 @@ pub struct ErrorResponse {
     pub error: String,
 }
 
+#[derive(Debug, Serialize)]
+pub struct InsufficientScopeResponse {
+    pub error: String,
+    pub required_scope: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MovedResponse {
+    pub error: String,
+    pub moved_to: String,
+}
+
 #[derive(Debug, Serialize)]
 pub struct FieldError {
     pub field: String,
@@ -29,12 +42,29 @@ pub enum AppError {
     #[error("Validation error")]
     Validation(Vec<FieldError>),
 
+    #[error("Unauthorized")]
+    Unauthorized,
+
     #[error("Not found")]
     NotFound,
 
     #[error("Forbidden")]
     Forbidden,
 
+    /// The resource existed but is no longer available, e.g. an expired public link.
+    #[error("Gone")]
+    Gone,
+
+    /// A public link whose slug was changed; answered with a permanent redirect.
+    #[error("Moved to {moved_to}")]
+    MovedPermanently { moved_to: String, location: String },
+
+    #[error("Too many requests")]
+    TooManyRequests,
+
+    #[error("Insufficient scope: {}", .0.as_str())]
+    InsufficientScope(Scope),
+
     #[error("Database error: {0}")]
     Database(#[from] anyhow::Error),
 
@@ -61,6 +91,13 @@ impl IntoResponse for AppError {
             AppError::BadRequest(msg) => {
                 (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: msg })).into_response()
             }
+            AppError::Unauthorized => (
+                StatusCode::UNAUTHORIZED,
+                Json(ErrorResponse {
+                    error: "Unauthorized".to_string(),
+                }),
+            )
+                .into_response(),
             AppError::NotFound => (
                 StatusCode::NOT_FOUND,
                 Json(ErrorResponse {
@@ -75,6 +112,37 @@ impl IntoResponse for AppError {
                 }),
             )
                 .into_response(),
+            AppError::Gone => (
+                StatusCode::GONE,
+                Json(ErrorResponse {
+                    error: "This link has expired".to_string(),
+                }),
+            )
+                .into_response(),
+            AppError::MovedPermanently { moved_to, location } => (
+                StatusCode::PERMANENT_REDIRECT,
+                [(header::LOCATION, location)],
+                Json(MovedResponse {
+                    error: "This link has moved".to_string(),
+                    moved_to,
+                }),
+            )
+                .into_response(),
+            AppError::TooManyRequests => (
+                StatusCode::TOO_MANY_REQUESTS,
+                Json(ErrorResponse {
+                    error: "Too many requests, try again later".to_string(),
+                }),
+            )
+                .into_response(),
+            AppError::InsufficientScope(scope) => (
+                StatusCode::FORBIDDEN,
+                Json(InsufficientScopeResponse {
+                    error: "insufficient_scope".to_string(),
+                    required_scope: scope.as_str().to_string(),
+                }),
+            )
+                .into_response(),
             AppError::Database(err) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ErrorResponse {
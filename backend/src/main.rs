@@ -12,15 +12,21 @@ mod services;
 mod tests_utils;
 
 use crate::config::Config;
-use crate::middleware::{auth_middleware, AuthState};
-use crate::repositories::{ListRepository, PageRepository, UserRepository, ApiKeyRepository};
+use crate::middleware::{auth_middleware, AuthState, TrustedProxies};
+use crate::repositories::{ListRepository, PageRepository, UserRepository, ApiKeyRepository, SessionRepository, TeamRepository};
 use crate::routes::{
-    auth_router, lists_router, pages_router, public_router, users_router, api_keys_router, AuthRouterState,
+    auth_router, lists_router, pages_router, public_router, shared_link_router, users_router, api_keys_router, AuthRouterState,
     ListsRouterState, PagesRouterState, PublicRouterState, UsersRouterState, ApiKeysRouterState,
+    sessions_router, SessionsRouterState, invites_router, InvitesRouterState,
+    teams_router, TeamsRouterState, access_requests_router, AccessRequestsRouterState,
+};
+use crate::services::{
+    AuthService, ApiKeyService, DevProvider, IdentityProvider, IdentityProviders, JwtKeyring, OidcProvider, PreviewCache, RateLimiter,
+    TwitchProvider,
 };
-use crate::services::{AuthService, ApiKeyService};
 use axum::{middleware as axum_middleware, Router};
 use sqlx::sqlite::SqlitePoolOptions;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use axum::http::HeaderValue;
 use url::Url;
@@ -48,6 +54,7 @@ async fn main() -> anyhow::Result<()> {
     // Do not log sensitive configuration like full database URLs or secrets.
     tracing::info!("Redirect URI: {}", config.twitch_redirect_uri);
     tracing::info!("Frontend URL: {}", config.frontend_url);
+    let trusted_proxies = Arc::new(TrustedProxies::parse(&config.trusted_proxies).map_err(anyhow::Error::msg)?);
 
     // Setup database connection pool
     let pool = SqlitePoolOptions::new()
@@ -56,8 +63,13 @@ async fn main() -> anyhow::Result<()> {
         .await?;
     tracing::info!("Database connection established");
 
-    // Run migrations
-    sqlx::migrate!("./migrations").run(&pool).await?;
+    // Run migrations. Foreign keys are off while they run so table rebuilds
+    // (drop + rename) don't cascade into child tables.
+    let mut conn = pool.acquire().await?;
+    sqlx::query("PRAGMA foreign_keys = OFF").execute(&mut *conn).await?;
+    sqlx::migrate!("./migrations").run(&mut *conn).await?;
+    sqlx::query("PRAGMA foreign_keys = ON").execute(&mut *conn).await?;
+    drop(conn);
     tracing::info!("Database migrations completed");
 
     // Initialize repositories
@@ -65,17 +77,68 @@ async fn main() -> anyhow::Result<()> {
     let page_repo = Arc::new(PageRepository::new(pool.clone()));
     let list_repo = Arc::new(ListRepository::new(pool.clone()));
     let api_key_repo = Arc::new(ApiKeyRepository::new(pool.clone()));
+    let session_repo = SessionRepository::new(pool.clone());
+    let team_repo = Arc::new(TeamRepository::new(pool.clone()));
+
+    // Expired permissions are already ignored on access checks; this only cleans them up
+    let purge_repo = page_repo.clone();
+    let purge_interval = std::time::Duration::from_secs(config.permission_purge_interval_secs.max(1));
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(purge_interval);
+        loop {
+            interval.tick().await;
+            match purge_repo.purge_expired_permissions().await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Purged {} expired page permissions", count),
+                Err(e) => tracing::warn!("Failed to purge expired page permissions: {}", e),
+            }
+        }
+    });
 
     // Create auth service
-    let auth_service = Arc::new(AuthService::new(
-        config.jwt_secret.clone(),
-        config.twitch_client_id.clone(),
-        config.twitch_client_secret.clone(),
-        (*user_repo).clone(),
-    ));
+    let jwt_keyring = JwtKeyring::from_config(&config)?;
+    tracing::info!("Signing access tokens with {} key {}", config.jwt_algorithm, config.jwt_key_id);
+    let auth_service = Arc::new(
+        AuthService::new(config.jwt_secret.clone(), (*user_repo).clone(), session_repo)
+            .with_keyring(jwt_keyring),
+    );
 
     let api_key_service = Arc::new(ApiKeyService::new((*api_key_repo).clone()));
 
+    // Identity providers offered on the login page; Twitch first as the default
+    let mut providers: Vec<Arc<dyn IdentityProvider>> = vec![Arc::new(TwitchProvider::new(
+        config.twitch_client_id.clone(),
+        config.twitch_client_secret.clone(),
+        config.twitch_redirect_uri.clone(),
+    ))];
+    if let (Some(issuer), Some(client_id), Some(client_secret)) = (
+        config.oidc_issuer.clone(),
+        config.oidc_client_id.clone(),
+        config.oidc_client_secret.clone(),
+    ) {
+        tracing::info!("OpenID Connect login enabled for issuer {}", issuer);
+        providers.push(Arc::new(OidcProvider::new(
+            "oidc".to_string(),
+            config.oidc_display_name.clone(),
+            issuer,
+            client_id,
+            client_secret,
+            config
+                .oidc_redirect_uri
+                .clone()
+                .unwrap_or_else(|| config.twitch_redirect_uri.clone()),
+        )));
+    }
+    let dev_login = if config.dev_login_enabled {
+        tracing::warn!("Dev login is enabled: anyone can sign in as any username. Do not use in production.");
+        let dev = Arc::new(DevProvider::new(config.twitch_redirect_uri.clone()));
+        providers.push(dev.clone());
+        Some(dev)
+    } else {
+        None
+    };
+    let identity_providers = Arc::new(IdentityProviders::new(providers));
+
     // Setup CORS — restrict allowed origin to the configured frontend URL when possible.
     // Limit allowed methods and headers to reduce attack surface.
     // If FRONTEND_URL is invalid, fall back to allowing any origin (with a warning).
@@ -93,7 +156,7 @@ async fn main() -> anyhow::Result<()> {
                 match HeaderValue::from_str(&origin) {
                     Ok(origin_hv) => CorsLayer::new()
                         .allow_origin(origin_hv)
-                        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE, Method::OPTIONS])
+                        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::PATCH, Method::DELETE, Method::OPTIONS])
                         .allow_headers(vec![AUTHORIZATION, CONTENT_TYPE, ACCEPT])
                         .allow_credentials(true),
                     Err(_) => {
@@ -115,15 +178,24 @@ async fn main() -> anyhow::Result<()> {
     // Auth routes (no auth middleware)
     let auth_routes = auth_router(AuthRouterState {
         auth_service: auth_service.clone(),
-        twitch_client_id: config.twitch_client_id.clone(),
-        redirect_uri: config.twitch_redirect_uri.clone(),
+        providers: identity_providers.clone(),
+        dev_login,
         frontend_url: config.frontend_url.clone(),
+        trusted_proxies: trusted_proxies.clone(),
     });
 
     // Protected routes (with auth middleware)
     let protected_routes = Router::new()
         .merge(pages_router(PagesRouterState {
             page_repo: page_repo.clone(),
+            team_repo: team_repo.clone(),
+            slug_cooldown: chrono::Duration::seconds(config.public_slug_cooldown_secs),
+        }))
+        .merge(access_requests_router(AccessRequestsRouterState {
+            page_repo: page_repo.clone(),
+        }))
+        .merge(invites_router(InvitesRouterState {
+            page_repo: page_repo.clone(),
         }))
         .merge(lists_router(ListsRouterState {
             list_repo: list_repo.clone(),
@@ -134,27 +206,47 @@ async fn main() -> anyhow::Result<()> {
         }))
         .merge(api_keys_router(ApiKeysRouterState {
             api_key_service: api_key_service.clone(),
+            page_repo: page_repo.clone(),
+            rotation_grace: chrono::Duration::seconds(config.api_key_rotation_grace_secs),
+        }))
+        .merge(teams_router(TeamsRouterState {
+            team_repo: team_repo.clone(),
+        }))
+        .merge(sessions_router(SessionsRouterState {
+            auth_service: auth_service.clone(),
         }))
         .layer(axum_middleware::from_fn_with_state(
             AuthState {
                 auth_service: auth_service.clone(),
                 api_key_service: api_key_service.clone(),
                 user_repo: user_repo.clone(),
+                trusted_proxies: trusted_proxies.clone(),
             },
             auth_middleware,
         ));
 
     // Public routes (no auth middleware)
-    let public_routes = public_router(PublicRouterState {
+    let public_state = PublicRouterState {
         page_repo: page_repo.clone(),
         list_repo: list_repo.clone(),
-    });
+        secure_cookies: crate::routes::auth::secure_cookies(&config.frontend_url),
+        frontend_url: config.frontend_url.clone(),
+        preview_cache: Arc::new(PreviewCache::new()),
+        rate_limiter: Arc::new(RateLimiter::new(
+            config.public_rate_limit_per_minute,
+            std::time::Duration::from_secs(60),
+        )),
+        unlock_limiter: Arc::new(RateLimiter::new(10, std::time::Duration::from_secs(5 * 60))),
+        trusted_proxies,
+    };
+    let public_routes = public_router(public_state.clone());
 
     // Main app
     let app = Router::new()
         .nest("/api/auth", auth_routes)
         .nest("/api", public_routes)
         .nest("/api", protected_routes)
+        .merge(shared_link_router(public_state))
         .layer(cors)
         // Limit JSON body size to 64 KiB to avoid OOM / huge payloads
         .layer(RequestBodyLimitLayer::new(64 * 1024))
@@ -175,7 +267,11 @@ async fn main() -> anyhow::Result<()> {
     let listener = tokio::net::TcpListener::bind(&config.server_addr()).await?;
     tracing::info!("Server listening on {}", config.server_addr());
 
-    axum::serve(listener, app).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
@@ -1,20 +1,25 @@
 
 use crate::services::{AuthService, ApiKeyService};
 use crate::repositories::UserRepository;
-use crate::models::Claims;
+use crate::error::AppError;
+use crate::models::{Claims, Scope};
+use crate::middleware::{client_ip, TrustedProxies};
 use axum::{
-    extract::{Request, State},
-    http::StatusCode,
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderMap, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
+use std::net::SocketAddr;
 use std::sync::Arc;
+use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct AuthState {
     pub auth_service: Arc<AuthService>,
     pub api_key_service: Arc<ApiKeyService>,
     pub user_repo: Arc<UserRepository>,
+    pub trusted_proxies: Arc<TrustedProxies>,
 }
 
 pub async fn auth_middleware(
@@ -22,59 +27,30 @@ pub async fn auth_middleware(
     mut request: Request,
     next: Next,
 ) -> Result<Response, AuthError> {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| *addr);
+    let ip = client_ip(request.headers(), peer, &state.trusted_proxies);
+
     // Prefer explicit API key header
     if let Some(x_key) = request.headers().get("x-api-key").and_then(|h| h.to_str().ok()) {
-        if let Ok(Some((user_id, scopes))) = state.api_key_service.verify_token(x_key).await {
-            if let Some(user) = state.user_repo.find_by_id(user_id).await.map_err(|_| AuthError::InvalidToken)? {
-                let claims = Claims {
-                    sub: user.id.to_string(),
-                    twitch_id: user.twitch_id.clone(),
-                    username: user.username.clone(),
-                    exp: usize::MAX / 2,
-                    scopes: Some(scopes),
-                };
-                request.extensions_mut().insert(claims);
-                return Ok(next.run(request).await);
-            } else {
-                return Err(AuthError::InvalidToken);
-            }
-        } else {
-            return Err(AuthError::InvalidToken);
-        }
+        let claims = authenticate_api_key(&state, x_key, ip.as_deref()).await?;
+        request.extensions_mut().insert(claims);
+        return Ok(next.run(request).await);
     }
 
     // Check Authorization header for ApiKey or Bearer
     if let Some(auth_header) = request.headers().get("Authorization").and_then(|h| h.to_str().ok()) {
-        if auth_header.starts_with("ApiKey ") {
-            let token = &auth_header[7..];
-            if let Ok(Some((user_id, scopes))) = state.api_key_service.verify_token(token).await {
-                if let Some(user) = state.user_repo.find_by_id(user_id).await.map_err(|_| AuthError::InvalidToken)? {
-                    let claims = Claims {
-                        sub: user.id.to_string(),
-                        twitch_id: user.twitch_id.clone(),
-                        username: user.username.clone(),
-                        exp: usize::MAX / 2,
-                        scopes: Some(scopes),
-                    };
-                    request.extensions_mut().insert(claims);
-                    return Ok(next.run(request).await);
-                } else {
-                    return Err(AuthError::InvalidToken);
-                }
-            } else {
-                return Err(AuthError::InvalidToken);
-            }
+        if let Some(token) = auth_header.strip_prefix("ApiKey ") {
+            let claims = authenticate_api_key(&state, token, ip.as_deref()).await?;
+            request.extensions_mut().insert(claims);
+            return Ok(next.run(request).await);
         }
 
         // Fallback to Bearer JWT
-        if auth_header.starts_with("Bearer ") {
-            let token = &auth_header[7..];
-            let mut claims = state
-                .auth_service
-                .verify_jwt(token)
-                .map_err(|_| AuthError::InvalidToken)?;
-
-            claims.scopes = None;
+        if let Some(token) = auth_header.strip_prefix("Bearer ") {
+            let claims = authenticate_jwt(&state, token).await?;
             request.extensions_mut().insert(claims);
 
             return Ok(next.run(request).await);
@@ -82,31 +58,106 @@ pub async fn auth_middleware(
     }
 
     // Also check cookie `auth_token` for JWT (httpOnly cookie flow)
-    if let Some(cookie_hdr) = request.headers().get("cookie").and_then(|h| h.to_str().ok()) {
-        // Parse cookies properly using the `cookie` crate to avoid brittle manual parsing.
-        // cookie::Cookie::parse will parse a single cookie string like "name=value"; browsers send
-        // multiple cookies in a single header separated by `; ` so try splitting and parsing each.
-        for pair in cookie_hdr.split(';') {
-            if let Ok(c) = cookie::Cookie::parse(pair.trim()) {
-                if c.name() == "auth_token" {
-                    let token = c.value();
-                    let mut claims = state
-                        .auth_service
-                        .verify_jwt(token)
-                        .map_err(|_| AuthError::InvalidToken)?;
-
-                    claims.scopes = None;
-                    request.extensions_mut().insert(claims);
-                    return Ok(next.run(request).await);
-                }
-            }
-        }
+    if let Some(token) = cookie_value(request.headers(), "auth_token") {
+        let claims = authenticate_jwt(&state, &token).await?;
+        request.extensions_mut().insert(claims);
+        return Ok(next.run(request).await);
     }
 
     Err(AuthError::MissingToken)
 }
 
+/// Read a single cookie from the `Cookie` header.
+pub fn cookie_value(headers: &HeaderMap, name: &str) -> Option<String> {
+    let cookie_hdr = headers.get("cookie").and_then(|h| h.to_str().ok())?;
+
+    // Browsers send multiple cookies in a single header separated by `; `, so split and
+    // parse each with the `cookie` crate rather than doing brittle manual parsing.
+    cookie_hdr
+        .split(';')
+        .filter_map(|pair| cookie::Cookie::parse(pair.trim()).ok())
+        .find(|c| c.name() == name)
+        .map(|c| c.value().to_string())
+}
+
+/// Verify a session access token. Tokens without a session, or whose
+/// session has been revoked or has expired, are rejected.
+async fn authenticate_jwt(state: &AuthState, token: &str) -> Result<Claims, AuthError> {
+    let mut claims = state
+        .auth_service
+        .verify_jwt(token)
+        .map_err(|_| AuthError::InvalidToken)?;
+
+    let sid = claims.sid.as_deref().ok_or(AuthError::InvalidToken)?;
+    match state.auth_service.is_session_active_for(sid, &claims.sub).await {
+        Ok(true) => {}
+        _ => return Err(AuthError::InvalidToken),
+    }
+
+    claims.scopes = None;
+    Ok(claims)
+}
+
+async fn authenticate_api_key(
+    state: &AuthState,
+    token: &str,
+    ip: Option<&str>,
+) -> Result<Claims, AuthError> {
+    let api_key = match state.api_key_service.verify_token(token, ip).await {
+        Ok(Some(found)) => found,
+        _ => return Err(AuthError::InvalidToken),
+    };
 
+    // A bound key whose pages have all been deleted has nothing left to act on
+    let pages = if api_key.page_bound {
+        let pages = state
+            .api_key_service
+            .list_key_pages(&api_key.id)
+            .await
+            .map_err(|_| AuthError::InvalidToken)?;
+        if pages.is_empty() {
+            return Err(AuthError::InvalidToken);
+        }
+        Some(pages)
+    } else {
+        None
+    };
+
+    let user = state
+        .user_repo
+        .find_by_id(api_key.user_id)
+        .await
+        .map_err(|_| AuthError::InvalidToken)?
+        .ok_or(AuthError::InvalidToken)?;
+
+    Ok(Claims {
+        sub: user.id.to_string(),
+        username: user.username.clone(),
+        exp: usize::MAX / 2,
+        scopes: Some(api_key.scopes_as_vec()),
+        pages,
+        sid: None,
+    })
+}
+
+/// Reject API-key callers whose key was not granted `scope`.
+pub fn require_scope(claims: &Claims, scope: Scope) -> Result<(), AppError> {
+    if claims.has_scope(scope) {
+        Ok(())
+    } else {
+        Err(AppError::InsufficientScope(scope))
+    }
+}
+
+/// Reject API-key callers whose key is bound to other pages, or bound
+/// read-only to this one when `write` is requested.
+pub fn require_page(claims: &Claims, page_id: Uuid, write: bool) -> Result<(), AppError> {
+    if claims.allows_page(page_id, write) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden)
+    }
+}
 
 #[derive(Debug)]
 pub enum AuthError {
@@ -128,7 +179,7 @@ impl IntoResponse for AuthError {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tests_utils::setup_db;
+    use crate::tests_utils::{session_id, setup_db};
     use crate::models::{CreateUser, Claims};
     use crate::repositories::UserRepository;
     use axum::{
@@ -150,9 +201,8 @@ mod tests {
         let user_repo = UserRepository::new(pool.clone());
         let auth_service = Arc::new(crate::services::AuthService::new(
             "test_secret".to_string(),
-            "client_id".to_string(),
-            "client_secret".to_string(),
             user_repo.clone(),
+            crate::repositories::SessionRepository::new(pool.clone()),
         ));
 
         let api_key_repo = crate::repositories::ApiKeyRepository::new(pool.clone());
@@ -161,7 +211,7 @@ mod tests {
         // Create a user and JWT
         let user = user_repo
             .create(CreateUser {
-                twitch_id: "tw123".to_string(),
+                twitch_id: Some("tw123".to_string()),
                 username: "testuser".to_string(),
                 display_name: None,
                 profile_image_url: None,
@@ -169,10 +219,10 @@ mod tests {
             })
             .await?;
 
-        let jwt = auth_service.create_jwt(&user)?;
+        let jwt = auth_service.start_session(&user, None, None).await?.access_token;
 
         // Create a test router with auth middleware
-        let auth_state = AuthState { auth_service, api_key_service, user_repo: Arc::new(user_repo) };
+        let auth_state = AuthState { auth_service, api_key_service, user_repo: Arc::new(user_repo), trusted_proxies: Default::default() };
         let app = Router::new()
             .route("/protected", get(test_handler))
             .layer(axum_middleware::from_fn_with_state(
@@ -198,14 +248,13 @@ mod tests {
         let user_repo = UserRepository::new(pool.clone());
         let auth_service = Arc::new(crate::services::AuthService::new(
             "test_secret".to_string(),
-            "client_id".to_string(),
-            "client_secret".to_string(),
             user_repo.clone(),
+            crate::repositories::SessionRepository::new(pool.clone()),
         ));
 
         let api_key_repo = crate::repositories::ApiKeyRepository::new(pool.clone());
         let api_key_service = Arc::new(crate::services::ApiKeyService::new(api_key_repo));
-        let auth_state = AuthState { auth_service, api_key_service, user_repo: Arc::new(user_repo) };
+        let auth_state = AuthState { auth_service, api_key_service, user_repo: Arc::new(user_repo), trusted_proxies: Default::default() };
         let app = Router::new()
             .route("/protected", get(test_handler))
             .layer(axum_middleware::from_fn_with_state(
@@ -230,14 +279,13 @@ mod tests {
         let user_repo = UserRepository::new(pool.clone());
         let auth_service = Arc::new(crate::services::AuthService::new(
             "test_secret".to_string(),
-            "client_id".to_string(),
-            "client_secret".to_string(),
             user_repo.clone(),
+            crate::repositories::SessionRepository::new(pool.clone()),
         ));
 
         let api_key_repo = crate::repositories::ApiKeyRepository::new(pool.clone());
         let api_key_service = Arc::new(crate::services::ApiKeyService::new(api_key_repo));
-        let auth_state = AuthState { auth_service, api_key_service, user_repo: Arc::new(user_repo) };
+        let auth_state = AuthState { auth_service, api_key_service, user_repo: Arc::new(user_repo), trusted_proxies: Default::default() };
         let app = Router::new()
             .route("/protected", get(test_handler))
             .layer(axum_middleware::from_fn_with_state(
@@ -263,14 +311,13 @@ mod tests {
         let user_repo = UserRepository::new(pool.clone());
         let auth_service = Arc::new(crate::services::AuthService::new(
             "test_secret".to_string(),
-            "client_id".to_string(),
-            "client_secret".to_string(),
             user_repo.clone(),
+            crate::repositories::SessionRepository::new(pool.clone()),
         ));
 
         let api_key_repo = crate::repositories::ApiKeyRepository::new(pool.clone());
         let api_key_service = Arc::new(crate::services::ApiKeyService::new(api_key_repo));
-        let auth_state = AuthState { auth_service, api_key_service, user_repo: Arc::new(user_repo) };
+        let auth_state = AuthState { auth_service, api_key_service, user_repo: Arc::new(user_repo), trusted_proxies: Default::default() };
         let app = Router::new()
             .route("/protected", get(test_handler))
             .layer(axum_middleware::from_fn_with_state(
@@ -289,4 +336,118 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_auth_middleware_revoked_session() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let user_repo = UserRepository::new(pool.clone());
+        let auth_service = Arc::new(crate::services::AuthService::new(
+            "test_secret".to_string(),
+            user_repo.clone(),
+            crate::repositories::SessionRepository::new(pool.clone()),
+        ));
+
+        let api_key_repo = crate::repositories::ApiKeyRepository::new(pool.clone());
+        let api_key_service = Arc::new(crate::services::ApiKeyService::new(api_key_repo));
+
+        let user = user_repo
+            .create(CreateUser {
+                twitch_id: Some("tw321".to_string()),
+                username: "revoked".to_string(),
+                display_name: None,
+                profile_image_url: None,
+                email: None,
+            })
+            .await?;
+
+        let tokens = auth_service.start_session(&user, None, None).await?;
+
+        let auth_state = AuthState { auth_service: auth_service.clone(), api_key_service, user_repo: Arc::new(user_repo), trusted_proxies: Default::default() };
+        let app = Router::new()
+            .route("/protected", get(test_handler))
+            .layer(axum_middleware::from_fn_with_state(
+                auth_state,
+                auth_middleware,
+            ));
+
+        // The access token is still unexpired, but its session is gone
+        auth_service.revoke_session(&session_id(&auth_service, &tokens)).await?;
+
+        let req = Request::builder()
+            .uri("/protected")
+            .header("cookie", format!("auth_token={}", tokens.access_token))
+            .body(Body::empty())?;
+
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_auth_middleware_page_bound_key_without_pages() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let user_repo = UserRepository::new(pool.clone());
+        let auth_service = Arc::new(crate::services::AuthService::new(
+            "test_secret".to_string(),
+            user_repo.clone(),
+            crate::repositories::SessionRepository::new(pool.clone()),
+        ));
+
+        let api_key_repo = crate::repositories::ApiKeyRepository::new(pool.clone());
+        let api_key_service = Arc::new(crate::services::ApiKeyService::new(api_key_repo));
+        let page_repo = crate::repositories::PageRepository::new(pool.clone());
+
+        let user = user_repo
+            .create(CreateUser {
+                twitch_id: Some("tw654".to_string()),
+                username: "bound".to_string(),
+                display_name: None,
+                profile_image_url: None,
+                email: None,
+            })
+            .await?;
+        let page = page_repo
+            .create(
+                user.id,
+                crate::models::CreatePage {
+                    title: "Shopping".to_string(),
+                    description: None,
+                },
+            )
+            .await?;
+        let key = api_key_service
+            .create_api_key(
+                user.id,
+                None,
+                vec!["pages:read".to_string()],
+                None,
+                vec![crate::models::ApiKeyPage { page_id: page.id, read_only: true }],
+            )
+            .await?;
+
+        let auth_state = AuthState { auth_service, api_key_service, user_repo: Arc::new(user_repo), trusted_proxies: Default::default() };
+        let app = Router::new()
+            .route("/protected", get(test_handler))
+            .layer(axum_middleware::from_fn_with_state(
+                auth_state,
+                auth_middleware,
+            ));
+        let request = || {
+            Request::builder()
+                .uri("/protected")
+                .header("x-api-key", key.token.as_str())
+                .body(Body::empty())
+        };
+
+        let resp = app.clone().oneshot(request()?).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // Deleting the only bound page must not leave an unrestricted key behind
+        page_repo.delete(page.id).await?;
+        let resp = app.oneshot(request()?).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }
 }
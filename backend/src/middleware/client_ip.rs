@@ -0,0 +1,113 @@
+use axum::http::HeaderMap;
+use std::net::{IpAddr, SocketAddr};
+
+/// Reverse proxies whose `X-Forwarded-For` header is believed, as addresses or
+/// CIDR ranges. Empty means the server is reached directly and the header is ignored.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies {
+    ranges: Vec<(IpAddr, u8)>,
+}
+
+impl TrustedProxies {
+    /// Parse a comma-separated list such as `127.0.0.1, 10.0.0.0/8, ::1`.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut ranges = Vec::new();
+        for entry in input.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (addr, prefix) = match entry.split_once('/') {
+                Some((addr, prefix)) => (addr, Some(prefix)),
+                None => (entry, None),
+            };
+            let addr: IpAddr = addr
+                .parse()
+                .map_err(|_| format!("Invalid trusted proxy address '{}'", entry))?;
+            let max = if addr.is_ipv4() { 32 } else { 128 };
+            let prefix = match prefix {
+                Some(prefix) => prefix
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|p| *p <= max)
+                    .ok_or_else(|| format!("Invalid trusted proxy range '{}'", entry))?,
+                None => max,
+            };
+            ranges.push((addr, prefix));
+        }
+        Ok(Self { ranges })
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        self.ranges.iter().any(|(net, prefix)| match (net, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - u32::from(*prefix)).unwrap_or(0);
+                u32::from(*net) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - u32::from(*prefix)).unwrap_or(0);
+                u128::from(*net) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        })
+    }
+}
+
+/// Client address for logging and rate limiting. `X-Forwarded-For` is only honored
+/// when the TCP peer is a trusted proxy, and then read from the right: the nearest
+/// hop that isn't one of our proxies is the client, anything before it is hearsay.
+pub fn client_ip(
+    headers: &HeaderMap,
+    peer: Option<SocketAddr>,
+    trusted: &TrustedProxies,
+) -> Option<String> {
+    let peer = peer?.ip().to_canonical();
+    if !trusted.contains(peer) {
+        return Some(peer.to_string());
+    }
+
+    let mut client = peer;
+    let hops = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|h| h.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(str::trim)
+        .collect::<Vec<_>>();
+    for hop in hops.into_iter().rev() {
+        match hop.parse::<IpAddr>() {
+            Ok(ip) => {
+                client = ip.to_canonical();
+                if !trusted.contains(client) {
+                    break;
+                }
+            }
+            // A garbled hop can't be attributed; stop at the last one we could verify
+            Err(_) => break,
+        }
+    }
+    Some(client.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_ip() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "198.51.100.1, 203.0.113.7, 10.0.0.2".parse().unwrap());
+        let proxy: SocketAddr = "10.0.0.1:4000".parse().unwrap();
+        let direct: SocketAddr = "192.0.2.9:4000".parse().unwrap();
+
+        // Without trusted proxies the header is ignored
+        let none = TrustedProxies::default();
+        assert_eq!(client_ip(&headers, Some(proxy), &none).as_deref(), Some("10.0.0.1"));
+
+        // Behind a trusted proxy the nearest untrusted hop wins, not the spoofable first one
+        let trusted = TrustedProxies::parse("10.0.0.0/8, ::1").unwrap();
+        assert_eq!(client_ip(&headers, Some(proxy), &trusted).as_deref(), Some("203.0.113.7"));
+        assert_eq!(client_ip(&headers, Some(direct), &trusted).as_deref(), Some("192.0.2.9"));
+        assert_eq!(client_ip(&headers, None, &trusted), None);
+
+        assert!(TrustedProxies::parse("10.0.0.0/33").is_err());
+        assert!(TrustedProxies::parse("proxy.local").is_err());
+    }
+}
@@ -1,3 +1,5 @@
 pub mod auth;
+pub mod client_ip;
 
-pub use auth::{auth_middleware, AuthState};
+pub use auth::{auth_middleware, cookie_value, require_page, require_scope, AuthState};
+pub use client_ip::{client_ip, TrustedProxies};
@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use super::permission::PageRole;
+use super::user::User;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+pub enum AccessRequestStatus {
+    Pending,
+    Approved,
+    Denied,
+}
+
+/// Someone without access asking the page's sharers to let them in.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AccessRequest {
+    pub id: Uuid,
+    pub page_id: Uuid,
+    pub user_id: Uuid,
+    pub message: Option<String>,
+    pub status: AccessRequestStatus,
+    pub role: Option<PageRole>,
+    pub decided_by: Option<Uuid>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AccessRequestWithUser {
+    #[serde(flatten)]
+    pub request: AccessRequest,
+    pub user: User,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateAccessRequest {
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApproveAccessRequest {
+    /// Viewer when omitted.
+    pub role: Option<PageRole>,
+}
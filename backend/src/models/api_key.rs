@@ -3,6 +3,50 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
+/// Permissions an API key can be granted. JWT (browser) sessions are not
+/// scoped and always act with the full authority of the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    PagesRead,
+    PagesWrite,
+    ListsRead,
+    ListsWrite,
+    SharingAdmin,
+    UsersRead,
+    UsersWrite,
+    KeysManage,
+}
+
+impl Scope {
+    pub const ALL: [Scope; 8] = [
+        Scope::PagesRead,
+        Scope::PagesWrite,
+        Scope::ListsRead,
+        Scope::ListsWrite,
+        Scope::SharingAdmin,
+        Scope::UsersRead,
+        Scope::UsersWrite,
+        Scope::KeysManage,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::PagesRead => "pages:read",
+            Scope::PagesWrite => "pages:write",
+            Scope::ListsRead => "lists:read",
+            Scope::ListsWrite => "lists:write",
+            Scope::SharingAdmin => "sharing:admin",
+            Scope::UsersRead => "users:read",
+            Scope::UsersWrite => "users:write",
+            Scope::KeysManage => "keys:manage",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Scope> {
+        Scope::ALL.into_iter().find(|s| s.as_str() == value)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct ApiKey {
     pub id: String,
@@ -15,9 +59,22 @@ pub struct ApiKey {
     pub revoked: bool,
     #[serde(with = "chrono::serde::ts_seconds")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "chrono::serde::ts_seconds_option")]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(with = "chrono::serde::ts_seconds_option")]
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub last_used_ip: Option<String>,
+    #[serde(with = "chrono::serde::ts_seconds_option")]
+    pub previous_token_expires_at: Option<DateTime<Utc>>,
+    /// Created for specific pages; stays restricted even once all of them are deleted.
+    pub page_bound: bool,
 }
 
 impl ApiKey {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.map(|t| t <= Utc::now()).unwrap_or(false)
+    }
+
     pub fn scopes_as_vec(&self) -> Vec<String> {
         if self.scopes.is_empty() {
             Vec::new()
@@ -33,10 +90,28 @@ impl ApiKey {
             scopes: self.scopes_as_vec(),
             revoked: self.revoked,
             created_at: self.created_at,
+            expires_at: self.expires_at,
+            expired: self.is_expired(),
+            last_used_at: self.last_used_at,
+            last_used_ip: self.last_used_ip.clone(),
+            previous_token_expires_at: self
+                .previous_token_expires_at
+                .filter(|t| *t > Utc::now()),
+            page_bound: self.page_bound,
+            pages: Vec::new(),
         }
     }
 }
 
+/// A page an API key is bound to. Keys created without bound pages act with the
+/// full authority of their owner.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ApiKeyPage {
+    pub page_id: Uuid,
+    #[serde(default)]
+    pub read_only: bool,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ApiKeyResponse {
     pub id: String,
@@ -45,10 +120,32 @@ pub struct ApiKeyResponse {
     pub revoked: bool,
     #[serde(with = "chrono::serde::ts_seconds")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "chrono::serde::ts_seconds_option")]
+    pub expires_at: Option<DateTime<Utc>>,
+    pub expired: bool,
+    #[serde(with = "chrono::serde::ts_seconds_option")]
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub last_used_ip: Option<String>,
+    /// Set while the token replaced by the last rotation is still accepted.
+    #[serde(with = "chrono::serde::ts_seconds_option")]
+    pub previous_token_expires_at: Option<DateTime<Utc>>,
+    /// Restricted to `pages`, which may have become empty as pages were deleted.
+    pub page_bound: bool,
+    pub pages: Vec<ApiKeyPage>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct CreateApiKeyResponse {
     pub id: String,
     pub token: String,
+    #[serde(with = "chrono::serde::ts_seconds_option")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RotateApiKeyResponse {
+    pub id: String,
+    pub token: String,
+    #[serde(with = "chrono::serde::ts_seconds_option")]
+    pub previous_token_expires_at: Option<DateTime<Utc>>,
 }
@@ -1,12 +1,78 @@
+use super::api_key::{ApiKeyPage, Scope};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: String, // user_id
-    pub twitch_id: String,
     pub username: String,
     pub exp: usize,
     pub scopes: Option<Vec<String>>,
+    /// Set only for API keys bound to specific pages.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pages: Option<Vec<ApiKeyPage>>,
+    /// Login session an access token belongs to; checked for revocation on every request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
+}
+
+impl Claims {
+    /// `scopes` is only set for API-key callers; JWT sessions are unrestricted.
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        match &self.scopes {
+            Some(scopes) => scopes.iter().any(|s| s == scope.as_str()),
+            None => true,
+        }
+    }
+
+    /// Whether a page-bound API key may touch `page_id`; `write` additionally
+    /// requires the binding not to be read-only.
+    pub fn allows_page(&self, page_id: Uuid, write: bool) -> bool {
+        match &self.pages {
+            Some(pages) => pages
+                .iter()
+                .any(|p| p.page_id == page_id && (!write || !p.read_only)),
+            None => true,
+        }
+    }
+
+    /// Whether the caller may manage a key with these scopes and pages: an API key
+    /// can't hand out scopes it lacks, and a page-bound one only keys for its own pages.
+    pub fn covers_key(&self, scopes: &[String], pages: &[ApiKeyPage]) -> bool {
+        let scopes_covered = match &self.scopes {
+            Some(own) => scopes.iter().all(|s| own.contains(s)),
+            None => true,
+        };
+        let pages_covered = match &self.pages {
+            Some(_) => {
+                !pages.is_empty() && pages.iter().all(|p| self.allows_page(p.page_id, !p.read_only))
+            }
+            None => true,
+        };
+        scopes_covered && pages_covered
+    }
+}
+
+/// Signed contents of the short-lived `oauth_state` cookie set by `/auth/login`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthState {
+    pub nonce: String,
+    pub pkce_verifier: String,
+    /// Identity provider the login was started with.
+    pub provider: String,
+    pub return_to: Option<String>,
+    /// Set when a signed-in user is linking another provider to their account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_user_id: Option<String>,
+    pub exp: usize,
+}
+
+/// Everything `/auth/login` needs to redirect to the provider.
+#[derive(Debug)]
+pub struct OAuthLoginRequest {
+    pub state: String,
+    pub code_challenge: String,
+    pub cookie: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Providers whose logins are unique and belong to one account, so a page can be
+/// shared with a login before that person signs up. OIDC usernames fall back to
+/// the email's local part and are neither unique nor verified.
+pub const LOGIN_SHARE_PROVIDERS: &[&str] = &["twitch"];
+
+/// A provider account linked to a user. A user may have several, but each
+/// `(provider, subject)` pair belongs to exactly one user.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct UserIdentity {
+    pub id: String,
+    pub user_id: Uuid,
+    pub provider: String,
+    pub subject: String,
+    pub username: Option<String>,
+    pub email: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A signed-in account as reported by an identity provider, normalised
+/// across providers.
+#[derive(Debug, Clone)]
+pub struct ExternalIdentity {
+    pub provider: String,
+    pub subject: String,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub profile_image_url: Option<String>,
+    pub email: Option<String>,
+}
+
+/// Entry in `GET /auth/providers`, used by the login page.
+#[derive(Debug, Clone, Serialize)]
+pub struct IdentityProviderInfo {
+    pub id: String,
+    pub name: String,
+}
@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+pub enum SuggestionStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+/// An item proposed by an anonymous visitor of a public list, moderated by its editors.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ItemSuggestion {
+    pub id: Uuid,
+    pub list_id: Uuid,
+    pub content: String,
+    pub author_name: Option<String>,
+    pub status: SuggestionStatus,
+    /// The item created when the suggestion was approved.
+    pub item_id: Option<Uuid>,
+    pub decided_by: Option<Uuid>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateItemSuggestion {
+    pub content: String,
+    pub author_name: Option<String>,
+}
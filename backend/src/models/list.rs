@@ -1,9 +1,23 @@
 use super::list_item::ListItem;
+use super::permission::PageRole;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
+/// Who can see a list, on top of access to its page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+pub enum ListVisibility {
+    /// Everyone who can view the page, including visitors of its public link.
+    Page,
+    /// The owner, collaborators with at least `min_role`, and the list's members.
+    Restricted,
+    /// Only the page owner.
+    Private,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct List {
     pub id: Uuid,
@@ -14,6 +28,32 @@ pub struct List {
     pub show_progress: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub visibility: ListVisibility,
+    /// Only used by restricted lists.
+    pub min_role: Option<PageRole>,
+    /// Anonymous visitors of the public link may tick items (needs `show_checkboxes`).
+    pub public_checking: bool,
+    /// Anonymous visitors may suggest items, queued for moderation.
+    pub public_suggestions: bool,
+}
+
+impl List {
+    /// Visitors of the public link can see this list at all.
+    pub fn is_public(&self) -> bool {
+        self.visibility == ListVisibility::Page
+    }
+
+    /// Whether someone with `role` on the page sees this list. `is_member` tells
+    /// whether they are listed in the list's members.
+    pub fn visible_to(&self, role: PageRole, is_member: bool) -> bool {
+        match self.visibility {
+            ListVisibility::Page => true,
+            ListVisibility::Private => role == PageRole::Owner,
+            ListVisibility::Restricted => {
+                role == PageRole::Owner || is_member || self.min_role.is_some_and(|r| role >= r)
+            }
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -30,6 +70,8 @@ pub struct UpdateList {
     pub position: Option<i32>,
     pub show_checkboxes: Option<bool>,
     pub show_progress: Option<bool>,
+    pub public_checking: Option<bool>,
+    pub public_suggestions: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
@@ -38,3 +80,19 @@ pub struct ListWithItems {
     pub list: List,
     pub items: Vec<ListItem>,
 }
+
+#[derive(Debug, Serialize)]
+pub struct ListAccess {
+    pub visibility: ListVisibility,
+    pub min_role: Option<PageRole>,
+    pub user_ids: Vec<Uuid>,
+}
+
+/// Replaces a list's visibility, minimum role and members in one go.
+#[derive(Debug, Deserialize)]
+pub struct SetListAccess {
+    pub visibility: ListVisibility,
+    pub min_role: Option<PageRole>,
+    #[serde(default)]
+    pub user_ids: Vec<Uuid>,
+}
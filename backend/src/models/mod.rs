@@ -1,15 +1,31 @@
+pub mod access_request;
 pub mod auth;
 pub mod api_key;
+pub mod identity;
+pub mod item_suggestion;
 pub mod list;
 pub mod list_item;
 pub mod page;
+pub mod page_invite;
+pub mod page_transfer;
 pub mod permission;
+pub mod public;
+pub mod session;
+pub mod team;
 pub mod user;
 
+pub use self::access_request::*;
 pub use self::auth::*;
 pub use self::api_key::*;
+pub use self::identity::*;
+pub use self::item_suggestion::*;
 pub use self::list::*;
 pub use self::list_item::*;
 pub use self::page::*;
+pub use self::page_invite::*;
+pub use self::page_transfer::*;
 pub use self::permission::*;
+pub use self::public::*;
+pub use self::session::*;
+pub use self::team::*;
 pub use self::user::*;
@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
-use super::list::ListWithItems;
+use super::permission::PageRole;
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Page {
@@ -13,6 +13,27 @@ pub struct Page {
     pub public_slug: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Serialized only as whether the public link asks for a password.
+    #[serde(
+        rename = "public_password_protected",
+        serialize_with = "serialize_is_some",
+        skip_deserializing
+    )]
+    pub public_password_hash: Option<String>,
+    pub public_expires_at: Option<DateTime<Utc>>,
+}
+
+impl Page {
+    pub fn is_public_link_expired(&self) -> bool {
+        self.public_expires_at.is_some_and(|t| t <= Utc::now())
+    }
+}
+
+fn serialize_is_some<S: serde::Serializer>(
+    value: &Option<String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bool(value.is_some())
 }
 
 #[derive(Debug, Deserialize)]
@@ -32,16 +53,30 @@ pub struct PageWithPermission {
     #[serde(flatten)]
     pub page: Page,
     pub is_creator: bool,
+    /// `role` allows editing and the caller's API key (if any) isn't read-only for this page.
     pub can_edit: bool,
+    pub role: PageRole,
+    /// Muted by the caller: still accessible, but left out of `GET /pages` by default.
+    pub hidden: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateMembership {
+    pub hidden: bool,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct SetPublicSlug {
     pub public_slug: Option<String>,
+    /// Omitted keeps the current password, `null` removes it.
+    #[serde(default, deserialize_with = "super::permission::deserialize_present")]
+    pub password: Option<Option<String>>,
+    /// Omitted keeps the current expiry, `null` makes the link permanent.
+    #[serde(default, deserialize_with = "super::permission::deserialize_present")]
+    pub expires_at: Option<Option<DateTime<Utc>>>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct PublicPageData {
-    pub page: Page,
-    pub lists: Vec<ListWithItems>,
+#[derive(Debug, Deserialize)]
+pub struct UnlockPublicPage {
+    pub password: String,
 }
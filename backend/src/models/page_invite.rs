@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use super::permission::PageRole;
+
+/// A link that grants `role` on a page to whoever redeems it, up to
+/// `max_uses` times and until `expires_at` (no limit when unset).
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PageInvite {
+    pub id: Uuid,
+    pub page_id: Uuid,
+    #[allow(dead_code)]
+    #[serde(skip)]
+    pub token_hash: String,
+    pub role: PageRole,
+    pub max_uses: Option<i64>,
+    pub uses: i64,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PageInvite {
+    /// Expired or used up.
+    pub fn is_spent(&self) -> bool {
+        self.expires_at.is_some_and(|t| t <= Utc::now())
+            || self.max_uses.is_some_and(|max| self.uses >= max)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreatePageInvite {
+    pub role: Option<PageRole>,
+    pub max_uses: Option<i64>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Returned once on creation; the token cannot be retrieved later.
+#[derive(Debug, Serialize)]
+pub struct CreatedPageInvite {
+    #[serde(flatten)]
+    pub invite: PageInvite,
+    pub token: String,
+}
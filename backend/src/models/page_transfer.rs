@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use super::page::Page;
+
+/// An ownership transfer offered by the page owner, waiting for the recipient to accept.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PageTransfer {
+    pub id: Uuid,
+    pub page_id: Uuid,
+    pub from_user_id: Uuid,
+    pub to_user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PageTransferWithPage {
+    #[serde(flatten)]
+    pub transfer: PageTransfer,
+    pub page: Page,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreatePageTransfer {
+    pub user_id: Uuid,
+}
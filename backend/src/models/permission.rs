@@ -2,32 +2,151 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
+use super::team::Team;
 use super::user::User;
 
+/// What a user may do on a page. Roles are ordered: each one includes
+/// everything the roles before it allow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+pub enum PageRole {
+    /// Read the page and its lists.
+    Viewer,
+    /// Also tick and untick items, but not change their text.
+    Checker,
+    /// Also edit the page, lists and items.
+    Editor,
+    /// Also share the page and manage its public link.
+    Manager,
+    /// The page creator. Never stored in `page_permissions`.
+    Owner,
+}
+
+/// An operation checked against a [`PageRole`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageAction {
+    View,
+    CheckItems,
+    Edit,
+    Share,
+    Delete,
+    Transfer,
+}
+
+impl PageRole {
+    pub fn allows(self, action: PageAction) -> bool {
+        let required = match action {
+            PageAction::View => PageRole::Viewer,
+            PageAction::CheckItems => PageRole::Checker,
+            PageAction::Edit => PageRole::Editor,
+            PageAction::Share => PageRole::Manager,
+            PageAction::Delete | PageAction::Transfer => PageRole::Owner,
+        };
+        self >= required
+    }
+}
+
+/// Grants `role` to exactly one of a user or a team.
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct PagePermission {
     pub id: Uuid,
     pub page_id: Uuid,
-    pub user_id: Uuid,
-    pub can_edit: bool,
+    pub user_id: Option<Uuid>,
+    pub team_id: Option<Uuid>,
+    pub role: PageRole,
     pub granted_by: Uuid,
     pub created_at: DateTime<Utc>,
+    /// Access ends at this time; kept until the purge task removes the row.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl PagePermission {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|t| t <= Utc::now())
+    }
 }
 
 #[derive(Debug, Serialize)]
 pub struct PagePermissionWithUser {
     #[serde(flatten)]
     pub permission: PagePermission,
-    pub user: User,
+    pub user: Option<User>,
+    pub team: Option<Team>,
+}
+
+/// A share addressed to a provider login (e.g. a Twitch name) that has no
+/// account yet. It becomes a [`PagePermission`] when that identity first signs in.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PendingPermission {
+    pub id: Uuid,
+    pub page_id: Uuid,
+    pub provider: String,
+    pub login: String,
+    pub role: PageRole,
+    pub granted_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    /// Carried over to the permission once the login signs in.
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
+/// Identifies the grantee by `user_id`, by `team_id`, or by `login` on
+/// `provider` (Twitch when omitted).
 #[derive(Debug, Deserialize)]
 pub struct GrantPermission {
-    pub user_id: Uuid,
-    pub can_edit: bool,
+    pub user_id: Option<Uuid>,
+    pub team_id: Option<Uuid>,
+    pub login: Option<String>,
+    pub provider: Option<String>,
+    pub role: Option<PageRole>,
+    /// Pre-roles clients send only this: `true` means editor, `false` viewer.
+    pub can_edit: Option<bool>,
+    /// Permanent when omitted.
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct UpdatePermission {
-    pub can_edit: bool,
+    pub role: Option<PageRole>,
+    /// See [`GrantPermission::can_edit`].
+    pub can_edit: Option<bool>,
+    /// Omitted keeps the current expiry, `null` makes the permission permanent.
+    #[serde(default, deserialize_with = "deserialize_present")]
+    pub expires_at: Option<Option<DateTime<Utc>>>,
+}
+
+/// Tells an explicit `null` (`Some(None)`) apart from a missing field (`None`).
+pub(crate) fn deserialize_present<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+/// `role` wins over the legacy `can_edit` flag; neither means viewer.
+pub fn requested_role(role: Option<PageRole>, can_edit: Option<bool>) -> PageRole {
+    match (role, can_edit) {
+        (Some(role), _) => role,
+        (None, Some(true)) => PageRole::Editor,
+        _ => PageRole::Viewer,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_allows() {
+        assert!(PageRole::Viewer.allows(PageAction::View));
+        assert!(!PageRole::Viewer.allows(PageAction::CheckItems));
+        assert!(PageRole::Checker.allows(PageAction::CheckItems));
+        assert!(!PageRole::Checker.allows(PageAction::Edit));
+        assert!(PageRole::Editor.allows(PageAction::Edit));
+        assert!(!PageRole::Editor.allows(PageAction::Share));
+        assert!(PageRole::Manager.allows(PageAction::Share));
+        assert!(!PageRole::Manager.allows(PageAction::Delete));
+        assert!(PageRole::Owner.allows(PageAction::Delete));
+    }
 }
@@ -0,0 +1,104 @@
+//! What anonymous visitors of a public link get to see. Kept separate from the
+//! stored models so internal fields and hidden list settings never leak.
+
+use super::list::List;
+use super::list_item::ListItem;
+use super::page::Page;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct PublicPage {
+    pub title: String,
+    pub description: Option<String>,
+    pub public_slug: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Page> for PublicPage {
+    fn from(page: Page) -> Self {
+        Self {
+            title: page.title,
+            description: page.description,
+            public_slug: page.public_slug,
+            updated_at: page.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicListItem {
+    pub id: Uuid,
+    pub content: String,
+    /// Left out when the list hides its checkboxes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checked: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicProgress {
+    pub checked: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicList {
+    pub id: Uuid,
+    pub title: String,
+    pub show_checkboxes: bool,
+    pub show_progress: bool,
+    /// Visitors may tick items.
+    pub public_checking: bool,
+    /// Visitors may suggest items.
+    pub public_suggestions: bool,
+    /// Only computed when the list shows progress.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<PublicProgress>,
+    pub items: Vec<PublicListItem>,
+}
+
+impl PublicListItem {
+    pub fn new(item: ListItem, show_checkboxes: bool) -> Self {
+        Self {
+            id: item.id,
+            content: item.content,
+            checked: show_checkboxes.then_some(item.checked),
+        }
+    }
+}
+
+impl PublicList {
+    pub fn new(list: List, items: Vec<ListItem>) -> Self {
+        let progress = list.show_progress.then(|| PublicProgress {
+            checked: items.iter().filter(|i| i.checked).count(),
+            total: items.len(),
+        });
+        let items = items
+            .into_iter()
+            .map(|item| PublicListItem::new(item, list.show_checkboxes))
+            .collect();
+
+        Self {
+            id: list.id,
+            title: list.title,
+            show_checkboxes: list.show_checkboxes,
+            show_progress: list.show_progress,
+            public_checking: list.public_checking && list.show_checkboxes,
+            public_suggestions: list.public_suggestions,
+            progress,
+            items,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetPublicItemChecked {
+    pub checked: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicPageData {
+    pub page: PublicPage,
+    pub lists: Vec<PublicList>,
+}
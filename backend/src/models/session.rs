@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A browser login. Access tokens carry the session id in `sid` and stop
+/// working as soon as the session is revoked.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Session {
+    pub id: String,
+    pub user_id: Uuid,
+    #[serde(skip)]
+    pub previous_refresh_token_hash: Option<String>,
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl Session {
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none() && self.expires_at > Utc::now()
+    }
+
+    pub fn to_response(&self, current_session_id: Option<&str>) -> SessionResponse {
+        SessionResponse {
+            id: self.id.clone(),
+            user_agent: self.user_agent.clone(),
+            ip: self.ip.clone(),
+            created_at: self.created_at,
+            last_seen_at: self.last_seen_at,
+            expires_at: self.expires_at,
+            current: current_session_id == Some(self.id.as_str()),
+        }
+    }
+}
+
+/// A login as shown under "Active sessions". `last_seen_at` advances each
+/// time the browser refreshes its access token.
+#[derive(Debug, Serialize)]
+pub struct SessionResponse {
+    pub id: String,
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// Whether this is the session making the request.
+    pub current: bool,
+}
+
+/// Credentials handed to the browser when a session starts or is refreshed.
+#[derive(Debug)]
+pub struct SessionTokens {
+    pub access_token: String,
+    pub access_expires_at: DateTime<Utc>,
+    pub refresh_token: String,
+    pub refresh_expires_at: DateTime<Utc>,
+}
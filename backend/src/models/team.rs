@@ -0,0 +1,97 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use super::user::User;
+
+/// A member's standing within a team, ordered like [`super::PageRole`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+pub enum TeamRole {
+    Member,
+    /// Also rename the team and manage its members.
+    Admin,
+    /// The team creator; also deletes the team.
+    Owner,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Team {
+    pub id: Uuid,
+    pub name: String,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TeamMember {
+    pub id: Uuid,
+    pub team_id: Uuid,
+    pub user_id: Uuid,
+    pub role: TeamRole,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TeamMemberWithUser {
+    #[serde(flatten)]
+    pub member: TeamMember,
+    pub user: User,
+}
+
+/// A team as seen by one of its members.
+#[derive(Debug, Serialize)]
+pub struct TeamWithRole {
+    #[serde(flatten)]
+    pub team: Team,
+    pub role: TeamRole,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TeamWithMembers {
+    #[serde(flatten)]
+    pub team: Team,
+    pub members: Vec<TeamMemberWithUser>,
+    pub invitations: Vec<TeamInvitation>,
+}
+
+/// A membership offered by a team admin, waiting for the user to accept.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TeamInvitation {
+    pub id: Uuid,
+    pub team_id: Uuid,
+    pub user_id: Uuid,
+    pub role: TeamRole,
+    pub invited_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TeamInvitationWithTeam {
+    #[serde(flatten)]
+    pub invitation: TeamInvitation,
+    pub team: Team,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateTeam {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTeam {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InviteTeamMember {
+    pub user_id: Uuid,
+    pub role: Option<TeamRole>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTeamMember {
+    pub role: TeamRole,
+}
@@ -6,7 +6,8 @@ use uuid::Uuid;
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct User {
     pub id: Uuid,
-    pub twitch_id: String,
+    /// Set for accounts that signed up with Twitch; see `user_identities` for all logins.
+    pub twitch_id: Option<String>,
     pub username: String,
     pub display_name: Option<String>,
     pub profile_image_url: Option<String>,
@@ -17,7 +18,7 @@ pub struct User {
 
 #[derive(Debug, Deserialize)]
 pub struct CreateUser {
-    pub twitch_id: String,
+    pub twitch_id: Option<String>,
     pub username: String,
     pub display_name: Option<String>,
     pub profile_image_url: Option<String>,
@@ -1,6 +1,6 @@
-use crate::models::ApiKey;
+use crate::models::{ApiKey, ApiKeyPage};
 use anyhow::{Context, Result};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use sqlx::SqlitePool;
 use uuid::Uuid;
 
@@ -20,44 +20,89 @@ impl ApiKeyRepository {
         name: Option<&str>,
         token_hash: &str,
         scopes: &str,
+        expires_at: Option<DateTime<Utc>>,
+        pages: &[ApiKeyPage],
     ) -> Result<ApiKey> {
         let id = Uuid::new_v4().to_string();
         let now = Utc::now();
 
+        let mut tx = self.pool.begin().await.context("Failed to begin transaction")?;
+
         sqlx::query!(
-            r#"INSERT INTO api_keys (id, user_id, name, token_hash, scopes, revoked, created_at)
-               VALUES (?, ?, ?, ?, ?, 0, ?)"#,
+            r#"INSERT INTO api_keys (id, user_id, name, token_hash, scopes, revoked, created_at, expires_at)
+               VALUES (?, ?, ?, ?, ?, 0, ?, ?)"#,
             id,
             user_id,
             name,
             token_hash,
             scopes,
-            now
+            now,
+            expires_at
         )
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await
         .context("Failed to insert API key")?;
 
+        for page in pages {
+            sqlx::query!(
+                r#"INSERT INTO api_key_pages (api_key_id, page_id, read_only) VALUES (?, ?, ?)"#,
+                id,
+                page.page_id,
+                page.read_only
+            )
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert API key page")?;
+        }
+
+        if !pages.is_empty() {
+            sqlx::query("UPDATE api_keys SET page_bound = 1 WHERE id = $1")
+                .bind(&id)
+                .execute(&mut *tx)
+                .await
+                .context("Failed to bind API key to pages")?;
+        }
+
         let api_key = sqlx::query_as::<_, ApiKey>(
             r#"SELECT id, user_id, name, token_hash, scopes,
-                      revoked, created_at
+                      revoked, created_at, expires_at, last_used_at, last_used_ip,
+                      previous_token_expires_at, page_bound
                FROM api_keys WHERE id = ?"#,
         )
         .bind(&id)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await
         .context("Failed to fetch created API key")?;
 
+        tx.commit().await.context("Failed to commit API key")?;
+
         Ok(api_key)
     }
 
+    pub async fn list_pages(&self, api_key_id: &str) -> Result<Vec<ApiKeyPage>> {
+        let pages = sqlx::query_as::<_, ApiKeyPage>(
+            r#"SELECT page_id, read_only FROM api_key_pages WHERE api_key_id = ?"#,
+        )
+        .bind(api_key_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list API key pages")?;
+
+        Ok(pages)
+    }
+
     pub async fn find_by_token_hash(&self, token_hash: &str) -> Result<Option<ApiKey>> {
         let api_key = sqlx::query_as::<_, ApiKey>(
             r#"SELECT id, user_id, name, token_hash, scopes,
-                      revoked, created_at
-               FROM api_keys WHERE token_hash = ? AND revoked = 0"#,
+                      revoked, created_at, expires_at, last_used_at, last_used_ip,
+                      previous_token_expires_at, page_bound
+               FROM api_keys
+               WHERE (token_hash = ? OR (previous_token_hash = ? AND previous_token_expires_at > ?))
+                 AND revoked = 0"#,
         )
         .bind(token_hash)
+        .bind(token_hash)
+        .bind(Utc::now())
         .fetch_optional(&self.pool)
         .await
         .context("Failed to query API key by token hash")?;
@@ -65,10 +110,27 @@ impl ApiKeyRepository {
         Ok(api_key)
     }
 
+    pub async fn record_usage(&self, id: &str, ip: Option<&str>) -> Result<()> {
+        let now = Utc::now();
+
+        sqlx::query!(
+            r#"UPDATE api_keys SET last_used_at = ?, last_used_ip = COALESCE(?, last_used_ip) WHERE id = ?"#,
+            now,
+            ip,
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to record API key usage")?;
+
+        Ok(())
+    }
+
     pub async fn list_by_user(&self, user_id: Uuid) -> Result<Vec<ApiKey>> {
         let api_keys = sqlx::query_as::<_, ApiKey>(
             r#"SELECT id, user_id, name, token_hash, scopes,
-                      revoked, created_at
+                      revoked, created_at, expires_at, last_used_at, last_used_ip,
+                      previous_token_expires_at, page_bound
                FROM api_keys WHERE user_id = ? ORDER BY created_at DESC"#,
         )
         .bind(user_id)
@@ -79,6 +141,38 @@ impl ApiKeyRepository {
         Ok(api_keys)
     }
 
+    /// Swap in a new token hash, keeping the current one valid until
+    /// `previous_expires_at`. Returns `None` if the key is missing or revoked.
+    pub async fn rotate(
+        &self,
+        id: &str,
+        user_id: Uuid,
+        token_hash: &str,
+        previous_expires_at: Option<DateTime<Utc>>,
+    ) -> Result<Option<ApiKey>> {
+        let result = sqlx::query!(
+            r#"UPDATE api_keys
+               SET previous_token_hash = CASE WHEN ? IS NULL THEN NULL ELSE token_hash END,
+                   previous_token_expires_at = ?,
+                   token_hash = ?
+               WHERE id = ? AND user_id = ? AND revoked = 0"#,
+            previous_expires_at,
+            previous_expires_at,
+            token_hash,
+            id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to rotate API key")?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        self.find_by_id(id, user_id).await
+    }
+
     pub async fn revoke(&self, id: &str, user_id: Uuid) -> Result<bool> {
         let result = sqlx::query!(
             r#"UPDATE api_keys SET revoked = 1 WHERE id = ? AND user_id = ? AND revoked = 0"#,
@@ -105,11 +199,11 @@ impl ApiKeyRepository {
         Ok(result.rows_affected() > 0)
     }
 
-    #[allow(dead_code)]
     pub async fn find_by_id(&self, id: &str, user_id: Uuid) -> Result<Option<ApiKey>> {
         let api_key = sqlx::query_as::<_, ApiKey>(
             r#"SELECT id, user_id, name, token_hash, scopes,
-                      revoked, created_at
+                      revoked, created_at, expires_at, last_used_at, last_used_ip,
+                      previous_token_expires_at, page_bound
                FROM api_keys WHERE id = ? AND user_id = ?"#,
         )
         .bind(id)
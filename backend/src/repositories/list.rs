@@ -1,5 +1,9 @@
-use crate::models::{CreateList, CreateListItem, List, ListItem, UpdateList, UpdateListItem};
+use crate::models::{
+    CreateItemSuggestion, CreateList, CreateListItem, ItemSuggestion, List, ListItem,
+    ListVisibility, PageRole, UpdateList, UpdateListItem,
+};
 use anyhow::Result;
+use chrono::Utc;
 use sqlx::{QueryBuilder, Sqlite, SqlitePool};
 use uuid::Uuid;
 
@@ -96,10 +100,22 @@ impl ListRepository {
             separated.push_bind_unseparated(*show_progress as i32);
         }
 
+        if let Some(public_checking) = &data.public_checking {
+            separated.push("public_checking = ");
+            separated.push_bind_unseparated(*public_checking as i32);
+        }
+
+        if let Some(public_suggestions) = &data.public_suggestions {
+            separated.push("public_suggestions = ");
+            separated.push_bind_unseparated(*public_suggestions as i32);
+        }
+
         if data.title.is_none()
             && data.position.is_none()
             && data.show_checkboxes.is_none()
             && data.show_progress.is_none()
+            && data.public_checking.is_none()
+            && data.public_suggestions.is_none()
         {
             return self.find_by_id(id, page_id).await;
         }
@@ -126,6 +142,87 @@ impl ListRepository {
         Ok(())
     }
 
+    pub async fn list_members(&self, list_id: Uuid) -> Result<Vec<Uuid>> {
+        let user_ids: Vec<Uuid> = sqlx::query_scalar(
+            "SELECT user_id FROM list_members WHERE list_id = $1 ORDER BY created_at ASC",
+        )
+        .bind(list_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(user_ids)
+    }
+
+    pub async fn is_list_member(&self, list_id: Uuid, user_id: Uuid) -> Result<bool> {
+        let member: Option<i64> =
+            sqlx::query_scalar("SELECT 1 FROM list_members WHERE list_id = $1 AND user_id = $2")
+                .bind(list_id)
+                .bind(user_id)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(member.is_some())
+    }
+
+    /// Lists of a page that name the user as a member.
+    pub async fn member_list_ids(&self, page_id: Uuid, user_id: Uuid) -> Result<Vec<Uuid>> {
+        let list_ids: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT m.list_id FROM list_members m
+            JOIN lists l ON l.id = m.list_id
+            WHERE l.page_id = $1 AND m.user_id = $2
+            "#,
+        )
+        .bind(page_id)
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(list_ids)
+    }
+
+    pub async fn set_list_access(
+        &self,
+        id: Uuid,
+        page_id: Uuid,
+        visibility: ListVisibility,
+        min_role: Option<PageRole>,
+        user_ids: &[Uuid],
+    ) -> Result<Option<List>> {
+        let mut tx = self.pool.begin().await?;
+
+        let list = sqlx::query_as::<_, List>(
+            r#"
+            UPDATE lists SET visibility = $1, min_role = $2
+            WHERE id = $3 AND page_id = $4
+            RETURNING *
+            "#,
+        )
+        .bind(visibility)
+        .bind(min_role)
+        .bind(id)
+        .bind(page_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(list) = list else {
+            return Ok(None);
+        };
+
+        sqlx::query("DELETE FROM list_members WHERE list_id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        for user_id in user_ids {
+            sqlx::query(
+                "INSERT INTO list_members (list_id, user_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+            )
+            .bind(id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(Some(list))
+    }
+
     pub async fn list_items_by_list_id(&self, list_id: Uuid) -> Result<Vec<ListItem>> {
         let items = sqlx::query_as::<_, ListItem>(
             "SELECT * FROM list_items WHERE list_id = $1 ORDER BY position ASC, created_at ASC",
@@ -227,6 +324,125 @@ impl ListRepository {
         Ok(())
     }
 
+    pub async fn create_suggestion(
+        &self,
+        list_id: Uuid,
+        data: CreateItemSuggestion,
+    ) -> Result<ItemSuggestion> {
+        let suggestion = sqlx::query_as::<_, ItemSuggestion>(
+            r#"
+            INSERT INTO item_suggestions (id, list_id, content, author_name)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(list_id)
+        .bind(&data.content)
+        .bind(&data.author_name)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(suggestion)
+    }
+
+    /// Pending suggestions on any list of the page, oldest first.
+    pub async fn list_pending_suggestions(&self, page_id: Uuid) -> Result<Vec<ItemSuggestion>> {
+        let suggestions = sqlx::query_as::<_, ItemSuggestion>(
+            r#"
+            SELECT s.* FROM item_suggestions s
+            JOIN lists l ON l.id = s.list_id
+            WHERE l.page_id = $1 AND s.status = 'pending'
+            ORDER BY s.created_at ASC
+            "#,
+        )
+        .bind(page_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(suggestions)
+    }
+
+    pub async fn find_pending_suggestion(
+        &self,
+        id: Uuid,
+        page_id: Uuid,
+    ) -> Result<Option<ItemSuggestion>> {
+        let suggestion = sqlx::query_as::<_, ItemSuggestion>(
+            r#"
+            SELECT s.* FROM item_suggestions s
+            JOIN lists l ON l.id = s.list_id
+            WHERE s.id = $1 AND l.page_id = $2 AND s.status = 'pending'
+            "#,
+        )
+        .bind(id)
+        .bind(page_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(suggestion)
+    }
+
+    /// Add the suggested item to the end of its list and mark the suggestion approved.
+    /// `None` if it was already decided.
+    pub async fn approve_suggestion(&self, id: Uuid, decided_by: Uuid) -> Result<Option<ListItem>> {
+        let mut tx = self.pool.begin().await?;
+
+        let suggestion = sqlx::query_as::<_, ItemSuggestion>(
+            "SELECT * FROM item_suggestions WHERE id = $1 AND status = 'pending'",
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(suggestion) = suggestion else {
+            return Ok(None);
+        };
+
+        let item = sqlx::query_as::<_, ListItem>(
+            r#"
+            INSERT INTO list_items (id, list_id, content, position)
+            VALUES ($1, $2, $3, (SELECT COALESCE(MAX(position), -1) + 1 FROM list_items WHERE list_id = $2))
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(suggestion.list_id)
+        .bind(&suggestion.content)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE item_suggestions
+            SET status = 'approved', item_id = $1, decided_by = $2, decided_at = $3
+            WHERE id = $4
+            "#,
+        )
+        .bind(item.id)
+        .bind(decided_by)
+        .bind(Utc::now())
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(item))
+    }
+
+    pub async fn reject_suggestion(&self, id: Uuid, decided_by: Uuid) -> Result<Option<ItemSuggestion>> {
+        let suggestion = sqlx::query_as::<_, ItemSuggestion>(
+            r#"
+            UPDATE item_suggestions
+            SET status = 'rejected', decided_by = $1, decided_at = $2
+            WHERE id = $3 AND status = 'pending'
+            RETURNING *
+            "#,
+        )
+        .bind(decided_by)
+        .bind(Utc::now())
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(suggestion)
+    }
+
     pub async fn get_page_id_for_list(&self, list_id: Uuid) -> Result<Option<Uuid>> {
         let page_id: Option<Uuid> = sqlx::query_scalar("SELECT page_id FROM lists WHERE id = $1")
             .bind(list_id)
@@ -252,7 +468,7 @@ mod tests {
         // Create user and page
         let creator = user_repo
             .create(CreateUser {
-                twitch_id: "u1".to_string(),
+                twitch_id: Some("u1".to_string()),
                 username: "u1".to_string(),
                 display_name: None,
                 profile_image_url: None,
@@ -339,6 +555,8 @@ mod tests {
                     position: Some(0),
                     show_checkboxes: Some(false),
                     show_progress: Some(false),
+                    public_checking: None,
+                    public_suggestions: None,
                 },
             )
             .await?
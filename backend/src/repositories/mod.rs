@@ -2,8 +2,12 @@ pub mod user;
 pub mod api_key;
 pub mod page;
 pub mod list;
+pub mod session;
+pub mod team;
 
 pub use user::UserRepository;
 pub use api_key::ApiKeyRepository;
 pub use page::PageRepository;
 pub use list::ListRepository;
+pub use session::SessionRepository;
+pub use team::TeamRepository;
@@ -1,11 +1,17 @@
 use crate::models::{
-    CreatePage, Page, PagePermission, PagePermissionWithUser, PageWithPermission, UpdatePage,
+    AccessRequest, AccessRequestWithUser, CreatePage, Page, PageAction, PageInvite, PagePermission, PagePermissionWithUser, PageRole,
+    PageTransfer, PageTransferWithPage, PageWithPermission, PendingPermission, Team, UpdatePage,
     User,
 };
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rand::{distributions::Alphanumeric, Rng};
+use sha2::{Digest, Sha256};
 use sqlx::{SqlitePool, Sqlite, QueryBuilder};
 use uuid::Uuid;
 
+const INVITE_TOKEN_LENGTH: usize = 32;
+
 #[derive(Clone)]
 pub struct PageRepository {
     pool: SqlitePool,
@@ -25,9 +31,22 @@ impl PageRepository {
         .fetch_all(&self.pool)
         .await?;
 
-        // Get pages shared with user
-        let shared_permissions =
-            sqlx::query_as::<_, PagePermission>("SELECT * FROM page_permissions WHERE user_id = $1")
+        // Get pages shared with user, directly or through their teams
+        let shared_permissions = sqlx::query_as::<_, PagePermission>(
+            r#"
+            SELECT * FROM page_permissions
+            WHERE (user_id = $1
+                   OR team_id IN (SELECT team_id FROM team_members WHERE user_id = $1))
+              AND (expires_at IS NULL OR expires_at > $2)
+            "#,
+        )
+        .bind(user_id)
+        .bind(Utc::now())
+        .fetch_all(&self.pool)
+        .await?;
+
+        let hidden: Vec<Uuid> =
+            sqlx::query_scalar("SELECT page_id FROM hidden_pages WHERE user_id = $1")
                 .bind(user_id)
                 .fetch_all(&self.pool)
                 .await?;
@@ -36,22 +55,34 @@ impl PageRepository {
 
         for page in created_pages {
             result.push(PageWithPermission {
+                hidden: hidden.contains(&page.id),
                 page,
                 is_creator: true,
                 can_edit: true,
+                role: PageRole::Owner,
             });
         }
 
         for perm in shared_permissions {
+            // Several grants can reach the same page; the highest role wins
+            if let Some(existing) = result.iter_mut().find(|p| p.page.id == perm.page_id) {
+                if perm.role > existing.role {
+                    existing.role = perm.role;
+                    existing.can_edit = perm.role.allows(PageAction::Edit);
+                }
+                continue;
+            }
             if let Some(page) = sqlx::query_as::<_, Page>("SELECT * FROM pages WHERE id = $1")
                 .bind(perm.page_id)
                 .fetch_optional(&self.pool)
                 .await?
             {
                 result.push(PageWithPermission {
+                    hidden: hidden.contains(&page.id),
                     page,
                     is_creator: false,
-                    can_edit: perm.can_edit,
+                    can_edit: perm.role.allows(PageAction::Edit),
+                    role: perm.role,
                 });
             }
         }
@@ -75,6 +106,45 @@ impl PageRepository {
         Ok(page)
     }
 
+    /// The page that used `slug` before switching to another one; pages that took
+    /// their link down don't count.
+    pub async fn find_by_previous_slug(&self, slug: &str) -> Result<Option<Page>> {
+        let page = sqlx::query_as::<_, Page>(
+            r#"
+            SELECT p.* FROM pages p
+            JOIN public_slug_history h ON h.page_id = p.id
+            WHERE h.slug = $1 AND p.public_slug IS NOT NULL
+            "#,
+        )
+        .bind(slug)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(page)
+    }
+
+    /// Whether a page other than `page_id` released `slug` after `since`.
+    pub async fn slug_reserved_by_other(
+        &self,
+        slug: &str,
+        page_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<bool> {
+        let reserved: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM public_slug_history
+                WHERE slug = $1 AND page_id != $2 AND released_at > $3
+            )
+            "#,
+        )
+        .bind(slug)
+        .bind(page_id)
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(reserved)
+    }
+
     pub async fn create(&self, creator_id: Uuid, data: CreatePage) -> Result<Page> {
         let id = Uuid::new_v4();
         let page = sqlx::query_as::<_, Page>(
@@ -130,14 +200,73 @@ impl PageRepository {
         Ok(())
     }
 
-    pub async fn set_public_slug(&self, id: Uuid, slug: Option<String>) -> Result<Page> {
+    /// Set the public slug along with its protection. `None` leaves the password
+    /// hash or expiry unchanged, `Some(None)` clears it. Removing the slug clears both.
+    /// A replaced slug goes into the page's slug history; the new one leaves it.
+    pub async fn set_public_slug(
+        &self,
+        id: Uuid,
+        slug: Option<String>,
+        password_hash: Option<Option<String>>,
+        expires_at: Option<Option<DateTime<Utc>>>,
+    ) -> Result<Page> {
+        let mut tx = self.pool.begin().await?;
+
+        let previous: Option<String> =
+            sqlx::query_scalar("SELECT public_slug FROM pages WHERE id = $1")
+                .bind(id)
+                .fetch_optional(&mut *tx)
+                .await?
+                .flatten();
+        if let Some(previous) = previous.filter(|p| Some(p) != slug.as_ref()) {
+            sqlx::query(
+                r#"
+                INSERT INTO public_slug_history (slug, page_id, released_at)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (slug) DO UPDATE SET page_id = excluded.page_id, released_at = excluded.released_at
+                "#,
+            )
+            .bind(previous)
+            .bind(id)
+            .bind(Utc::now())
+            .execute(&mut *tx)
+            .await?;
+        }
+        if let Some(ref slug) = slug {
+            sqlx::query("DELETE FROM public_slug_history WHERE slug = $1")
+                .bind(slug)
+                .execute(&mut *tx)
+                .await?;
+        }
+
         let page = sqlx::query_as::<_, Page>(
-            "UPDATE pages SET public_slug = $1 WHERE id = $2 RETURNING *",
+            r#"
+            UPDATE pages SET
+                public_slug = $1,
+                public_password_hash = CASE
+                    WHEN $1 IS NULL THEN NULL
+                    WHEN $2 THEN $3
+                    ELSE public_password_hash
+                END,
+                public_expires_at = CASE
+                    WHEN $1 IS NULL THEN NULL
+                    WHEN $4 THEN $5
+                    ELSE public_expires_at
+                END
+            WHERE id = $6
+            RETURNING *
+            "#,
         )
         .bind(slug)
+        .bind(password_hash.is_some())
+        .bind(password_hash.flatten())
+        .bind(expires_at.is_some())
+        .bind(expires_at.flatten())
         .bind(id)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await?;
+
+        tx.commit().await?;
         Ok(page)
     }
 
@@ -153,48 +282,113 @@ impl PageRepository {
         for permission in permissions {
             let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = $1")
                 .bind(permission.user_id)
-                .fetch_one(&self.pool)
+                .fetch_optional(&self.pool)
                 .await?;
-            result.push(PagePermissionWithUser { permission, user });
+            let team = sqlx::query_as::<_, Team>("SELECT * FROM teams WHERE id = $1")
+                .bind(permission.team_id)
+                .fetch_optional(&self.pool)
+                .await?;
+            result.push(PagePermissionWithUser { permission, user, team });
         }
         Ok(result)
     }
 
+    /// Grant `role` to a user, replacing an expired permission left for the purge task.
     pub async fn grant_permission(
         &self,
         page_id: Uuid,
         user_id: Uuid,
-        can_edit: bool,
+        role: PageRole,
         granted_by: Uuid,
+        expires_at: Option<DateTime<Utc>>,
     ) -> Result<PagePermission> {
         let id = Uuid::new_v4();
         let permission = sqlx::query_as::<_, PagePermission>(
             r#"
-            INSERT INTO page_permissions (id, page_id, user_id, can_edit, granted_by)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO page_permissions (id, page_id, user_id, role, granted_by, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT(page_id, user_id) DO UPDATE SET
+                role = excluded.role,
+                granted_by = excluded.granted_by,
+                expires_at = excluded.expires_at
             RETURNING *
             "#,
         )
         .bind(id)
         .bind(page_id)
         .bind(user_id)
-        .bind(can_edit)
+        .bind(role)
         .bind(granted_by)
+        .bind(expires_at)
         .fetch_one(&self.pool)
         .await?;
         Ok(permission)
     }
 
+    pub async fn grant_team_permission(
+        &self,
+        page_id: Uuid,
+        team_id: Uuid,
+        role: PageRole,
+        granted_by: Uuid,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<PagePermission> {
+        let permission = sqlx::query_as::<_, PagePermission>(
+            r#"
+            INSERT INTO page_permissions (id, page_id, team_id, role, granted_by, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT(page_id, team_id) DO UPDATE SET
+                role = excluded.role,
+                granted_by = excluded.granted_by,
+                expires_at = excluded.expires_at
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(page_id)
+        .bind(team_id)
+        .bind(role)
+        .bind(granted_by)
+        .bind(expires_at)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(permission)
+    }
+
+    pub async fn get_team_permission(
+        &self,
+        page_id: Uuid,
+        team_id: Uuid,
+    ) -> Result<Option<PagePermission>> {
+        let permission = sqlx::query_as::<_, PagePermission>(
+            "SELECT * FROM page_permissions WHERE page_id = $1 AND team_id = $2",
+        )
+        .bind(page_id)
+        .bind(team_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(permission)
+    }
+
     pub async fn update_permission(
         &self,
         page_id: Uuid,
         permission_id: Uuid,
-        can_edit: bool,
+        role: PageRole,
+        expires_at: Option<Option<DateTime<Utc>>>,
     ) -> Result<Option<PagePermission>> {
+        // `expires_at` is only changed when given; `Some(None)` clears it
         let permission = sqlx::query_as::<_, PagePermission>(
-            "UPDATE page_permissions SET can_edit = $1 WHERE id = $2 AND page_id = $3 RETURNING *",
+            r#"
+            UPDATE page_permissions
+            SET role = $1, expires_at = CASE WHEN $2 THEN $3 ELSE expires_at END
+            WHERE id = $4 AND page_id = $5
+            RETURNING *
+            "#,
         )
-        .bind(can_edit)
+        .bind(role)
+        .bind(expires_at.is_some())
+        .bind(expires_at.flatten())
         .bind(permission_id)
         .bind(page_id)
         .fetch_optional(&self.pool)
@@ -202,6 +396,20 @@ impl PageRepository {
         Ok(permission)
     }
 
+    /// Delete permissions whose expiry has passed. Returns how many were removed.
+    pub async fn purge_expired_permissions(&self) -> Result<u64> {
+        let now = Utc::now();
+        let granted = sqlx::query("DELETE FROM page_permissions WHERE expires_at <= $1")
+            .bind(now)
+            .execute(&self.pool)
+            .await?;
+        let pending = sqlx::query("DELETE FROM pending_permissions WHERE expires_at <= $1")
+            .bind(now)
+            .execute(&self.pool)
+            .await?;
+        Ok(granted.rows_affected() + pending.rows_affected())
+    }
+
     pub async fn revoke_permission(&self, page_id: Uuid, permission_id: Uuid) -> Result<()> {
         sqlx::query("DELETE FROM page_permissions WHERE id = $1 AND page_id = $2")
             .bind(permission_id)
@@ -211,6 +419,51 @@ impl PageRepository {
         Ok(())
     }
 
+    /// Drop the user's own grant on a page. Team grants stay; they follow team membership.
+    /// Returns false if the user had no direct permission.
+    pub async fn leave_page(&self, page_id: Uuid, user_id: Uuid) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query("DELETE FROM page_permissions WHERE page_id = $1 AND user_id = $2")
+            .bind(page_id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query("DELETE FROM hidden_pages WHERE page_id = $1 AND user_id = $2")
+            .bind(page_id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn is_hidden(&self, page_id: Uuid, user_id: Uuid) -> Result<bool> {
+        let hidden: Option<i64> =
+            sqlx::query_scalar("SELECT 1 FROM hidden_pages WHERE page_id = $1 AND user_id = $2")
+                .bind(page_id)
+                .bind(user_id)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(hidden.is_some())
+    }
+
+    pub async fn set_hidden(&self, page_id: Uuid, user_id: Uuid, hidden: bool) -> Result<()> {
+        let query = if hidden {
+            "INSERT INTO hidden_pages (page_id, user_id) VALUES ($1, $2) ON CONFLICT DO NOTHING"
+        } else {
+            "DELETE FROM hidden_pages WHERE page_id = $1 AND user_id = $2"
+        };
+        sqlx::query(query)
+            .bind(page_id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
     pub async fn get_user_permission(
         &self,
         page_id: Uuid,
@@ -226,37 +479,488 @@ impl PageRepository {
         Ok(permission)
     }
 
-    pub async fn check_access(&self, page_id: Uuid, user_id: Uuid) -> Result<bool> {
-        let page = self.find_by_id(page_id).await?;
-        if let Some(page) = page {
-            if page.creator_id == user_id {
-                return Ok(true);
+    /// The user's role on a page: `Owner` for the creator, otherwise the highest
+    /// unexpired role granted to them or to one of their teams, `None` without
+    /// access (or if the page doesn't exist).
+    pub async fn get_role(&self, page_id: Uuid, user_id: Uuid) -> Result<Option<PageRole>> {
+        let page = match self.find_by_id(page_id).await? {
+            Some(page) => page,
+            None => return Ok(None),
+        };
+        if page.creator_id == user_id {
+            return Ok(Some(PageRole::Owner));
+        }
+        let roles = sqlx::query_scalar::<_, PageRole>(
+            r#"
+            SELECT role FROM page_permissions
+            WHERE page_id = $1
+              AND (user_id = $2
+                   OR team_id IN (SELECT team_id FROM team_members WHERE user_id = $2))
+              AND (expires_at IS NULL OR expires_at > $3)
+            "#,
+        )
+        .bind(page_id)
+        .bind(user_id)
+        .bind(Utc::now())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(roles.into_iter().max())
+    }
+
+    /// The single permission check for pages and everything on them.
+    pub async fn can(&self, page_id: Uuid, user_id: Uuid, action: PageAction) -> Result<bool> {
+        let role = self.get_role(page_id, user_id).await?;
+        Ok(role.is_some_and(|r| r.allows(action)))
+    }
+
+    /// The user who signed in with `login` on `provider`, matched case-insensitively.
+    /// Only meaningful for [`LOGIN_SHARE_PROVIDERS`](crate::models::LOGIN_SHARE_PROVIDERS).
+    pub async fn find_user_by_login(&self, provider: &str, login: &str) -> Result<Option<User>> {
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            SELECT users.* FROM users
+            JOIN user_identities ON user_identities.user_id = users.id
+            WHERE user_identities.provider = $1 AND user_identities.username = $2 COLLATE NOCASE
+            "#,
+        )
+        .bind(provider)
+        .bind(login)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(user)
+    }
+
+    /// Record (or re-role) a share for someone who hasn't signed in yet.
+    pub async fn grant_pending_permission(
+        &self,
+        page_id: Uuid,
+        provider: &str,
+        login: &str,
+        role: PageRole,
+        granted_by: Uuid,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<PendingPermission> {
+        let pending = sqlx::query_as::<_, PendingPermission>(
+            r#"
+            INSERT INTO pending_permissions (id, page_id, provider, login, role, granted_by, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT(page_id, provider, login) DO UPDATE SET
+                role = excluded.role,
+                expires_at = excluded.expires_at
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(page_id)
+        .bind(provider)
+        .bind(login)
+        .bind(role)
+        .bind(granted_by)
+        .bind(expires_at)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(pending)
+    }
+
+    pub async fn list_pending_permissions(&self, page_id: Uuid) -> Result<Vec<PendingPermission>> {
+        let pending = sqlx::query_as::<_, PendingPermission>(
+            "SELECT * FROM pending_permissions WHERE page_id = $1 ORDER BY created_at",
+        )
+        .bind(page_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(pending)
+    }
+
+    pub async fn revoke_pending_permission(&self, page_id: Uuid, pending_id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM pending_permissions WHERE id = $1 AND page_id = $2")
+            .bind(pending_id)
+            .bind(page_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Offer the page to `to_user_id`, replacing any transfer already pending.
+    pub async fn create_transfer(
+        &self,
+        page_id: Uuid,
+        from_user_id: Uuid,
+        to_user_id: Uuid,
+    ) -> Result<PageTransfer> {
+        let id = Uuid::new_v4();
+        let transfer = sqlx::query_as::<_, PageTransfer>(
+            r#"
+            INSERT INTO page_transfers (id, page_id, from_user_id, to_user_id)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT(page_id) DO UPDATE SET
+                id = excluded.id,
+                from_user_id = excluded.from_user_id,
+                to_user_id = excluded.to_user_id,
+                created_at = CURRENT_TIMESTAMP
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(page_id)
+        .bind(from_user_id)
+        .bind(to_user_id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(transfer)
+    }
+
+    pub async fn find_transfer(&self, page_id: Uuid) -> Result<Option<PageTransfer>> {
+        let transfer = sqlx::query_as::<_, PageTransfer>(
+            "SELECT * FROM page_transfers WHERE page_id = $1",
+        )
+        .bind(page_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(transfer)
+    }
+
+    /// Pending transfers offered to `user_id`, with their pages.
+    pub async fn list_incoming_transfers(&self, user_id: Uuid) -> Result<Vec<PageTransferWithPage>> {
+        let transfers = sqlx::query_as::<_, PageTransfer>(
+            "SELECT * FROM page_transfers WHERE to_user_id = $1 ORDER BY created_at DESC",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut result = Vec::new();
+        for transfer in transfers {
+            if let Some(page) = self.find_by_id(transfer.page_id).await? {
+                result.push(PageTransferWithPage { transfer, page });
             }
-            let has_permission: bool = sqlx::query_scalar(
-                "SELECT EXISTS(SELECT 1 FROM page_permissions WHERE page_id = $1 AND user_id = $2)",
-            )
+        }
+        Ok(result)
+    }
+
+    pub async fn delete_transfer(&self, page_id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM page_transfers WHERE page_id = $1")
             .bind(page_id)
-            .bind(user_id)
-            .fetch_one(&self.pool)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Apply a pending transfer: the recipient becomes creator (dropping their
+    /// own permission row) and the previous creator stays on as an editor.
+    /// Returns `None` if the transfer is gone, the page changed hands meanwhile,
+    /// or the recipient is no longer a direct collaborator.
+    pub async fn accept_transfer(&self, page_id: Uuid, to_user_id: Uuid) -> Result<Option<Page>> {
+        let mut tx = self.pool.begin().await?;
+
+        let transfer = sqlx::query_as::<_, PageTransfer>(
+            r#"
+            DELETE FROM page_transfers
+            WHERE page_id = $1 AND to_user_id = $2
+              AND EXISTS (
+                  SELECT 1 FROM page_permissions
+                  WHERE page_id = $1 AND user_id = $2
+                    AND (expires_at IS NULL OR expires_at > $3)
+              )
+            RETURNING *
+            "#,
+        )
+        .bind(page_id)
+        .bind(to_user_id)
+        .bind(Utc::now())
+        .fetch_optional(&mut *tx)
+        .await?;
+        let transfer = match transfer {
+            Some(t) => t,
+            None => return Ok(None),
+        };
+
+        let page = sqlx::query_as::<_, Page>(
+            "UPDATE pages SET creator_id = $1 WHERE id = $2 AND creator_id = $3 RETURNING *",
+        )
+        .bind(to_user_id)
+        .bind(page_id)
+        .bind(transfer.from_user_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let page = match page {
+            Some(p) => p,
+            None => return Ok(None),
+        };
+
+        sqlx::query("DELETE FROM page_permissions WHERE page_id = $1 AND user_id = $2")
+            .bind(page_id)
+            .bind(to_user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO page_permissions (id, page_id, user_id, role, granted_by)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT(page_id, user_id) DO UPDATE SET
+                role = excluded.role,
+                expires_at = excluded.expires_at
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(page_id)
+        .bind(transfer.from_user_id)
+        .bind(PageRole::Editor)
+        .bind(to_user_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(page))
+    }
+
+    /// Create an invite and return it with its plaintext token, which is not stored.
+    pub async fn create_invite(
+        &self,
+        page_id: Uuid,
+        role: PageRole,
+        max_uses: Option<i64>,
+        expires_at: Option<DateTime<Utc>>,
+        created_by: Uuid,
+    ) -> Result<(PageInvite, String)> {
+        let token: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(INVITE_TOKEN_LENGTH)
+            .map(char::from)
+            .collect();
+        let invite = sqlx::query_as::<_, PageInvite>(
+            r#"
+            INSERT INTO page_invites (id, page_id, token_hash, role, max_uses, expires_at, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(page_id)
+        .bind(hash_invite_token(&token))
+        .bind(role)
+        .bind(max_uses)
+        .bind(expires_at)
+        .bind(created_by)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok((invite, token))
+    }
+
+    pub async fn find_invite_by_token(&self, token: &str) -> Result<Option<PageInvite>> {
+        let invite = sqlx::query_as::<_, PageInvite>(
+            "SELECT * FROM page_invites WHERE token_hash = $1",
+        )
+        .bind(hash_invite_token(token))
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(invite)
+    }
+
+    pub async fn list_invites(&self, page_id: Uuid) -> Result<Vec<PageInvite>> {
+        let invites = sqlx::query_as::<_, PageInvite>(
+            "SELECT * FROM page_invites WHERE page_id = $1 ORDER BY created_at DESC",
+        )
+        .bind(page_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(invites)
+    }
+
+    pub async fn delete_invite(&self, page_id: Uuid, invite_id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM page_invites WHERE id = $1 AND page_id = $2")
+            .bind(invite_id)
+            .bind(page_id)
+            .execute(&self.pool)
             .await?;
-            return Ok(has_permission);
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Use up one redemption of the invite and grant its role to `user_id`.
+    /// Returns `None` if the invite is expired or has no uses left.
+    pub async fn redeem_invite(&self, invite_id: Uuid, user_id: Uuid) -> Result<Option<PagePermission>> {
+        let mut tx = self.pool.begin().await?;
+
+        let invite = sqlx::query_as::<_, PageInvite>(
+            r#"
+            UPDATE page_invites SET uses = uses + 1
+            WHERE id = $1
+              AND (max_uses IS NULL OR uses < max_uses)
+              AND (expires_at IS NULL OR expires_at > $2)
+            RETURNING *
+            "#,
+        )
+        .bind(invite_id)
+        .bind(Utc::now())
+        .fetch_optional(&mut *tx)
+        .await?;
+        let invite = match invite {
+            Some(i) => i,
+            None => return Ok(None),
+        };
+
+        let permission = sqlx::query_as::<_, PagePermission>(
+            r#"
+            INSERT INTO page_permissions (id, page_id, user_id, role, granted_by)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT(page_id, user_id) DO UPDATE SET
+                role = excluded.role,
+                expires_at = excluded.expires_at
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(invite.page_id)
+        .bind(user_id)
+        .bind(invite.role)
+        .bind(invite.created_by)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(permission))
+    }
+
+    /// Open a request, or refresh the message of the one already pending.
+    pub async fn create_access_request(
+        &self,
+        page_id: Uuid,
+        user_id: Uuid,
+        message: Option<String>,
+    ) -> Result<AccessRequest> {
+        let request = sqlx::query_as::<_, AccessRequest>(
+            r#"
+            INSERT INTO access_requests (id, page_id, user_id, message)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT(page_id, user_id) WHERE status = 'pending'
+            DO UPDATE SET message = excluded.message
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(page_id)
+        .bind(user_id)
+        .bind(message)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(request)
+    }
+
+    pub async fn list_pending_access_requests(&self, page_id: Uuid) -> Result<Vec<AccessRequestWithUser>> {
+        let requests = sqlx::query_as::<_, AccessRequest>(
+            "SELECT * FROM access_requests WHERE page_id = $1 AND status = 'pending' ORDER BY created_at",
+        )
+        .bind(page_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut result = Vec::new();
+        for request in requests {
+            let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = $1")
+                .bind(request.user_id)
+                .fetch_one(&self.pool)
+                .await?;
+            result.push(AccessRequestWithUser { request, user });
         }
-        Ok(false)
+        Ok(result)
     }
 
-    pub async fn check_edit_permission(&self, page_id: Uuid, user_id: Uuid) -> Result<bool> {
-        let page = self.find_by_id(page_id).await?;
-        if let Some(page) = page {
-            if page.creator_id == user_id {
-                return Ok(true);
-            }
-            let permission = self.get_user_permission(page_id, user_id).await?;
-            return Ok(permission.map(|p| p.can_edit).unwrap_or(false));
+    /// Approve a pending request and grant (or raise to) `role` for its author.
+    /// Approved access doesn't expire: a lapsed or lower permission is replaced, while
+    /// one already at `role` or above is left as it is, expiry included.
+    pub async fn approve_access_request(
+        &self,
+        page_id: Uuid,
+        request_id: Uuid,
+        role: PageRole,
+        decided_by: Uuid,
+    ) -> Result<Option<PagePermission>> {
+        let mut tx = self.pool.begin().await?;
+
+        let request = sqlx::query_as::<_, AccessRequest>(
+            r#"
+            UPDATE access_requests
+            SET status = 'approved', role = $1, decided_by = $2, decided_at = $3
+            WHERE id = $4 AND page_id = $5 AND status = 'pending'
+            RETURNING *
+            "#,
+        )
+        .bind(role)
+        .bind(decided_by)
+        .bind(Utc::now())
+        .bind(request_id)
+        .bind(page_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let request = match request {
+            Some(r) => r,
+            None => return Ok(None),
+        };
+
+        let existing = sqlx::query_as::<_, PagePermission>(
+            "SELECT * FROM page_permissions WHERE page_id = $1 AND user_id = $2",
+        )
+        .bind(page_id)
+        .bind(request.user_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        if let Some(existing) = existing.filter(|p| !p.is_expired() && p.role >= role) {
+            tx.commit().await?;
+            return Ok(Some(existing));
         }
-        Ok(false)
+
+        let permission = sqlx::query_as::<_, PagePermission>(
+            r#"
+            INSERT INTO page_permissions (id, page_id, user_id, role, granted_by)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT(page_id, user_id) DO UPDATE SET
+                role = excluded.role,
+                expires_at = NULL
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(page_id)
+        .bind(request.user_id)
+        .bind(role)
+        .bind(decided_by)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(permission))
+    }
+
+    pub async fn deny_access_request(
+        &self,
+        page_id: Uuid,
+        request_id: Uuid,
+        decided_by: Uuid,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE access_requests
+            SET status = 'denied', decided_by = $1, decided_at = $2
+            WHERE id = $3 AND page_id = $4 AND status = 'pending'
+            "#,
+        )
+        .bind(decided_by)
+        .bind(Utc::now())
+        .bind(request_id)
+        .bind(page_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
     }
 }
 
+fn hash_invite_token(token: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(token.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -272,7 +976,7 @@ mod tests {
         // Create creator and another user
         let creator = user_repo
             .create(CreateUser {
-                twitch_id: "c1".to_string(),
+                twitch_id: Some("c1".to_string()),
                 username: "creator".to_string(),
                 display_name: None,
                 profile_image_url: None,
@@ -282,7 +986,7 @@ mod tests {
 
         let other = user_repo
             .create(CreateUser {
-                twitch_id: "u2".to_string(),
+                twitch_id: Some("u2".to_string()),
                 username: "other".to_string(),
                 display_name: None,
                 profile_image_url: None,
@@ -305,38 +1009,109 @@ mod tests {
 
         // Set public slug
         let page = page_repo
-            .set_public_slug(page.id, Some("slug-1".to_string()))
+            .set_public_slug(page.id, Some("slug-1".to_string()), None, None)
             .await?;
         assert_eq!(page.public_slug.as_deref(), Some("slug-1"));
 
         // Grant permission
         let perm = page_repo
-            .grant_permission(page.id, other.id, true, creator.id)
+            .grant_permission(page.id, other.id, PageRole::Editor, creator.id, None)
             .await?;
-        assert_eq!(perm.user_id, other.id);
-        assert!(perm.can_edit);
+        assert_eq!(perm.user_id, Some(other.id));
+        assert_eq!(perm.role, PageRole::Editor);
 
         // Permissions listing
         let perms = page_repo.get_permissions(page.id).await?;
         assert_eq!(perms.len(), 1);
-        assert_eq!(perms[0].user.id, other.id);
+        assert_eq!(perms[0].user.as_ref().map(|u| u.id), Some(other.id));
 
         // Access checks
-        assert!(page_repo.check_access(page.id, creator.id).await?);
-        assert!(page_repo.check_access(page.id, other.id).await?);
-        assert!(page_repo.check_edit_permission(page.id, other.id).await?);
+        assert_eq!(page_repo.get_role(page.id, creator.id).await?, Some(PageRole::Owner));
+        assert!(page_repo.can(page.id, creator.id, PageAction::Delete).await?);
+        assert!(page_repo.can(page.id, other.id, PageAction::View).await?);
+        assert!(page_repo.can(page.id, other.id, PageAction::Edit).await?);
+        assert!(!page_repo.can(page.id, other.id, PageAction::Share).await?);
 
         // Update permission
         let updated_perm = page_repo
-            .update_permission(page.id, perm.id, false)
+            .update_permission(page.id, perm.id, PageRole::Checker, None)
             .await?
             .expect("permission should exist");
-        assert!(!updated_perm.can_edit);
-        assert!(!page_repo.check_edit_permission(page.id, other.id).await?);
+        assert_eq!(updated_perm.role, PageRole::Checker);
+        assert!(page_repo.can(page.id, other.id, PageAction::CheckItems).await?);
+        assert!(!page_repo.can(page.id, other.id, PageAction::Edit).await?);
 
         // Revoke permission
         page_repo.revoke_permission(page.id, perm.id).await?;
-        assert!(!page_repo.check_access(page.id, other.id).await?);
+        assert!(!page_repo.can(page.id, other.id, PageAction::View).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_permission_expiry() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let user_repo = crate::repositories::UserRepository::new(pool.clone());
+        let page_repo = PageRepository::new(pool.clone());
+
+        let creator = user_repo
+            .create(CreateUser {
+                twitch_id: Some("c1".to_string()),
+                username: "creator".to_string(),
+                display_name: None,
+                profile_image_url: None,
+                email: None,
+            })
+            .await?;
+        let guest = user_repo
+            .create(CreateUser {
+                twitch_id: Some("g1".to_string()),
+                username: "guest".to_string(),
+                display_name: None,
+                profile_image_url: None,
+                email: None,
+            })
+            .await?;
+        let page = page_repo
+            .create(
+                creator.id,
+                CreatePage {
+                    title: "Stream".to_string(),
+                    description: None,
+                },
+            )
+            .await?;
+
+        // Still valid
+        let tomorrow = Utc::now() + chrono::Duration::days(1);
+        let perm = page_repo
+            .grant_permission(page.id, guest.id, PageRole::Viewer, creator.id, Some(tomorrow))
+            .await?;
+        assert!(page_repo.can(page.id, guest.id, PageAction::View).await?);
+        assert_eq!(page_repo.list_for_user(guest.id).await?.len(), 1);
+        assert_eq!(page_repo.purge_expired_permissions().await?, 0);
+
+        // Lapsed: no access and not listed, but kept until purged
+        let yesterday = Utc::now() - chrono::Duration::days(1);
+        page_repo
+            .update_permission(page.id, perm.id, PageRole::Viewer, Some(Some(yesterday)))
+            .await?;
+        assert_eq!(page_repo.get_role(page.id, guest.id).await?, None);
+        assert!(page_repo.list_for_user(guest.id).await?.is_empty());
+        assert!(page_repo.get_user_permission(page.id, guest.id).await?.is_some_and(|p| p.is_expired()));
+
+        // Granting again replaces the expired row
+        let regranted = page_repo
+            .grant_permission(page.id, guest.id, PageRole::Editor, creator.id, None)
+            .await?;
+        assert_eq!(regranted.expires_at, None);
+        assert!(page_repo.can(page.id, guest.id, PageAction::Edit).await?);
+
+        page_repo
+            .update_permission(page.id, regranted.id, PageRole::Editor, Some(Some(yesterday)))
+            .await?;
+        assert_eq!(page_repo.purge_expired_permissions().await?, 1);
+        assert!(page_repo.get_user_permission(page.id, guest.id).await?.is_none());
 
         Ok(())
     }
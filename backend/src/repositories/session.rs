@@ -0,0 +1,152 @@
+use crate::models::Session;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct SessionRepository {
+    pool: SqlitePool,
+}
+
+impl SessionRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        refresh_token_hash: &str,
+        expires_at: DateTime<Utc>,
+        user_agent: Option<&str>,
+        ip: Option<&str>,
+    ) -> Result<Session> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+        let session = sqlx::query_as::<_, Session>(
+            r#"
+            INSERT INTO sessions (id, user_id, refresh_token_hash, user_agent, ip, created_at, last_seen_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(&id)
+        .bind(user_id)
+        .bind(refresh_token_hash)
+        .bind(user_agent)
+        .bind(ip)
+        .bind(now)
+        .bind(expires_at)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(session)
+    }
+
+    pub async fn find_by_id(&self, id: &str) -> Result<Option<Session>> {
+        let session = sqlx::query_as::<_, Session>("SELECT * FROM sessions WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(session)
+    }
+
+    /// Look a session up by its current or previous refresh token hash.
+    pub async fn find_by_refresh_token_hash(&self, hash: &str) -> Result<Option<Session>> {
+        let session = sqlx::query_as::<_, Session>(
+            "SELECT * FROM sessions WHERE refresh_token_hash = $1 OR previous_refresh_token_hash = $1",
+        )
+        .bind(hash)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(session)
+    }
+
+    /// Replace the refresh token hash, but only if it is still `current_hash`;
+    /// a concurrent refresh that already rotated it makes this return `None`.
+    pub async fn rotate(
+        &self,
+        id: &str,
+        current_hash: &str,
+        new_hash: &str,
+        user_agent: Option<&str>,
+        ip: Option<&str>,
+    ) -> Result<Option<Session>> {
+        let session = sqlx::query_as::<_, Session>(
+            r#"
+            UPDATE sessions
+            SET previous_refresh_token_hash = refresh_token_hash,
+                refresh_token_hash = $1,
+                last_seen_at = $2,
+                user_agent = COALESCE($3, user_agent),
+                ip = COALESCE($4, ip)
+            WHERE id = $5 AND refresh_token_hash = $6 AND revoked_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(new_hash)
+        .bind(Utc::now())
+        .bind(user_agent)
+        .bind(ip)
+        .bind(id)
+        .bind(current_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(session)
+    }
+
+    pub async fn revoke(&self, id: &str) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE sessions SET revoked_at = $1 WHERE id = $2 AND revoked_at IS NULL",
+        )
+        .bind(Utc::now())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Sessions a user can still be signed in with, most recently used first.
+    pub async fn list_active_by_user(&self, user_id: Uuid) -> Result<Vec<Session>> {
+        let sessions = sqlx::query_as::<_, Session>(
+            r#"
+            SELECT * FROM sessions
+            WHERE user_id = $1 AND revoked_at IS NULL AND expires_at > $2
+            ORDER BY last_seen_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .bind(Utc::now())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(sessions)
+    }
+
+    pub async fn revoke_for_user(&self, id: &str, user_id: Uuid) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE sessions SET revoked_at = $1 WHERE id = $2 AND user_id = $3 AND revoked_at IS NULL",
+        )
+        .bind(Utc::now())
+        .bind(id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Revoke every session of `user_id` except `keep`.
+    pub async fn revoke_all_for_user_except(&self, user_id: Uuid, keep: &str) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE sessions SET revoked_at = $1
+            WHERE user_id = $2 AND revoked_at IS NULL AND id != $3
+            "#,
+        )
+        .bind(Utc::now())
+        .bind(user_id)
+        .bind(keep)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
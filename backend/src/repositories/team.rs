@@ -0,0 +1,313 @@
+use crate::models::{
+    Team, TeamInvitation, TeamInvitationWithTeam, TeamMember, TeamMemberWithUser, TeamRole,
+    TeamWithRole, User,
+};
+use anyhow::Result;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct TeamRepository {
+    pool: SqlitePool,
+}
+
+impl TeamRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Create a team with its creator as the owner member.
+    pub async fn create(&self, name: &str, created_by: Uuid) -> Result<Team> {
+        let mut tx = self.pool.begin().await?;
+
+        let team = sqlx::query_as::<_, Team>(
+            "INSERT INTO teams (id, name, created_by) VALUES ($1, $2, $3) RETURNING *",
+        )
+        .bind(Uuid::new_v4())
+        .bind(name)
+        .bind(created_by)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query("INSERT INTO team_members (id, team_id, user_id, role) VALUES ($1, $2, $3, $4)")
+            .bind(Uuid::new_v4())
+            .bind(team.id)
+            .bind(created_by)
+            .bind(TeamRole::Owner)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(team)
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<Team>> {
+        let team = sqlx::query_as::<_, Team>("SELECT * FROM teams WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(team)
+    }
+
+    /// Teams the user belongs to, with their role in each.
+    pub async fn list_for_user(&self, user_id: Uuid) -> Result<Vec<TeamWithRole>> {
+        let rows = sqlx::query_as::<_, (Uuid, TeamRole)>(
+            r#"
+            SELECT team_members.team_id, team_members.role FROM team_members
+            JOIN teams ON teams.id = team_members.team_id
+            WHERE team_members.user_id = $1
+            ORDER BY teams.name COLLATE NOCASE
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut result = Vec::new();
+        for (team_id, role) in rows {
+            if let Some(team) = self.find_by_id(team_id).await? {
+                result.push(TeamWithRole { team, role });
+            }
+        }
+        Ok(result)
+    }
+
+    pub async fn rename(&self, id: Uuid, name: &str) -> Result<Option<Team>> {
+        let team = sqlx::query_as::<_, Team>("UPDATE teams SET name = $1 WHERE id = $2 RETURNING *")
+            .bind(name)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(team)
+    }
+
+    pub async fn delete(&self, id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM teams WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get_role(&self, team_id: Uuid, user_id: Uuid) -> Result<Option<TeamRole>> {
+        let role = sqlx::query_scalar::<_, TeamRole>(
+            "SELECT role FROM team_members WHERE team_id = $1 AND user_id = $2",
+        )
+        .bind(team_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(role)
+    }
+
+    pub async fn list_members(&self, team_id: Uuid) -> Result<Vec<TeamMemberWithUser>> {
+        let members = sqlx::query_as::<_, TeamMember>(
+            "SELECT * FROM team_members WHERE team_id = $1 ORDER BY created_at",
+        )
+        .bind(team_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut result = Vec::new();
+        for member in members {
+            let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = $1")
+                .bind(member.user_id)
+                .fetch_one(&self.pool)
+                .await?;
+            result.push(TeamMemberWithUser { member, user });
+        }
+        Ok(result)
+    }
+
+    /// Invite a user, or refresh the role of an invitation they haven't answered.
+    /// Returns `None` if the user doesn't exist.
+    pub async fn create_invitation(
+        &self,
+        team_id: Uuid,
+        user_id: Uuid,
+        role: TeamRole,
+        invited_by: Uuid,
+    ) -> Result<Option<TeamInvitation>> {
+        let invitation = sqlx::query_as::<_, TeamInvitation>(
+            r#"
+            INSERT INTO team_invitations (id, team_id, user_id, role, invited_by)
+            SELECT $1, $2, $3, $4, $5
+            WHERE EXISTS (SELECT 1 FROM users WHERE id = $3)
+            ON CONFLICT(team_id, user_id) DO UPDATE SET
+                role = excluded.role,
+                invited_by = excluded.invited_by,
+                created_at = CURRENT_TIMESTAMP
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(team_id)
+        .bind(user_id)
+        .bind(role)
+        .bind(invited_by)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(invitation)
+    }
+
+    pub async fn list_invitations(&self, team_id: Uuid) -> Result<Vec<TeamInvitation>> {
+        let invitations = sqlx::query_as::<_, TeamInvitation>(
+            "SELECT * FROM team_invitations WHERE team_id = $1 ORDER BY created_at",
+        )
+        .bind(team_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(invitations)
+    }
+
+    /// Pending invitations for `user_id`, with their teams.
+    pub async fn list_incoming_invitations(&self, user_id: Uuid) -> Result<Vec<TeamInvitationWithTeam>> {
+        let invitations = sqlx::query_as::<_, TeamInvitation>(
+            "SELECT * FROM team_invitations WHERE user_id = $1 ORDER BY created_at DESC",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut result = Vec::new();
+        for invitation in invitations {
+            if let Some(team) = self.find_by_id(invitation.team_id).await? {
+                result.push(TeamInvitationWithTeam { invitation, team });
+            }
+        }
+        Ok(result)
+    }
+
+    pub async fn delete_invitation(&self, team_id: Uuid, user_id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM team_invitations WHERE team_id = $1 AND user_id = $2")
+            .bind(team_id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Turn a pending invitation into a membership with the invited role.
+    /// Returns `None` if there is no invitation or the user already joined.
+    pub async fn accept_invitation(&self, team_id: Uuid, user_id: Uuid) -> Result<Option<TeamMember>> {
+        let mut tx = self.pool.begin().await?;
+
+        let invitation = sqlx::query_as::<_, TeamInvitation>(
+            "DELETE FROM team_invitations WHERE team_id = $1 AND user_id = $2 RETURNING *",
+        )
+        .bind(team_id)
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let invitation = match invitation {
+            Some(i) => i,
+            None => return Ok(None),
+        };
+
+        let member = sqlx::query_as::<_, TeamMember>(
+            r#"
+            INSERT INTO team_members (id, team_id, user_id, role)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT(team_id, user_id) DO NOTHING
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(team_id)
+        .bind(user_id)
+        .bind(invitation.role)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(member)
+    }
+
+    pub async fn update_member(
+        &self,
+        team_id: Uuid,
+        user_id: Uuid,
+        role: TeamRole,
+    ) -> Result<Option<TeamMember>> {
+        let member = sqlx::query_as::<_, TeamMember>(
+            "UPDATE team_members SET role = $1 WHERE team_id = $2 AND user_id = $3 RETURNING *",
+        )
+        .bind(role)
+        .bind(team_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(member)
+    }
+
+    pub async fn remove_member(&self, team_id: Uuid, user_id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM team_members WHERE team_id = $1 AND user_id = $2")
+            .bind(team_id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CreateUser;
+    use crate::repositories::UserRepository;
+    use crate::tests_utils::setup_db;
+
+    #[tokio::test]
+    async fn test_team_membership() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let user_repo = UserRepository::new(pool.clone());
+        let team_repo = TeamRepository::new(pool.clone());
+
+        let owner = user_repo
+            .create(CreateUser {
+                twitch_id: Some("tw1".to_string()),
+                username: "owner".to_string(),
+                display_name: None,
+                profile_image_url: None,
+                email: None,
+            })
+            .await?;
+        let member = user_repo
+            .create(CreateUser {
+                twitch_id: Some("tw2".to_string()),
+                username: "member".to_string(),
+                display_name: None,
+                profile_image_url: None,
+                email: None,
+            })
+            .await?;
+
+        let team = team_repo.create("Mods", owner.id).await?;
+        assert_eq!(team_repo.get_role(team.id, owner.id).await?, Some(TeamRole::Owner));
+
+        // Nobody joins until they accept
+        team_repo
+            .create_invitation(team.id, member.id, TeamRole::Member, owner.id)
+            .await?
+            .unwrap();
+        assert!(team_repo.create_invitation(team.id, Uuid::new_v4(), TeamRole::Member, owner.id).await?.is_none());
+        assert_eq!(team_repo.list_members(team.id).await?.len(), 1);
+        assert_eq!(team_repo.list_incoming_invitations(member.id).await?[0].team.name, "Mods");
+
+        assert!(team_repo.accept_invitation(team.id, member.id).await?.is_some());
+        assert_eq!(team_repo.list_members(team.id).await?.len(), 2);
+        assert_eq!(team_repo.list_for_user(member.id).await?[0].team.name, "Mods");
+        assert!(team_repo.list_incoming_invitations(member.id).await?.is_empty());
+
+        // An invitation is used up once accepted
+        assert!(team_repo.accept_invitation(team.id, member.id).await?.is_none());
+
+        team_repo.update_member(team.id, member.id, TeamRole::Admin).await?;
+        assert_eq!(team_repo.get_role(team.id, member.id).await?, Some(TeamRole::Admin));
+
+        assert!(team_repo.remove_member(team.id, member.id).await?);
+        assert!(team_repo.list_for_user(member.id).await?.is_empty());
+
+        Ok(())
+    }
+}
@@ -1,5 +1,8 @@
-use crate::models::{CreateUser, User};
+use crate::models::{
+    CreateUser, ExternalIdentity, PendingPermission, User, UserIdentity, LOGIN_SHARE_PROVIDERS,
+};
 use anyhow::Result;
+use chrono::Utc;
 use sqlx::SqlitePool;
 use uuid::Uuid;
 
@@ -13,16 +16,8 @@ impl UserRepository {
         Self { pool }
     }
 
-    pub async fn find_by_twitch_id(&self, twitch_id: &str) -> Result<Option<User>> {
-        let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE twitch_id = $1")
-            .bind(twitch_id)
-            .fetch_optional(&self.pool)
-            .await?;
-        Ok(user)
-    }
-
-
-
+    /// Create a user without an identity; sign-ups go through `create_with_identity`.
+    #[allow(dead_code)]
     pub async fn create(&self, user: CreateUser) -> Result<User> {
         let id = Uuid::new_v4();
         let user = sqlx::query_as::<_, User>(
@@ -43,32 +38,6 @@ impl UserRepository {
         Ok(user)
     }
 
-    pub async fn update_twitch_info(
-        &self,
-        twitch_id: &str,
-        username: &str,
-        display_name: &str,
-        profile_image_url: &str,
-        email: Option<String>,
-    ) -> Result<User> {
-        let user = sqlx::query_as::<_, User>(
-            r#"
-            UPDATE users
-            SET username = $1, display_name = $2, profile_image_url = $3, email = $4
-            WHERE twitch_id = $5
-            RETURNING *
-            "#,
-        )
-        .bind(username)
-        .bind(display_name)
-        .bind(profile_image_url)
-        .bind(email)
-        .bind(twitch_id)
-        .fetch_one(&self.pool)
-        .await?;
-        Ok(user)
-    }
-
     pub async fn find_by_id(&self, id: Uuid) -> Result<Option<User>> {
         let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = $1")
             .bind(id)
@@ -119,6 +88,178 @@ impl UserRepository {
         .await?;
         Ok(users)
     }
+
+    /// The user an external identity is linked to, if any.
+    pub async fn find_by_identity(&self, provider: &str, subject: &str) -> Result<Option<User>> {
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            SELECT users.* FROM users
+            JOIN user_identities ON user_identities.user_id = users.id
+            WHERE user_identities.provider = $1 AND user_identities.subject = $2
+            "#,
+        )
+        .bind(provider)
+        .bind(subject)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(user)
+    }
+
+    /// Create a user together with the identity they signed up with.
+    pub async fn create_with_identity(
+        &self,
+        user: CreateUser,
+        identity: &ExternalIdentity,
+    ) -> Result<User> {
+        let mut tx = self.pool.begin().await?;
+
+        let id = Uuid::new_v4();
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            INSERT INTO users (id, twitch_id, username, display_name, profile_image_url, email)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(&user.twitch_id)
+        .bind(&user.username)
+        .bind(&user.display_name)
+        .bind(&user.profile_image_url)
+        .bind(&user.email)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO user_identities (id, user_id, provider, subject, username, email)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(user.id)
+        .bind(&identity.provider)
+        .bind(&identity.subject)
+        .bind(&identity.username)
+        .bind(&identity.email)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(user)
+    }
+
+    pub async fn add_identity(&self, user_id: Uuid, identity: &ExternalIdentity) -> Result<UserIdentity> {
+        let identity = sqlx::query_as::<_, UserIdentity>(
+            r#"
+            INSERT INTO user_identities (id, user_id, provider, subject, username, email)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(user_id)
+        .bind(&identity.provider)
+        .bind(&identity.subject)
+        .bind(&identity.username)
+        .bind(&identity.email)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(identity)
+    }
+
+    /// Refresh the provider-side username/email recorded for an identity.
+    pub async fn update_identity(&self, identity: &ExternalIdentity) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE user_identities SET username = $1, email = $2
+            WHERE provider = $3 AND subject = $4
+            "#,
+        )
+        .bind(&identity.username)
+        .bind(&identity.email)
+        .bind(&identity.provider)
+        .bind(&identity.subject)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn list_identities(&self, user_id: Uuid) -> Result<Vec<UserIdentity>> {
+        let identities = sqlx::query_as::<_, UserIdentity>(
+            "SELECT * FROM user_identities WHERE user_id = $1 ORDER BY created_at",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(identities)
+    }
+
+    /// Unlink an identity, unless it is the user's last way to sign in.
+    pub async fn delete_identity(&self, id: &str, user_id: Uuid) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM user_identities
+            WHERE id = $1 AND user_id = $2
+            AND (SELECT COUNT(*) FROM user_identities WHERE user_id = $2) > 1
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Turn shares waiting for this identity's login into real permissions.
+    /// Pages the user already has access to (or owns) are left as they are.
+    pub async fn claim_pending_permissions(
+        &self,
+        user_id: Uuid,
+        identity: &ExternalIdentity,
+    ) -> Result<u64> {
+        if !LOGIN_SHARE_PROVIDERS.contains(&identity.provider.as_str()) {
+            return Ok(0);
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        let pending = sqlx::query_as::<_, PendingPermission>(
+            "DELETE FROM pending_permissions WHERE provider = $1 AND login = $2 RETURNING *",
+        )
+        .bind(&identity.provider)
+        .bind(&identity.username)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut granted = 0;
+        for p in pending {
+            // Lapsed before the login ever showed up
+            if p.expires_at.is_some_and(|t| t <= Utc::now()) {
+                continue;
+            }
+            let result = sqlx::query(
+                r#"
+                INSERT INTO page_permissions (id, page_id, user_id, role, granted_by, expires_at)
+                SELECT $1, $2, $3, $4, $5, $6
+                WHERE NOT EXISTS (SELECT 1 FROM pages WHERE id = $2 AND creator_id = $3)
+                ON CONFLICT(page_id, user_id) DO NOTHING
+                "#,
+            )
+            .bind(Uuid::new_v4())
+            .bind(p.page_id)
+            .bind(user_id)
+            .bind(p.role)
+            .bind(p.granted_by)
+            .bind(p.expires_at)
+            .execute(&mut *tx)
+            .await?;
+            granted += result.rows_affected();
+        }
+
+        tx.commit().await?;
+        Ok(granted)
+    }
 }
 
 #[cfg(test)]
@@ -134,7 +275,7 @@ mod tests {
 
         // Create user
         let create = CreateUser {
-            twitch_id: "t123".to_string(),
+            twitch_id: Some("t123".to_string()),
             username: "tester".to_string(),
             display_name: Some("Test User".to_string()),
             profile_image_url: None,
@@ -142,22 +283,22 @@ mod tests {
         };
 
         let user = repo.create(create).await?;
-        assert_eq!(user.twitch_id, "t123");
+        assert_eq!(user.twitch_id.as_deref(), Some("t123"));
         assert_eq!(user.username, "tester");
 
-        // Find by twitch id
-        let found = repo.find_by_twitch_id(&user.twitch_id).await?;
+        // Find by id
+        let found = repo.find_by_id(user.id).await?;
         assert!(found.is_some());
         let found = found.unwrap();
         assert_eq!(found.id, user.id);
 
-        // Update twitch info
+        // Update profile
         let updated = repo
-            .update_twitch_info(
-                &user.twitch_id,
+            .update_by_id(
+                user.id,
                 "tester2",
-                "Tester 2",
-                "http://image",
+                Some("Tester 2"),
+                Some("http://image"),
                 None,
             )
             .await?;
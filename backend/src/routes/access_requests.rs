@@ -0,0 +1,369 @@
+use crate::error::AppError;
+use crate::models::{
+    AccessRequest, AccessRequestWithUser, ApproveAccessRequest, Claims, CreateAccessRequest,
+    PageAction, PagePermission, PageRole, Scope,
+};
+use crate::middleware::{require_page, require_scope};
+use crate::repositories::PageRepository;
+use crate::validators::validate_access_request_message;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Extension, Router,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct AccessRequestsRouterState {
+    pub page_repo: Arc<PageRepository>,
+}
+
+pub fn access_requests_router(state: AccessRequestsRouterState) -> Router {
+    Router::new()
+        .route(
+            "/pages/:id/access-requests",
+            get(list_access_requests).post(create_access_request),
+        )
+        .route(
+            "/pages/:id/access-requests/:request_id/approve",
+            post(approve_access_request),
+        )
+        .route(
+            "/pages/:id/access-requests/:request_id/deny",
+            post(deny_access_request),
+        )
+        .with_state(state)
+}
+
+// Ask for access to a page the user can't open
+async fn create_access_request(
+    State(state): State<AccessRequestsRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path(page_id): Path<Uuid>,
+    Json(payload): Json<CreateAccessRequest>,
+) -> Result<Json<AccessRequest>, AppError> {
+    require_scope(&claims, Scope::PagesRead)?;
+    require_page(&claims, page_id, false)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    let message = validate_access_request_message(&payload.message)?;
+
+    state
+        .page_repo
+        .find_by_id(page_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    if state.page_repo.get_role(page_id, user_id).await?.is_some() {
+        return Err(AppError::BadRequest(
+            "You already have access to this page".to_string(),
+        ));
+    }
+
+    let request = state
+        .page_repo
+        .create_access_request(page_id, user_id, message)
+        .await?;
+
+    Ok(Json(request))
+}
+
+// Open requests, for the owner and managers
+async fn list_access_requests(
+    State(state): State<AccessRequestsRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path(page_id): Path<Uuid>,
+) -> Result<Json<Vec<AccessRequestWithUser>>, AppError> {
+    require_scope(&claims, Scope::SharingAdmin)?;
+    require_page(&claims, page_id, true)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    // Sharing is for managers and the owner
+    state
+        .page_repo
+        .find_by_id(page_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    if !state.page_repo.can(page_id, user_id, PageAction::Share).await? {
+        return Err(AppError::Forbidden);
+    }
+
+    let requests = state.page_repo.list_pending_access_requests(page_id).await?;
+
+    Ok(Json(requests))
+}
+
+// Approving grants the chosen role right away
+async fn approve_access_request(
+    State(state): State<AccessRequestsRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path((page_id, request_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<ApproveAccessRequest>,
+) -> Result<Json<PagePermission>, AppError> {
+    require_scope(&claims, Scope::SharingAdmin)?;
+    require_page(&claims, page_id, true)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    // Sharing is for managers and the owner
+    state
+        .page_repo
+        .find_by_id(page_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    if !state.page_repo.can(page_id, user_id, PageAction::Share).await? {
+        return Err(AppError::Forbidden);
+    }
+
+    let role = payload.role.unwrap_or(PageRole::Viewer);
+    if role == PageRole::Owner {
+        return Err(AppError::BadRequest(
+            "The owner role cannot be granted".to_string(),
+        ));
+    }
+
+    let permission = state
+        .page_repo
+        .approve_access_request(page_id, request_id, role, user_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    Ok(Json(permission))
+}
+
+async fn deny_access_request(
+    State(state): State<AccessRequestsRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path((page_id, request_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    require_scope(&claims, Scope::SharingAdmin)?;
+    require_page(&claims, page_id, true)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    // Sharing is for managers and the owner
+    state
+        .page_repo
+        .find_by_id(page_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    if !state.page_repo.can(page_id, user_id, PageAction::Share).await? {
+        return Err(AppError::Forbidden);
+    }
+
+    if !state
+        .page_repo
+        .deny_access_request(page_id, request_id, user_id)
+        .await?
+    {
+        return Err(AppError::NotFound);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CreatePage, CreateUser, User};
+    use crate::repositories::UserRepository;
+    use crate::tests_utils::setup_db;
+    use axum::body::{self, Body};
+    use axum::http::{Method, Request};
+    use tower::util::ServiceExt;
+
+    fn claims_for(user: &User) -> Claims {
+        Claims {
+            sub: user.id.to_string(),
+            username: user.username.clone(),
+            exp: 9999999999,
+            scopes: None,
+            pages: None,
+            sid: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_access_request_approve_and_deny() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let user_repo = UserRepository::new(pool.clone());
+        let page_repo = Arc::new(PageRepository::new(pool.clone()));
+
+        let mut users = Vec::new();
+        for name in ["owner", "asker", "stranger"] {
+            users.push(
+                user_repo
+                    .create(CreateUser {
+                        twitch_id: Some(format!("tw_{}", name)),
+                        username: name.to_string(),
+                        display_name: None,
+                        profile_image_url: None,
+                        email: None,
+                    })
+                    .await?,
+            );
+        }
+        let (owner, asker, stranger) = (&users[0], &users[1], &users[2]);
+        let page = page_repo
+            .create(owner.id, CreatePage {
+                title: "Private".to_string(),
+                description: None,
+            })
+            .await?;
+
+        let app = access_requests_router(AccessRequestsRouterState {
+            page_repo: page_repo.clone(),
+        });
+        let ask = |user: &User| -> anyhow::Result<Request<Body>> {
+            Ok(Request::builder()
+                .method(Method::POST)
+                .uri(format!("/pages/{}/access-requests", page.id))
+                .header("content-type", "application/json")
+                .extension(claims_for(user))
+                .body(Body::from(serde_json::to_vec(&serde_json::json!({
+                    "message": "Can I help with this list?"
+                }))?))?)
+        };
+
+        // Asking twice keeps a single open request
+        for _ in 0..2 {
+            let resp = app.clone().oneshot(ask(asker)?).await.unwrap();
+            assert_eq!(resp.status(), 200);
+        }
+        let resp = app.clone().oneshot(ask(stranger)?).await.unwrap();
+        assert_eq!(resp.status(), 200);
+
+        // Only sharers see the queue
+        let list = |user: &User| {
+            Request::builder()
+                .method(Method::GET)
+                .uri(format!("/pages/{}/access-requests", page.id))
+                .extension(claims_for(user))
+                .body(Body::empty())
+        };
+        let resp = app.clone().oneshot(list(asker)?).await.unwrap();
+        assert_eq!(resp.status(), 403);
+        let resp = app.clone().oneshot(list(owner)?).await.unwrap();
+        let bytes = body::to_bytes(resp.into_body(), 64 * 1024).await?;
+        let requests: Vec<serde_json::Value> = serde_json::from_slice(&bytes)?;
+        assert_eq!(requests.len(), 2);
+        let id_of = |user: &User| {
+            requests
+                .iter()
+                .find(|r| r["user"]["id"].as_str() == Some(&user.id.to_string()))
+                .and_then(|r| r["id"].as_str())
+                .unwrap()
+                .to_string()
+        };
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("/pages/{}/access-requests/{}/approve", page.id, id_of(asker)))
+            .header("content-type", "application/json")
+            .extension(claims_for(owner))
+            .body(Body::from(serde_json::to_vec(&serde_json::json!({ "role": "checker" }))?))?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 200);
+        assert_eq!(page_repo.get_role(page.id, asker.id).await?, Some(PageRole::Checker));
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("/pages/{}/access-requests/{}/deny", page.id, id_of(stranger)))
+            .extension(claims_for(owner))
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 204);
+        assert_eq!(page_repo.get_role(page.id, stranger.id).await?, None);
+
+        // Decided requests leave the queue, and members can't ask again
+        let resp = app.clone().oneshot(list(owner)?).await.unwrap();
+        let bytes = body::to_bytes(resp.into_body(), 64 * 1024).await?;
+        let requests: Vec<serde_json::Value> = serde_json::from_slice(&bytes)?;
+        assert!(requests.is_empty());
+        let resp = app.oneshot(ask(asker)?).await.unwrap();
+        assert_eq!(resp.status(), 400);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_approving_never_downgrades() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let user_repo = UserRepository::new(pool.clone());
+        let page_repo = Arc::new(PageRepository::new(pool.clone()));
+
+        let mut users = Vec::new();
+        for name in ["owner", "editor", "lapsed"] {
+            users.push(
+                user_repo
+                    .create(CreateUser {
+                        twitch_id: Some(format!("tw_{}", name)),
+                        username: name.to_string(),
+                        display_name: None,
+                        profile_image_url: None,
+                        email: None,
+                    })
+                    .await?,
+            );
+        }
+        let (owner, editor, lapsed) = (&users[0], &users[1], &users[2]);
+        let page = page_repo
+            .create(owner.id, CreatePage {
+                title: "Private".to_string(),
+                description: None,
+            })
+            .await?;
+
+        // Both asked before anyone shared the page with them
+        let editor_request = page_repo.create_access_request(page.id, editor.id, None).await?;
+        let lapsed_request = page_repo.create_access_request(page.id, lapsed.id, None).await?;
+        let next_week = chrono::Utc::now() + chrono::Duration::days(7);
+        page_repo
+            .grant_permission(page.id, editor.id, PageRole::Editor, owner.id, Some(next_week))
+            .await?;
+        let yesterday = chrono::Utc::now() - chrono::Duration::days(1);
+        page_repo
+            .grant_permission(page.id, lapsed.id, PageRole::Editor, owner.id, Some(yesterday))
+            .await?;
+
+        let app = access_requests_router(AccessRequestsRouterState {
+            page_repo: page_repo.clone(),
+        });
+        let approve = |request_id: Uuid| -> anyhow::Result<Request<Body>> {
+            Ok(Request::builder()
+                .method(Method::POST)
+                .uri(format!("/pages/{}/access-requests/{}/approve", page.id, request_id))
+                .header("content-type", "application/json")
+                .extension(claims_for(owner))
+                .body(Body::from(serde_json::to_vec(&serde_json::json!({ "role": "viewer" }))?))?)
+        };
+
+        // A higher role is kept, along with its expiry
+        let resp = app.clone().oneshot(approve(editor_request.id)?).await.unwrap();
+        assert_eq!(resp.status(), 200);
+        let permission = page_repo.get_user_permission(page.id, editor.id).await?.unwrap();
+        assert_eq!(permission.role, PageRole::Editor);
+        assert_eq!(permission.expires_at.map(|t| t.timestamp()), Some(next_week.timestamp()));
+
+        // A lapsed one is replaced by lasting access
+        let resp = app.oneshot(approve(lapsed_request.id)?).await.unwrap();
+        assert_eq!(resp.status(), 200);
+        let permission = page_repo.get_user_permission(page.id, lapsed.id).await?.unwrap();
+        assert_eq!(permission.role, PageRole::Viewer);
+        assert_eq!(permission.expires_at, None);
+
+        Ok(())
+    }
+}
@@ -1,7 +1,14 @@
 use crate::error::AppError;
-use crate::models::{ApiKeyResponse, Claims, CreateApiKeyResponse};
+use crate::middleware::require_scope;
+use crate::models::{
+    ApiKeyPage, ApiKeyResponse, Claims, CreateApiKeyResponse, PageAction, RotateApiKeyResponse,
+    Scope,
+};
+use crate::repositories::PageRepository;
 use crate::services::ApiKeyService;
-use crate::validators::{validate_api_key_name, validate_scopes};
+use crate::validators::{
+    validate_api_key_name, validate_api_key_pages, validate_expiry, validate_scopes,
+};
 use crate::error::FieldError;
 use axum::{
     extract::{Extension, Json, Path, State, Query},
@@ -10,6 +17,7 @@ use axum::{
     http::StatusCode,
     Router,
 };
+use chrono::{DateTime, Duration, Utc};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -18,18 +26,26 @@ use uuid::Uuid;
 #[derive(Clone)]
 pub struct ApiKeysRouterState {
     pub api_key_service: Arc<ApiKeyService>,
+    pub page_repo: Arc<PageRepository>,
+    pub rotation_grace: Duration,
 }
 
 #[derive(Debug, Deserialize, serde::Serialize)]
 pub struct CreateApiKeyRequest {
     pub name: Option<String>,
     pub scopes: Vec<String>,
+    #[serde(default, with = "chrono::serde::ts_seconds_option")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Bind the key to these pages only; omit for a key covering all pages.
+    #[serde(default)]
+    pub pages: Option<Vec<ApiKeyPage>>,
 }
 
 pub fn api_keys_router(state: ApiKeysRouterState) -> Router {
     Router::new()
         .route("/settings/api-keys", post(create_api_key).get(list_api_keys))
         .route("/settings/api-keys/:id", delete(revoke_api_key))
+        .route("/settings/api-keys/:id/rotate", post(rotate_api_key))
         .with_state(state)
 }
 
@@ -38,6 +54,8 @@ async fn create_api_key(
     Extension(claims): Extension<Claims>,
     Json(payload): Json<CreateApiKeyRequest>,
 ) -> Result<ResponseJson<CreateApiKeyResponse>, AppError> {
+    require_scope(&claims, Scope::KeysManage)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
@@ -58,16 +76,36 @@ async fn create_api_key(
         return Err(e);
     }
 
+    let mut pages_res = validate_api_key_pages(&payload.pages);
+    if let Err(crate::error::AppError::Validation(ref mut es)) = pages_res {
+        errors.append(es);
+    } else if let Err(e) = pages_res {
+        return Err(e);
+    }
+
+    let mut expiry_res = validate_expiry(&payload.expires_at);
+    if let Err(crate::error::AppError::Validation(ref mut es)) = expiry_res {
+        errors.append(es);
+    } else if let Err(e) = expiry_res {
+        return Err(e);
+    }
+
     if !errors.is_empty() {
         return Err(crate::error::AppError::Validation(errors));
     }
 
     let name = name_res.unwrap();
     let scopes = scopes_res.unwrap();
+    let expires_at = expiry_res.unwrap();
+    let pages = pages_res.unwrap();
+    if !claims.covers_key(&scopes, &pages) {
+        return Err(AppError::Forbidden);
+    }
+    check_key_pages(&state, user_id, &pages).await?;
 
     let response = state
         .api_key_service
-        .create_api_key(user_id, name, scopes)
+        .create_api_key(user_id, name, scopes, expires_at, pages)
         .await
         .map_err(|e| {
             tracing::error!("Failed to create API key: {:?}", e);
@@ -77,10 +115,68 @@ async fn create_api_key(
     Ok(ResponseJson(response))
 }
 
+/// Keys can only be bound to pages the caller can see, and only writable where the
+/// caller can change something.
+async fn check_key_pages(
+    state: &ApiKeysRouterState,
+    user_id: Uuid,
+    pages: &[ApiKeyPage],
+) -> Result<(), AppError> {
+    let mut errors: Vec<FieldError> = Vec::new();
+    for page in pages {
+        if state.page_repo.find_by_id(page.page_id).await?.is_none() {
+            return Err(AppError::NotFound);
+        }
+        let role = state.page_repo.get_role(page.page_id, user_id).await?;
+        let required = if page.read_only { PageAction::View } else { PageAction::CheckItems };
+        let message = match role {
+            Some(role) if role.allows(required) => continue,
+            Some(_) => format!("Page {} is read-only for you; bind it read-only", page.page_id),
+            None => format!("You don't have access to page {}", page.page_id),
+        };
+        errors.push(FieldError {
+            field: "pages".to_string(),
+            message,
+        });
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Validation(errors))
+    }
+}
+
+/// API-key callers only manage keys within their own page binding; `scopes` also
+/// keeps them from rotating a key with more scopes than they have.
+async fn require_key_within_caller(
+    state: &ApiKeysRouterState,
+    claims: &Claims,
+    id: &str,
+    user_id: Uuid,
+    scopes: bool,
+) -> Result<(), AppError> {
+    if claims.scopes.is_none() {
+        return Ok(());
+    }
+    let key = state
+        .api_key_service
+        .get_api_key(id, user_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let pages = state.api_key_service.list_key_pages(&key.id).await?;
+    let scopes = if scopes { key.scopes_as_vec() } else { Vec::new() };
+    if !claims.covers_key(&scopes, &pages) {
+        return Err(AppError::Forbidden);
+    }
+    Ok(())
+}
+
 async fn list_api_keys(
     State(state): State<ApiKeysRouterState>,
     Extension(claims): Extension<Claims>,
 ) -> Result<ResponseJson<Vec<ApiKeyResponse>>, AppError> {
+    require_scope(&claims, Scope::KeysManage)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
@@ -93,19 +189,60 @@ async fn list_api_keys(
             AppError::Internal("Failed to list API keys".to_string())
         })?;
 
-    let responses: Vec<ApiKeyResponse> = api_keys.into_iter().map(|key| key.to_response()).collect();
+    let mut responses: Vec<ApiKeyResponse> = Vec::with_capacity(api_keys.len());
+    for key in api_keys {
+        let mut response = key.to_response();
+        response.pages = state
+            .api_key_service
+            .list_key_pages(&key.id)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to list API key pages: {}", e);
+                AppError::Internal("Failed to list API keys".to_string())
+            })?;
+        responses.push(response);
+    }
     Ok(ResponseJson(responses))
 }
 
+async fn rotate_api_key(
+    State(state): State<ApiKeysRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<String>,
+) -> Result<ResponseJson<RotateApiKeyResponse>, AppError> {
+    require_scope(&claims, Scope::KeysManage)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    require_key_within_caller(&state, &claims, &id, user_id, true).await?;
+
+    let response = state
+        .api_key_service
+        .rotate_api_key(&id, user_id, state.rotation_grace)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to rotate API key: {:?}", e);
+            AppError::Internal("Failed to rotate API key".to_string())
+        })?
+        .ok_or(AppError::NotFound)?;
+
+    Ok(ResponseJson(response))
+}
+
 async fn revoke_api_key(
     State(state): State<ApiKeysRouterState>,
     Extension(claims): Extension<Claims>,
     Path(id): Path<String>,
     Query(params): Query<HashMap<String, String>>,
 ) -> Result<StatusCode, AppError> {
+    require_scope(&claims, Scope::KeysManage)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
+    require_key_within_caller(&state, &claims, &id, user_id, false).await?;
+
     let hard_delete = params.get("hard").map(|v| v == "true").unwrap_or(false);
 
     if hard_delete {
@@ -155,32 +292,37 @@ mod tests {
         // Create test user
         let user_repo = UserRepository::new(pool.clone());
         let user = user_repo.create(CreateUser {
-            twitch_id: "test123".to_string(),
+            twitch_id: Some("test123".to_string()),
             username: "testuser".to_string(),
             display_name: None,
             profile_image_url: None,
             email: None,
         }).await?;
 
-        let api_key_repo = ApiKeyRepository::new(pool);
+        let api_key_repo = ApiKeyRepository::new(pool.clone());
         let api_key_service = Arc::new(ApiKeyService::new(api_key_repo));
         
         let claims = Claims {
             sub: user.id.to_string(),
-            twitch_id: user.twitch_id.clone(),
             username: user.username.clone(),
             exp: 9999999999,
             scopes: None,
+            pages: None,
+            sid: None,
         };
 
         let request = CreateApiKeyRequest {
             name: Some("Test Key".to_string()),
-            scopes: vec!["read".to_string()],
+            scopes: vec!["pages:read".to_string()],
+            expires_at: None,
+            pages: None,
         };
 
         let result = create_api_key(
             State(ApiKeysRouterState {
                 api_key_service: api_key_service.clone(),
+                page_repo: Arc::new(PageRepository::new(pool.clone())),
+                rotation_grace: Duration::hours(1),
             }),
             Extension(claims),
             Json(request),
@@ -201,31 +343,38 @@ mod tests {
         // Create test user
         let user_repo = UserRepository::new(pool.clone());
         let user = user_repo.create(CreateUser {
-            twitch_id: "testv".to_string(),
+            twitch_id: Some("testv".to_string()),
             username: "testuserv".to_string(),
             display_name: None,
             profile_image_url: None,
             email: None,
         }).await?;
 
-        let api_key_repo = ApiKeyRepository::new(pool);
+        let api_key_repo = ApiKeyRepository::new(pool.clone());
         let api_key_service = Arc::new(ApiKeyService::new(api_key_repo));
 
         let claims = Claims {
             sub: user.id.to_string(),
-            twitch_id: user.twitch_id.clone(),
             username: user.username.clone(),
             exp: 9999999999,
             scopes: None,
+            pages: None,
+            sid: None,
         };
 
         // Invalid scopes: empty
         let request = CreateApiKeyRequest {
             name: Some("Test Key".to_string()),
             scopes: vec![],
+            expires_at: None,
+            pages: None,
         };
         // Call via router so we can inspect the HTTP response body
-        let app = api_keys_router(ApiKeysRouterState { api_key_service: api_key_service.clone() });
+        let app = api_keys_router(ApiKeysRouterState {
+            api_key_service: api_key_service.clone(),
+            page_repo: Arc::new(PageRepository::new(pool.clone())),
+            rotation_grace: Duration::hours(1),
+        });
 
         use axum::http::{Request, Method};
         use axum::body::Body;
@@ -261,43 +410,49 @@ use axum::http::{Request, Method};
         // Create test user
         let user_repo = UserRepository::new(pool.clone());
         let user = user_repo.create(CreateUser {
-            twitch_id: "test123".to_string(),
+            twitch_id: Some("test123".to_string()),
             username: "testuser".to_string(),
             display_name: None,
             profile_image_url: None,
             email: None,
         }).await?;
 
-        let api_key_repo = ApiKeyRepository::new(pool);
+        let api_key_repo = ApiKeyRepository::new(pool.clone());
         let api_key_service = Arc::new(ApiKeyService::new(api_key_repo));
 
         let claims = Claims {
             sub: user.id.to_string(),
-            twitch_id: user.twitch_id.clone(),
             username: user.username.clone(),
             exp: 9999999999,
             scopes: None,
+            pages: None,
+            sid: None,
         };
 
         // Create an API key
-        let create_resp = api_key_service.create_api_key(user.id, Some("ToDelete".to_string()), vec!["read".to_string()]).await?;
+        let create_resp = api_key_service.create_api_key(user.id, Some("ToDelete".to_string()), vec!["pages:read".to_string()], None, Vec::new()).await?;
         assert!(!create_resp.id.is_empty());
 
         // Delete the API key (hard delete)
         let mut params = HashMap::new();
         params.insert("hard".to_string(), "true".to_string());
         // Call the HTTP handler to ensure it returns 204 No Content
-        let app = api_keys_router(ApiKeysRouterState { api_key_service: api_key_service.clone() });
+        let app = api_keys_router(ApiKeysRouterState {
+            api_key_service: api_key_service.clone(),
+            page_repo: Arc::new(PageRepository::new(pool.clone())),
+            rotation_grace: Duration::hours(1),
+        });
 
         let req = Request::builder()
             .method(Method::DELETE)
             .uri(format!("/settings/api-keys/{}?hard=true", create_resp.id))
             .extension(Claims {
                 sub: user.id.to_string(),
-                twitch_id: user.twitch_id.clone(),
                 username: user.username.clone(),
                 exp: 9999999999,
                 scopes: None,
+                pages: None,
+                sid: None,
             })
             .body(Body::empty())?;
 
@@ -306,7 +461,11 @@ use axum::http::{Request, Method};
 
         // Ensure the key is gone from list
         let list = list_api_keys(
-            State(ApiKeysRouterState { api_key_service: api_key_service.clone() }),
+            State(ApiKeysRouterState {
+                api_key_service: api_key_service.clone(),
+                page_repo: Arc::new(PageRepository::new(pool.clone())),
+                rotation_grace: Duration::hours(1),
+            }),
             Extension(claims),
         ).await?;
 
@@ -315,4 +474,243 @@ use axum::http::{Request, Method};
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_rotate_api_key_keeps_old_token_during_grace() -> anyhow::Result<()> {
+        use axum::body::Body;
+        use axum::http::{Method, Request};
+        use tower::util::ServiceExt;
+
+        let pool = setup_db().await;
+
+        let user_repo = UserRepository::new(pool.clone());
+        let user = user_repo.create(CreateUser {
+            twitch_id: Some("rot123".to_string()),
+            username: "rotuser".to_string(),
+            display_name: None,
+            profile_image_url: None,
+            email: None,
+        }).await?;
+
+        let api_key_service = Arc::new(ApiKeyService::new(ApiKeyRepository::new(pool.clone())));
+        let created = api_key_service
+            .create_api_key(user.id, Some("bot".to_string()), vec!["pages:read".to_string()], None, Vec::new())
+            .await?;
+
+        let app = api_keys_router(ApiKeysRouterState {
+            api_key_service: api_key_service.clone(),
+            page_repo: Arc::new(PageRepository::new(pool.clone())),
+            rotation_grace: Duration::hours(1),
+        });
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("/settings/api-keys/{}/rotate", created.id))
+            .extension(Claims {
+                sub: user.id.to_string(),
+                username: user.username.clone(),
+                exp: 9999999999,
+                scopes: None,
+                pages: None,
+                sid: None,
+            })
+            .body(Body::empty())?;
+
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 200);
+        let bytes = axum::body::to_bytes(resp.into_body(), 64 * 1024).await?;
+        let v: serde_json::Value = serde_json::from_slice(&bytes)?;
+        assert_eq!(v["id"].as_str(), Some(created.id.as_str()));
+        assert!(v["previous_token_expires_at"].as_i64().is_some());
+        let new_token = v["token"].as_str().unwrap().to_string();
+        assert_ne!(new_token, created.token);
+
+        // Both secrets resolve to the same key record while the grace window is open
+        let old = api_key_service.verify_token(&created.token, None).await?.expect("old token valid");
+        let new = api_key_service.verify_token(&new_token, None).await?.expect("new token valid");
+        assert_eq!(old.id, created.id);
+        assert_eq!(new.id, created.id);
+        assert_eq!(new.name.as_deref(), Some("bot"));
+        assert_eq!(new.scopes_as_vec(), vec!["pages:read".to_string()]);
+
+        // Rotating again without grace invalidates every earlier secret
+        let rotated = api_key_service
+            .rotate_api_key(&created.id, user.id, Duration::zero())
+            .await?
+            .expect("key exists");
+        assert!(rotated.previous_token_expires_at.is_none());
+        assert!(api_key_service.verify_token(&created.token, None).await?.is_none());
+        assert!(api_key_service.verify_token(&new_token, None).await?.is_none());
+        assert!(api_key_service.verify_token(&rotated.token, None).await?.is_some());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_api_key_checks_bound_pages() -> anyhow::Result<()> {
+        use crate::models::{CreatePage, PageRole};
+        use axum::body::Body;
+        use axum::http::{Method, Request};
+        use tower::util::ServiceExt;
+
+        let pool = setup_db().await;
+
+        let user_repo = UserRepository::new(pool.clone());
+        let user = user_repo.create(CreateUser {
+            twitch_id: Some("bind123".to_string()),
+            username: "binduser".to_string(),
+            display_name: None,
+            profile_image_url: None,
+            email: None,
+        }).await?;
+        let other = user_repo.create(CreateUser {
+            twitch_id: Some("bind456".to_string()),
+            username: "otheruser".to_string(),
+            display_name: None,
+            profile_image_url: None,
+            email: None,
+        }).await?;
+
+        let page_repo = Arc::new(PageRepository::new(pool.clone()));
+        let page = |creator: Uuid, title: &str| {
+            let page_repo = page_repo.clone();
+            let data = CreatePage { title: title.to_string(), description: None };
+            async move { page_repo.create(creator, data).await }
+        };
+        let own = page(user.id, "Mine").await?;
+        let viewed = page(other.id, "Shared").await?;
+        let foreign = page(other.id, "Private").await?;
+        page_repo
+            .grant_permission(viewed.id, user.id, PageRole::Viewer, other.id, None)
+            .await?;
+
+        let app = api_keys_router(ApiKeysRouterState {
+            api_key_service: Arc::new(ApiKeyService::new(ApiKeyRepository::new(pool.clone()))),
+            page_repo,
+            rotation_grace: Duration::hours(1),
+        });
+        let create = |pages: serde_json::Value| {
+            let app = app.clone();
+            let claims = Claims {
+                sub: user.id.to_string(),
+                username: user.username.clone(),
+                exp: 9999999999,
+                scopes: None,
+                pages: None,
+                sid: None,
+            };
+            async move {
+                let body = serde_json::json!({ "scopes": ["pages:read"], "pages": pages });
+                let req = Request::builder()
+                    .method(Method::POST)
+                    .uri("/settings/api-keys")
+                    .header("content-type", "application/json")
+                    .extension(claims)
+                    .body(Body::from(serde_json::to_vec(&body)?))?;
+                anyhow::Ok(app.oneshot(req).await.unwrap().status())
+            }
+        };
+
+        let status = create(serde_json::json!([{ "page_id": Uuid::new_v4() }])).await?;
+        assert_eq!(status, 404);
+        let status = create(serde_json::json!([{ "page_id": foreign.id, "read_only": true }])).await?;
+        assert_eq!(status, 400);
+        let status = create(serde_json::json!([{ "page_id": viewed.id }])).await?;
+        assert_eq!(status, 400);
+        let status = create(serde_json::json!([
+            { "page_id": own.id },
+            { "page_id": viewed.id, "read_only": true },
+        ]))
+        .await?;
+        assert_eq!(status, 200);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_page_bound_key_cannot_escalate() -> anyhow::Result<()> {
+        use crate::models::CreatePage;
+        use axum::body::Body;
+        use axum::http::{Method, Request};
+        use tower::util::ServiceExt;
+
+        let pool = setup_db().await;
+
+        let user = UserRepository::new(pool.clone()).create(CreateUser {
+            twitch_id: Some("bot123".to_string()),
+            username: "botowner".to_string(),
+            display_name: None,
+            profile_image_url: None,
+            email: None,
+        }).await?;
+        let page_repo = Arc::new(PageRepository::new(pool.clone()));
+        let bound = page_repo
+            .create(user.id, CreatePage { title: "Bot".to_string(), description: None })
+            .await?;
+        let other = page_repo
+            .create(user.id, CreatePage { title: "Other".to_string(), description: None })
+            .await?;
+
+        let api_key_service = Arc::new(ApiKeyService::new(ApiKeyRepository::new(pool.clone())));
+        let unbound = api_key_service
+            .create_api_key(user.id, None, vec!["pages:read".to_string()], None, Vec::new())
+            .await?;
+
+        let app = api_keys_router(ApiKeysRouterState {
+            api_key_service,
+            page_repo,
+            rotation_grace: Duration::hours(1),
+        });
+        // The caller is a key bound to one page with keys:manage
+        let bot = Claims {
+            sub: user.id.to_string(),
+            username: user.username.clone(),
+            exp: 9999999999,
+            scopes: Some(vec!["keys:manage".to_string(), "pages:read".to_string()]),
+            pages: Some(vec![ApiKeyPage { page_id: bound.id, read_only: false }]),
+            sid: None,
+        };
+        let send = |method: Method, uri: String, body: Option<serde_json::Value>| {
+            let app = app.clone();
+            let bot = bot.clone();
+            async move {
+                let body = match body {
+                    Some(body) => Body::from(serde_json::to_vec(&body)?),
+                    None => Body::empty(),
+                };
+                let req = Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .header("content-type", "application/json")
+                    .extension(bot)
+                    .body(body)?;
+                anyhow::Ok(app.oneshot(req).await.unwrap().status())
+            }
+        };
+        let create = |scopes: serde_json::Value, pages: serde_json::Value| {
+            send(
+                Method::POST,
+                "/settings/api-keys".to_string(),
+                Some(serde_json::json!({ "scopes": scopes, "pages": pages })),
+            )
+        };
+
+        // No unbound keys, no other pages, no extra scopes
+        let status = create(serde_json::json!(["pages:read"]), serde_json::Value::Null).await?;
+        assert_eq!(status, 403);
+        let status = create(serde_json::json!(["pages:read"]), serde_json::json!([{ "page_id": other.id }])).await?;
+        assert_eq!(status, 403);
+        let status = create(serde_json::json!(["pages:write"]), serde_json::json!([{ "page_id": bound.id }])).await?;
+        assert_eq!(status, 403);
+        let status = create(serde_json::json!(["pages:read"]), serde_json::json!([{ "page_id": bound.id }])).await?;
+        assert_eq!(status, 200);
+
+        // Keys outside the binding can't be rotated or revoked
+        let status = send(Method::POST, format!("/settings/api-keys/{}/rotate", unbound.id), None).await?;
+        assert_eq!(status, 403);
+        let status = send(Method::DELETE, format!("/settings/api-keys/{}", unbound.id), None).await?;
+        assert_eq!(status, 403);
+
+        Ok(())
+    }
 }
@@ -1,130 +1,645 @@
 use crate::error::AppError;
 
-use crate::services::AuthService;
+use crate::middleware::{client_ip, cookie_value, TrustedProxies};
+use crate::models::{IdentityProviderInfo, SessionTokens};
+use crate::services::{AuthService, DevProvider, IdentityProviders};
+use crate::validators::{validate_return_to, validate_username};
 use axum::{
-    extract::{Query, State},
-    response::{IntoResponse, Redirect},
+    extract::{ConnectInfo, Query, State},
+    http::{header, HeaderMap},
+    response::{Html, IntoResponse, Json as ResponseJson, Redirect, Response},
     routing::get,
     Router,
 };
 use cookie::{Cookie, time::Duration as CookieDuration};
+use jsonwebtoken::jwk::JwkSet;
 use axum::http::StatusCode;
+use chrono::Utc;
 use serde::Deserialize;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use url::Url;
+use uuid::Uuid;
+
+const OAUTH_STATE_COOKIE: &str = "oauth_state";
+const AUTH_COOKIE: &str = "auth_token";
+const REFRESH_COOKIE: &str = "refresh_token";
+// The refresh token is only ever needed by `/api/auth/*`, so keep it off every other request.
+const REFRESH_COOKIE_PATH: &str = "/api/auth";
 
 #[derive(Clone)]
 pub struct AuthRouterState {
     pub auth_service: Arc<AuthService>,
-    pub twitch_client_id: String,
-    pub redirect_uri: String,
+    pub providers: Arc<IdentityProviders>,
+    /// Set when `DEV_LOGIN_ENABLED` is on; also registered in `providers`.
+    pub dev_login: Option<Arc<DevProvider>>,
     pub frontend_url: String,
+    pub trusted_proxies: Arc<TrustedProxies>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LoginQuery {
+    /// Identity provider id; defaults to the first configured provider.
+    pub provider: Option<String>,
+    pub return_to: Option<String>,
+    /// Link the provider account to the signed-in user instead of logging in.
+    #[serde(default)]
+    pub link: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DevAuthorizeQuery {
+    pub state: String,
+    pub code_challenge: String,
+    pub username: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct AuthCallbackQuery {
     pub code: String,
-    #[allow(dead_code)]
     pub state: Option<String>,
 }
 
-
-
 pub fn auth_router(state: AuthRouterState) -> Router {
     Router::new()
+        .route("/providers", get(list_providers))
+        .route("/jwks.json", get(jwks))
         .route("/login", get(login))
         .route("/callback", get(callback))
+        .route("/dev/authorize", get(dev_authorize))
+        .route("/refresh", axum::routing::post(refresh))
         .route("/logout", axum::routing::post(logout))
         .with_state(state)
 }
 
-async fn login(State(state): State<AuthRouterState>) -> impl IntoResponse {
-    let auth_url = format!(
-        "https://id.twitch.tv/oauth2/authorize?client_id={}&redirect_uri={}&response_type=code&scope=user:read:email",
-        state.twitch_client_id,
-        urlencoding::encode(&state.redirect_uri)
+// Cookies must not be marked Secure for local development over http.
+pub(crate) fn secure_cookies(frontend_url: &str) -> bool {
+    match Url::parse(frontend_url) {
+        Ok(parsed) => parsed.scheme() == "https",
+        Err(_) => true,
+    }
+}
+
+fn user_agent(headers: &HeaderMap) -> Option<&str> {
+    headers.get(header::USER_AGENT).and_then(|h| h.to_str().ok())
+}
+
+// Cookie max-age from an absolute expiry; never negative.
+fn max_age_until(expires_at: chrono::DateTime<Utc>) -> CookieDuration {
+    CookieDuration::seconds((expires_at - Utc::now()).num_seconds().max(0))
+}
+
+fn append_cookie(headers: &mut HeaderMap, c: &Cookie) {
+    headers.append(
+        axum::http::header::SET_COOKIE,
+        axum::http::HeaderValue::from_str(&c.to_string()).unwrap(),
     );
+}
+
+// Set the access and refresh token cookies for a session.
+fn append_session_cookies(headers: &mut HeaderMap, tokens: &SessionTokens, secure: bool) {
+    let access = Cookie::build(AUTH_COOKIE, tokens.access_token.clone())
+        .path("/")
+        .http_only(true)
+        .same_site(cookie::SameSite::Lax)
+        .max_age(max_age_until(tokens.access_expires_at))
+        .secure(secure)
+        .finish();
+    append_cookie(headers, &access);
+
+    let refresh = Cookie::build(REFRESH_COOKIE, tokens.refresh_token.clone())
+        .path(REFRESH_COOKIE_PATH)
+        .http_only(true)
+        .same_site(cookie::SameSite::Strict)
+        .max_age(max_age_until(tokens.refresh_expires_at))
+        .secure(secure)
+        .finish();
+    append_cookie(headers, &refresh);
+}
 
-    Redirect::temporary(&auth_url)
+fn append_cleared_cookie(headers: &mut HeaderMap, name: &'static str, path: &'static str, secure: bool) {
+    let c = Cookie::build(name, "")
+        .path(path)
+        .http_only(true)
+        .max_age(CookieDuration::seconds(0))
+        .secure(secure)
+        .finish();
+    append_cookie(headers, &c);
 }
 
-async fn callback(
+// Providers available on the login page
+async fn list_providers(
     State(state): State<AuthRouterState>,
-    Query(params): Query<AuthCallbackQuery>,
+) -> ResponseJson<Vec<IdentityProviderInfo>> {
+    ResponseJson(state.providers.list())
+}
+
+// Public keys for verifying our access tokens elsewhere; empty when signing with HS256
+async fn jwks(State(state): State<AuthRouterState>) -> ResponseJson<JwkSet> {
+    ResponseJson(state.auth_service.keyring().jwks())
+}
+
+async fn login(
+    State(state): State<AuthRouterState>,
+    request_headers: HeaderMap,
+    Query(params): Query<LoginQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    // Exchange code for token
-    let token_response = state
-        .auth_service
-        .exchange_code_for_token(&params.code, &state.redirect_uri)
-        .await
-        .map_err(|e| AppError::Internal(e.to_string()))?;
+    let provider = match params.provider.as_deref() {
+        Some(id) => state.providers.get(id),
+        None => state.providers.default_provider(),
+    }
+    .ok_or_else(|| AppError::BadRequest("Unknown identity provider".to_string()))?;
 
-    // Get user info from Twitch
-    let twitch_user = state
-        .auth_service
-        .get_twitch_user(&token_response.access_token)
-        .await
-        .map_err(|e| AppError::Internal(e.to_string()))?;
+    // An invalid return path is dropped rather than failing the login.
+    let return_to = params.return_to.and_then(|r| validate_return_to(&r).ok());
 
-    // Create or update user in database
-    let user = state
-        .auth_service
-        .get_or_create_user(twitch_user)
-        .await
-        .map_err(|e| AppError::Internal(e.to_string()))?;
+    // Linking requires an active session; the user id travels in the signed state.
+    let link_user_id = if params.link {
+        let claims = cookie_value(&request_headers, AUTH_COOKIE)
+            .and_then(|token| state.auth_service.verify_jwt(&token).ok())
+            .ok_or(AppError::Unauthorized)?;
+        let sid = claims.sid.as_deref().ok_or(AppError::Unauthorized)?;
+        if !state
+            .auth_service
+            .is_session_active(sid)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?
+        {
+            return Err(AppError::Unauthorized);
+        }
+        Some(claims.sub)
+    } else {
+        None
+    };
 
-    // Generate JWT
-    let jwt = state
+    let oauth = state
         .auth_service
-        .create_jwt(&user)
+        .begin_oauth_login(provider.id(), return_to, link_user_id)
         .map_err(|e| AppError::Internal(e.to_string()))?;
 
-    // Set JWT as httpOnly secure cookie and redirect to frontend callback path without token in URL
-
-    // Build cookie with proper attributes. Determine Secure flag based on frontend URL scheme.
-    // For local development over http the `Secure` flag must be disabled so the cookie can be set.
-    let secure_flag = match Url::parse(&state.frontend_url) {
-        Ok(parsed) => parsed.scheme() == "https",
-        Err(_) => true,
-    };
+    let auth_url = provider
+        .authorize_url(&oauth.state, &oauth.code_challenge)
+        .await
+        .map_err(|e| AppError::Internal(e.to_string()))?;
 
-    let c = Cookie::build("auth_token", jwt)
+    // SameSite=Lax so the cookie survives the top-level redirect back from the provider.
+    let c = Cookie::build(OAUTH_STATE_COOKIE, oauth.cookie)
         .path("/")
         .http_only(true)
         .same_site(cookie::SameSite::Lax)
-        .max_age(CookieDuration::days(7))
-        .secure(secure_flag)
+        .max_age(CookieDuration::minutes(10))
+        .secure(secure_cookies(&state.frontend_url))
         .finish();
 
-    let redirect_url = format!("{}/auth/callback", state.frontend_url);
-
     let mut headers = axum::http::HeaderMap::new();
     headers.insert(
         axum::http::header::SET_COOKIE,
         axum::http::HeaderValue::from_str(&c.to_string()).unwrap(),
     );
 
-    Ok((headers, Redirect::temporary(&redirect_url)))
+    Ok((headers, Redirect::temporary(&auth_url)))
 }
 
-async fn logout(State(state): State<AuthRouterState>) -> impl IntoResponse {
-    // Clear cookie by setting Max-Age=0. Respect frontend scheme to set Secure flag accordingly.
-    let secure_flag = match Url::parse(&state.frontend_url) {
-        Ok(parsed) => parsed.scheme() == "https",
-        Err(_) => true,
+// Stand-in consent screen for the dev provider: asks for a username, then
+// redirects to the callback like a real provider would
+async fn dev_authorize(
+    State(state): State<AuthRouterState>,
+    Query(params): Query<DevAuthorizeQuery>,
+) -> Result<Response, AppError> {
+    let dev = state.dev_login.as_ref().ok_or(AppError::NotFound)?;
+
+    let Some(username) = params.username else {
+        return Ok(Html(format!(
+            r#"<!doctype html>
+<title>Development login</title>
+<form method="get">
+  <input type="hidden" name="state" value="{}">
+  <input type="hidden" name="code_challenge" value="{}">
+  <label>Username <input name="username" autofocus required></label>
+  <button type="submit">Sign in</button>
+</form>"#,
+            html_escape(&params.state),
+            html_escape(&params.code_challenge),
+        ))
+        .into_response());
     };
+    let username = validate_username(&username)?;
 
-    let c = Cookie::build("auth_token", "")
-        .path("/")
-        .http_only(true)
-        .max_age(CookieDuration::seconds(0))
-        .secure(secure_flag)
-        .finish();
+    let callback_url = dev
+        .callback_url(&username, &params.state, &params.code_challenge)
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+    Ok(Redirect::temporary(&callback_url).into_response())
+}
 
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+async fn callback(
+    State(state): State<AuthRouterState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    request_headers: HeaderMap,
+    Query(params): Query<AuthCallbackQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    // Reject callbacks that were not initiated by this browser (login CSRF)
+    let state_cookie = cookie_value(&request_headers, OAUTH_STATE_COOKIE)
+        .ok_or_else(|| AppError::BadRequest("Missing OAuth state".to_string()))?;
+    let oauth_state = state
+        .auth_service
+        .verify_oauth_state(&state_cookie, params.state.as_deref().unwrap_or_default())
+        .map_err(|_| AppError::BadRequest("Invalid OAuth state".to_string()))?;
+
+    let provider = state
+        .providers
+        .get(&oauth_state.provider)
+        .ok_or_else(|| AppError::BadRequest("Unknown identity provider".to_string()))?;
+
+    // Exchange the code and fetch the account from the provider
+    let identity = provider
+        .fetch_identity(&params.code, &oauth_state.pkce_verifier)
+        .await
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+
+    let secure_flag = secure_cookies(&state.frontend_url);
     let mut headers = axum::http::HeaderMap::new();
-    headers.insert(
-        axum::http::header::SET_COOKIE,
-        axum::http::HeaderValue::from_str(&c.to_string()).unwrap(),
-    );
-    (headers, StatusCode::NO_CONTENT)
+
+    if let Some(ref link_user_id) = oauth_state.link_user_id {
+        // Linking keeps the current session; only the identity is attached
+        let user_id = Uuid::parse_str(link_user_id)
+            .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+        state
+            .auth_service
+            .link_identity(user_id, identity)
+            .await
+            .map_err(|e| AppError::BadRequest(e.to_string()))?;
+    } else {
+        // Create or update user in database
+        let user = state
+            .auth_service
+            .get_or_create_user(identity)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        // Start a server-side session and issue its first token pair
+        let ip = client_ip(&request_headers, connect_info.map(|ConnectInfo(addr)| addr), &state.trusted_proxies);
+        let tokens = state
+            .auth_service
+            .start_session(&user, user_agent(&request_headers), ip.as_deref())
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+        append_session_cookies(&mut headers, &tokens, secure_flag);
+    }
+
+    let redirect_url = match oauth_state.return_to {
+        Some(ref path) => format!(
+            "{}/auth/callback?return_to={}",
+            state.frontend_url,
+            urlencoding::encode(path)
+        ),
+        None => format!("{}/auth/callback", state.frontend_url),
+    };
+
+    // Redirect to frontend callback path without token in URL. The state cookie is single-use.
+    append_cleared_cookie(&mut headers, OAUTH_STATE_COOKIE, "/", secure_flag);
+
+    Ok((headers, Redirect::temporary(&redirect_url)))
+}
+
+// Exchange the refresh cookie for a new access token, rotating the refresh token
+async fn refresh(
+    State(state): State<AuthRouterState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    request_headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let refresh_token =
+        cookie_value(&request_headers, REFRESH_COOKIE).ok_or(AppError::Unauthorized)?;
+
+    let ip = client_ip(&request_headers, connect_info.map(|ConnectInfo(addr)| addr), &state.trusted_proxies);
+    let tokens = state
+        .auth_service
+        .refresh_session(&refresh_token, user_agent(&request_headers), ip.as_deref())
+        .await
+        .map_err(|e| AppError::Internal(e.to_string()))?
+        .ok_or(AppError::Unauthorized)?;
+
+    let mut headers = axum::http::HeaderMap::new();
+    append_session_cookies(&mut headers, &tokens, secure_cookies(&state.frontend_url));
+    Ok((headers, StatusCode::NO_CONTENT))
+}
+
+async fn logout(
+    State(state): State<AuthRouterState>,
+    request_headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    // Revoke the server-side session so copies of the tokens stop working too
+    if let Some(refresh_token) = cookie_value(&request_headers, REFRESH_COOKIE) {
+        state
+            .auth_service
+            .revoke_session_by_refresh_token(&refresh_token)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+    }
+    if let Some(sid) = cookie_value(&request_headers, AUTH_COOKIE)
+        .and_then(|token| state.auth_service.verify_jwt(&token).ok())
+        .and_then(|claims| claims.sid)
+    {
+        state
+            .auth_service
+            .revoke_session(&sid)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+    }
+
+    // Clear cookies by setting Max-Age=0. Respect frontend scheme to set Secure flag accordingly.
+    let secure_flag = secure_cookies(&state.frontend_url);
+    let mut headers = axum::http::HeaderMap::new();
+    append_cleared_cookie(&mut headers, AUTH_COOKIE, "/", secure_flag);
+    append_cleared_cookie(&mut headers, REFRESH_COOKIE, REFRESH_COOKIE_PATH, secure_flag);
+    Ok((headers, StatusCode::NO_CONTENT))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{SessionRepository, UserRepository};
+    use crate::tests_utils::{session_id, setup_db};
+    use axum::body::Body;
+    use axum::http::{header, Method, Request};
+    use tower::util::ServiceExt;
+
+    async fn test_state() -> AuthRouterState {
+        let pool = setup_db().await;
+        let user_repo = UserRepository::new(pool.clone());
+        AuthRouterState {
+            auth_service: Arc::new(AuthService::new(
+                "test_secret".to_string(),
+                user_repo,
+                SessionRepository::new(pool),
+            )),
+            providers: Arc::new(IdentityProviders::new(vec![Arc::new(
+                crate::services::TwitchProvider::new(
+                    "client_id".to_string(),
+                    "client_secret".to_string(),
+                    "http://localhost:8000/api/auth/callback".to_string(),
+                ),
+            )])),
+            dev_login: None,
+            frontend_url: "http://localhost:5173".to_string(),
+            trusted_proxies: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_login_sets_state_cookie() -> anyhow::Result<()> {
+        let state = test_state().await;
+        let app = auth_router(state.clone());
+
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri("/login?return_to=%2Fpages%2F123")
+            .body(Body::empty())?;
+
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::TEMPORARY_REDIRECT);
+
+        let location = resp.headers()[header::LOCATION].to_str()?.to_string();
+        let url = Url::parse(&location)?;
+        let query: std::collections::HashMap<_, _> = url.query_pairs().into_owned().collect();
+        assert_eq!(query.get("code_challenge_method").map(String::as_str), Some("S256"));
+
+        let set_cookie = resp.headers()[header::SET_COOKIE].to_str()?;
+        let cookie = Cookie::parse(set_cookie.to_string())?;
+        assert_eq!(cookie.name(), OAUTH_STATE_COOKIE);
+        assert_eq!(cookie.http_only(), Some(true));
+
+        // The cookie verifies against the state sent to Twitch and carries the return path
+        let oauth_state = state
+            .auth_service
+            .verify_oauth_state(cookie.value(), &query["state"])?;
+        assert_eq!(oauth_state.return_to.as_deref(), Some("/pages/123"));
+        assert_eq!(oauth_state.provider, "twitch");
+        assert!(oauth_state.link_user_id.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_login_rejects_unknown_provider_and_anonymous_link() -> anyhow::Result<()> {
+        let state = test_state().await;
+        let app = auth_router(state);
+
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri("/login?provider=nope")
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        // Linking another provider needs a signed-in session
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri("/login?provider=twitch&link=true")
+            .body(Body::empty())?;
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_login_drops_external_return_to() -> anyhow::Result<()> {
+        let state = test_state().await;
+        let app = auth_router(state.clone());
+
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri("/login?return_to=%2F%2Fevil.example")
+            .body(Body::empty())?;
+
+        let resp = app.oneshot(req).await.unwrap();
+        let location = resp.headers()[header::LOCATION].to_str()?.to_string();
+        let url = Url::parse(&location)?;
+        let nonce = url
+            .query_pairs()
+            .find(|(k, _)| k == "state")
+            .map(|(_, v)| v.into_owned())
+            .unwrap();
+        let cookie = Cookie::parse(resp.headers()[header::SET_COOKIE].to_str()?.to_string())?;
+
+        let oauth_state = state.auth_service.verify_oauth_state(cookie.value(), &nonce)?;
+        assert!(oauth_state.return_to.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dev_login_flow() -> anyhow::Result<()> {
+        let mut state = test_state().await;
+
+        // Disabled by default
+        let app = auth_router(state.clone());
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri("/dev/authorize?state=s&code_challenge=c&username=alice")
+            .body(Body::empty())?;
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let dev = Arc::new(DevProvider::new(
+            "http://localhost:8000/api/auth/callback".to_string(),
+        ));
+        state.providers = Arc::new(IdentityProviders::new(vec![dev.clone()]));
+        state.dev_login = Some(dev);
+        let app = auth_router(state.clone());
+
+        // Login redirects to the local consent screen
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri("/login?provider=dev&return_to=%2Fpages")
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::TEMPORARY_REDIRECT);
+        let location = Url::parse(resp.headers()[header::LOCATION].to_str()?)?;
+        assert_eq!(location.path(), "/api/auth/dev/authorize");
+        let state_cookie = Cookie::parse(resp.headers()[header::SET_COOKIE].to_str()?.to_string())?;
+
+        // Without a username the form is shown
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(format!("/dev/authorize?{}", location.query().unwrap()))
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // Picking a username redirects to the callback with a code
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(format!("/dev/authorize?{}&username=alice", location.query().unwrap()))
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::TEMPORARY_REDIRECT);
+        let callback = Url::parse(resp.headers()[header::LOCATION].to_str()?)?;
+        assert_eq!(callback.path(), "/api/auth/callback");
+
+        // The callback signs alice in and returns to the requested page
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(format!("/callback?{}", callback.query().unwrap()))
+            .header(header::COOKIE, format!("{}={}", OAUTH_STATE_COOKIE, state_cookie.value()))
+            .body(Body::empty())?;
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::TEMPORARY_REDIRECT);
+        assert_eq!(
+            resp.headers()[header::LOCATION].to_str()?,
+            "http://localhost:5173/auth/callback?return_to=%2Fpages"
+        );
+        let access = resp
+            .headers()
+            .get_all(header::SET_COOKIE)
+            .iter()
+            .map(|v| Cookie::parse(v.to_str().unwrap().to_string()).unwrap())
+            .find(|c| c.name() == AUTH_COOKIE)
+            .unwrap();
+        let claims = state.auth_service.verify_jwt(access.value())?;
+        assert_eq!(claims.username, "alice");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_callback_rejects_state_mismatch() -> anyhow::Result<()> {
+        let state = test_state().await;
+        let oauth = state.auth_service.begin_oauth_login("twitch", None, None)?;
+        let app = auth_router(state);
+
+        // Missing cookie
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(format!("/callback?code=abc&state={}", oauth.state))
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        // Cookie present but state from a different login attempt
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri("/callback?code=abc&state=forged")
+            .header(header::COOKIE, format!("{}={}", OAUTH_STATE_COOKIE, oauth.cookie))
+            .body(Body::empty())?;
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_refresh_and_logout() -> anyhow::Result<()> {
+        let state = test_state().await;
+        let user = state
+            .auth_service
+            .get_or_create_user(
+                crate::models::TwitchUser {
+                    id: "tw1".to_string(),
+                    login: "user1".to_string(),
+                    display_name: "User 1".to_string(),
+                    profile_image_url: "http://image.url".to_string(),
+                    email: None,
+                }
+                .into(),
+            )
+            .await?;
+        let tokens = state.auth_service.start_session(&user, None, None).await?;
+        let app = auth_router(state.clone());
+
+        // Refresh issues a new access token and rotates the refresh cookie
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/refresh")
+            .header(header::COOKIE, format!("{}={}", REFRESH_COOKIE, tokens.refresh_token))
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+        let cookies: Vec<Cookie> = resp
+            .headers()
+            .get_all(header::SET_COOKIE)
+            .iter()
+            .map(|v| Cookie::parse(v.to_str().unwrap().to_string()).unwrap())
+            .collect();
+        let refresh_cookie = cookies.iter().find(|c| c.name() == REFRESH_COOKIE).unwrap();
+        assert_eq!(refresh_cookie.path(), Some(REFRESH_COOKIE_PATH));
+        assert_ne!(refresh_cookie.value(), tokens.refresh_token);
+        assert!(cookies.iter().any(|c| c.name() == AUTH_COOKIE));
+
+        // Missing cookie
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/refresh")
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        // Logout revokes the session behind the rotated refresh token
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/logout")
+            .header(header::COOKIE, format!("{}={}", REFRESH_COOKIE, refresh_cookie.value()))
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        assert!(!state.auth_service.is_session_active(&session_id(&state.auth_service, &tokens)).await?);
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/refresh")
+            .header(header::COOKIE, format!("{}={}", REFRESH_COOKIE, refresh_cookie.value()))
+            .body(Body::empty())?;
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,336 @@
+use crate::error::AppError;
+use crate::models::{
+    Claims, CreatePageInvite, CreatedPageInvite, PageAction, PageInvite, PageRole,
+    PageWithPermission, Scope,
+};
+use crate::middleware::{require_page, require_scope};
+use crate::repositories::PageRepository;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{delete, get, post},
+    Extension, Router,
+};
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct InvitesRouterState {
+    pub page_repo: Arc<PageRepository>,
+}
+
+pub fn invites_router(state: InvitesRouterState) -> Router {
+    Router::new()
+        .route("/pages/:id/invites", get(list_invites).post(create_invite))
+        .route("/pages/:id/invites/:invite_id", delete(revoke_invite))
+        .route("/invites/:token/accept", post(accept_invite))
+        .with_state(state)
+}
+
+// Only managers and the owner may hand out invites, same as direct sharing
+async fn require_share(
+    state: &InvitesRouterState,
+    claims: &Claims,
+    page_id: Uuid,
+) -> Result<Uuid, AppError> {
+    require_scope(claims, Scope::SharingAdmin)?;
+    require_page(claims, page_id, true)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    state
+        .page_repo
+        .find_by_id(page_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    if !state.page_repo.can(page_id, user_id, PageAction::Share).await? {
+        return Err(AppError::Forbidden);
+    }
+
+    Ok(user_id)
+}
+
+// Create an invite link; the token is only returned here
+async fn create_invite(
+    State(state): State<InvitesRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path(page_id): Path<Uuid>,
+    Json(payload): Json<CreatePageInvite>,
+) -> Result<Json<CreatedPageInvite>, AppError> {
+    let user_id = require_share(&state, &claims, page_id).await?;
+
+    let role = payload.role.unwrap_or(PageRole::Viewer);
+    if role == PageRole::Owner {
+        return Err(AppError::BadRequest(
+            "The owner role cannot be granted".to_string(),
+        ));
+    }
+    if payload.max_uses.is_some_and(|n| n < 1) {
+        return Err(AppError::BadRequest(
+            "max_uses must be at least 1".to_string(),
+        ));
+    }
+    if payload.expires_at.is_some_and(|t| t <= Utc::now()) {
+        return Err(AppError::BadRequest(
+            "expires_at must be in the future".to_string(),
+        ));
+    }
+
+    let (invite, token) = state
+        .page_repo
+        .create_invite(page_id, role, payload.max_uses, payload.expires_at, user_id)
+        .await?;
+
+    Ok(Json(CreatedPageInvite { invite, token }))
+}
+
+// Outstanding and spent invites for a page
+async fn list_invites(
+    State(state): State<InvitesRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path(page_id): Path<Uuid>,
+) -> Result<Json<Vec<PageInvite>>, AppError> {
+    require_share(&state, &claims, page_id).await?;
+
+    let invites = state.page_repo.list_invites(page_id).await?;
+
+    Ok(Json(invites))
+}
+
+async fn revoke_invite(
+    State(state): State<InvitesRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path((page_id, invite_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    require_share(&state, &claims, page_id).await?;
+
+    if !state.page_repo.delete_invite(page_id, invite_id).await? {
+        return Err(AppError::NotFound);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Redeem an invite for the current user. Never lowers a role the user already has.
+async fn accept_invite(
+    State(state): State<InvitesRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path(token): Path<String>,
+) -> Result<Json<PageWithPermission>, AppError> {
+    require_scope(&claims, Scope::PagesWrite)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    let invite = state
+        .page_repo
+        .find_invite_by_token(&token)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    require_page(&claims, invite.page_id, false)?;
+
+    let spent = || AppError::BadRequest("This invite has expired or been used up".to_string());
+
+    let role = match state.page_repo.get_role(invite.page_id, user_id).await? {
+        Some(current) if current >= invite.role => current,
+        _ if invite.is_spent() => return Err(spent()),
+        _ => {
+            state
+                .page_repo
+                .redeem_invite(invite.id, user_id)
+                .await?
+                .ok_or_else(spent)?
+                .role
+        }
+    };
+
+    let page = state
+        .page_repo
+        .find_by_id(invite.page_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let hidden = state.page_repo.is_hidden(page.id, user_id).await?;
+
+    Ok(Json(PageWithPermission {
+        is_creator: role == PageRole::Owner,
+        hidden,
+        can_edit: role.allows(PageAction::Edit) && claims.allows_page(page.id, true),
+        role,
+        page,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CreatePage, CreateUser, User};
+    use crate::repositories::UserRepository;
+    use crate::tests_utils::setup_db;
+    use axum::body::{self, Body};
+    use axum::http::{Method, Request};
+    use tower::util::ServiceExt;
+
+    fn claims_for(user: &User) -> Claims {
+        Claims {
+            sub: user.id.to_string(),
+            username: user.username.clone(),
+            exp: 9999999999,
+            scopes: None,
+            pages: None,
+            sid: None,
+        }
+    }
+
+    async fn create_user(user_repo: &UserRepository, name: &str) -> anyhow::Result<User> {
+        user_repo
+            .create(CreateUser {
+                twitch_id: Some(format!("tw_{}", name)),
+                username: name.to_string(),
+                display_name: None,
+                profile_image_url: None,
+                email: None,
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_invite_link_lifecycle() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let user_repo = UserRepository::new(pool.clone());
+        let page_repo = Arc::new(PageRepository::new(pool.clone()));
+
+        let owner = create_user(&user_repo, "owner").await?;
+        let guest = create_user(&user_repo, "guest").await?;
+        let late = create_user(&user_repo, "late").await?;
+        let page = page_repo
+            .create(owner.id, CreatePage {
+                title: "Shared".to_string(),
+                description: None,
+            })
+            .await?;
+
+        let app = invites_router(InvitesRouterState {
+            page_repo: page_repo.clone(),
+        });
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("/pages/{}/invites", page.id))
+            .header("content-type", "application/json")
+            .extension(claims_for(&owner))
+            .body(Body::from(serde_json::to_vec(&serde_json::json!({
+                "role": "checker",
+                "max_uses": 1
+            }))?))?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 200);
+        let bytes = body::to_bytes(resp.into_body(), 64 * 1024).await?;
+        let created: serde_json::Value = serde_json::from_slice(&bytes)?;
+        let token = created["token"].as_str().unwrap().to_string();
+        assert!(created.get("token_hash").is_none());
+
+        let accept = |user: &User| {
+            Request::builder()
+                .method(Method::POST)
+                .uri(format!("/invites/{}/accept", token))
+                .extension(claims_for(user))
+                .body(Body::empty())
+        };
+
+        let resp = app.clone().oneshot(accept(&guest)?).await.unwrap();
+        assert_eq!(resp.status(), 200);
+        assert_eq!(page_repo.get_role(page.id, guest.id).await?, Some(PageRole::Checker));
+
+        // Accepting again is a no-op and doesn't burn the single use
+        let resp = app.clone().oneshot(accept(&guest)?).await.unwrap();
+        assert_eq!(resp.status(), 200);
+
+        // Nobody else can use it afterwards
+        let resp = app.clone().oneshot(accept(&late)?).await.unwrap();
+        assert_eq!(resp.status(), 400);
+        assert_eq!(page_repo.get_role(page.id, late.id).await?, None);
+
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(format!("/pages/{}/invites", page.id))
+            .extension(claims_for(&owner))
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        let bytes = body::to_bytes(resp.into_body(), 64 * 1024).await?;
+        let invites: Vec<serde_json::Value> = serde_json::from_slice(&bytes)?;
+        assert_eq!(invites.len(), 1);
+        assert_eq!(invites[0]["uses"].as_i64(), Some(1));
+
+        // Collaborators below manager can't see or revoke invites
+        let invite_id = invites[0]["id"].as_str().unwrap();
+        let req = Request::builder()
+            .method(Method::DELETE)
+            .uri(format!("/pages/{}/invites/{}", page.id, invite_id))
+            .extension(claims_for(&guest))
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 403);
+
+        let req = Request::builder()
+            .method(Method::DELETE)
+            .uri(format!("/pages/{}/invites/{}", page.id, invite_id))
+            .extension(claims_for(&owner))
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 204);
+
+        let resp = app.oneshot(accept(&late)?).await.unwrap();
+        assert_eq!(resp.status(), 404);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invite_restores_expired_access() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let user_repo = UserRepository::new(pool.clone());
+        let page_repo = Arc::new(PageRepository::new(pool.clone()));
+
+        let owner = create_user(&user_repo, "owner").await?;
+        let guest = create_user(&user_repo, "guest").await?;
+        let page = page_repo
+            .create(owner.id, CreatePage {
+                title: "Shared".to_string(),
+                description: None,
+            })
+            .await?;
+
+        // The guest had temporary access that ran out
+        let lapsed = chrono::Utc::now() - chrono::Duration::minutes(1);
+        page_repo
+            .grant_permission(page.id, guest.id, PageRole::Viewer, owner.id, Some(lapsed))
+            .await?;
+        assert_eq!(page_repo.get_role(page.id, guest.id).await?, None);
+
+        let (_, token) = page_repo
+            .create_invite(page.id, PageRole::Editor, None, None, owner.id)
+            .await?;
+        let app = invites_router(InvitesRouterState {
+            page_repo: page_repo.clone(),
+        });
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("/invites/{}/accept", token))
+            .extension(claims_for(&guest))
+            .body(Body::empty())?;
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 200);
+
+        // The new grant replaces the old expiry instead of inheriting it
+        assert_eq!(page_repo.get_role(page.id, guest.id).await?, Some(PageRole::Editor));
+        let permission = page_repo.get_user_permission(page.id, guest.id).await?.unwrap();
+        assert_eq!(permission.expires_at, None);
+
+        Ok(())
+    }
+}
@@ -1,7 +1,9 @@
 use crate::error::AppError;
 use crate::models::{
-    Claims, CreateList, CreateListItem, List, ListItem, ListWithItems, UpdateList, UpdateListItem,
+    Claims, CreateList, CreateListItem, ItemSuggestion, List, ListAccess, ListItem, ListVisibility,
+    ListWithItems, PageAction, Scope, SetListAccess, UpdateList, UpdateListItem,
 };
+use crate::middleware::{require_page, require_scope};
 use crate::repositories::{ListRepository, PageRepository};
 use crate::validators::{validate_title, validate_item_content};
 use crate::error::FieldError;
@@ -9,7 +11,7 @@ use axum::{
     extract::{Path, State},
     http::StatusCode,
     response::Json,
-    routing::get,
+    routing::{get, post},
     Extension, Router,
 };
 use std::sync::Arc;
@@ -28,6 +30,19 @@ pub fn lists_router(state: ListsRouterState) -> Router {
             "/pages/:page_id/lists/:id",
             get(get_list).patch(update_list).delete(delete_list),
         )
+        .route(
+            "/pages/:page_id/lists/:id/access",
+            get(get_list_access).put(set_list_access),
+        )
+        .route("/pages/:page_id/suggestions", get(list_suggestions))
+        .route(
+            "/pages/:page_id/suggestions/:id/approve",
+            post(approve_suggestion),
+        )
+        .route(
+            "/pages/:page_id/suggestions/:id/reject",
+            post(reject_suggestion),
+        )
         .route("/lists/:list_id/items", get(list_items).post(create_item))
         .route(
             "/lists/:list_id/items/:id",
@@ -42,15 +57,24 @@ async fn list_lists(
     Extension(claims): Extension<Claims>,
     Path(page_id): Path<Uuid>,
 ) -> Result<Json<Vec<List>>, AppError> {
+    require_scope(&claims, Scope::ListsRead)?;
+    require_page(&claims, page_id, false)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
     // Check if user has access to page
-    if !state.page_repo.check_access(page_id, user_id).await? {
-        return Err(AppError::Forbidden);
-    }
+    let role = state
+        .page_repo
+        .get_role(page_id, user_id)
+        .await?
+        .ok_or(AppError::Forbidden)?;
+
+    let mut lists = state.list_repo.list_by_page_id(page_id).await?;
 
-    let lists = state.list_repo.list_by_page_id(page_id).await?;
+    // Leave out restricted and private lists the user isn't allowed to see
+    let member_of = state.list_repo.member_list_ids(page_id, user_id).await?;
+    lists.retain(|l| l.visible_to(role, member_of.contains(&l.id)));
 
     Ok(Json(lists))
 }
@@ -62,15 +86,14 @@ async fn create_list(
     Path(page_id): Path<Uuid>,
     Json(payload): Json<CreateList>,
 ) -> Result<Json<List>, AppError> {
+    require_scope(&claims, Scope::ListsWrite)?;
+    require_page(&claims, page_id, true)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
     // Check if user can edit
-    if !state
-        .page_repo
-        .check_edit_permission(page_id, user_id)
-        .await?
-    {
+    if !state.page_repo.can(page_id, user_id, PageAction::Edit).await? {
         return Err(AppError::Forbidden);
     }
 
@@ -101,19 +124,13 @@ async fn get_list(
     Extension(claims): Extension<Claims>,
     Path((page_id, list_id)): Path<(Uuid, Uuid)>,
 ) -> Result<Json<ListWithItems>, AppError> {
+    require_scope(&claims, Scope::ListsRead)?;
+    require_page(&claims, page_id, false)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
-    // Check if user has access to page
-    if !state.page_repo.check_access(page_id, user_id).await? {
-        return Err(AppError::Forbidden);
-    }
-
-    let list = state
-        .list_repo
-        .find_by_id(list_id, page_id)
-        .await?
-        .ok_or(AppError::NotFound)?;
+    let list = require_list(&state, page_id, list_id, user_id, PageAction::View).await?;
 
     let items = state.list_repo.list_items_by_list_id(list_id).await?;
 
@@ -127,17 +144,20 @@ async fn update_list(
     Path((page_id, list_id)): Path<(Uuid, Uuid)>,
     Json(payload): Json<UpdateList>,
 ) -> Result<Json<List>, AppError> {
+    require_scope(&claims, Scope::ListsWrite)?;
+    require_page(&claims, page_id, true)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
-    // Check if user can edit
-    if !state
-        .page_repo
-        .check_edit_permission(page_id, user_id)
-        .await?
-    {
-        return Err(AppError::Forbidden);
-    }
+    // Letting anonymous visitors change a list is a sharing decision
+    let action = if payload.public_checking.is_some() || payload.public_suggestions.is_some() {
+        require_scope(&claims, Scope::SharingAdmin)?;
+        PageAction::Share
+    } else {
+        PageAction::Edit
+    };
+    require_list(&state, page_id, list_id, user_id, action).await?;
 
     // Validate title if provided
     let mut payload = payload;
@@ -160,21 +180,172 @@ async fn delete_list(
     Extension(claims): Extension<Claims>,
     Path((page_id, list_id)): Path<(Uuid, Uuid)>,
 ) -> Result<StatusCode, AppError> {
+    require_scope(&claims, Scope::ListsWrite)?;
+    require_page(&claims, page_id, true)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
-    // Check if user can edit
-    if !state
+    require_list(&state, page_id, list_id, user_id, PageAction::Edit).await?;
+
+    state.list_repo.delete_list(list_id, page_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Who can see a list beyond the page's own access
+async fn get_list_access(
+    State(state): State<ListsRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path((page_id, list_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ListAccess>, AppError> {
+    require_scope(&claims, Scope::ListsRead)?;
+    require_page(&claims, page_id, false)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    let list = require_list(&state, page_id, list_id, user_id, PageAction::Share).await?;
+    let user_ids = state.list_repo.list_members(list_id).await?;
+
+    Ok(Json(ListAccess {
+        visibility: list.visibility,
+        min_role: list.min_role,
+        user_ids,
+    }))
+}
+
+// Restrict a list; like sharing the page, this is for managers and the owner
+async fn set_list_access(
+    State(state): State<ListsRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path((page_id, list_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<SetListAccess>,
+) -> Result<Json<List>, AppError> {
+    require_scope(&claims, Scope::SharingAdmin)?;
+    require_page(&claims, page_id, true)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    require_list(&state, page_id, list_id, user_id, PageAction::Share).await?;
+
+    if payload.visibility != ListVisibility::Restricted
+        && (payload.min_role.is_some() || !payload.user_ids.is_empty())
+    {
+        return Err(AppError::BadRequest(
+            "Only restricted lists take a minimum role or members".to_string(),
+        ));
+    }
+    for member_id in &payload.user_ids {
+        if state.page_repo.get_role(page_id, *member_id).await?.is_none() {
+            return Err(AppError::BadRequest(
+                "List members must have access to the page".to_string(),
+            ));
+        }
+    }
+
+    let list = state
+        .list_repo
+        .set_list_access(list_id, page_id, payload.visibility, payload.min_role, &payload.user_ids)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    Ok(Json(list))
+}
+
+// Items visitors suggested on the page's public lists, waiting for review
+async fn list_suggestions(
+    State(state): State<ListsRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path(page_id): Path<Uuid>,
+) -> Result<Json<Vec<ItemSuggestion>>, AppError> {
+    require_scope(&claims, Scope::ListsRead)?;
+    require_page(&claims, page_id, false)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    let role = state
         .page_repo
-        .check_edit_permission(page_id, user_id)
+        .get_role(page_id, user_id)
         .await?
-    {
+        .ok_or(AppError::Forbidden)?;
+    if !role.allows(PageAction::Edit) {
         return Err(AppError::Forbidden);
     }
 
-    state.list_repo.delete_list(list_id, page_id).await?;
+    // A list may have been restricted since the suggestion came in
+    let member_of = state.list_repo.member_list_ids(page_id, user_id).await?;
+    let visible: Vec<Uuid> = state
+        .list_repo
+        .list_by_page_id(page_id)
+        .await?
+        .into_iter()
+        .filter(|l| l.visible_to(role, member_of.contains(&l.id)))
+        .map(|l| l.id)
+        .collect();
 
-    Ok(StatusCode::NO_CONTENT)
+    let mut suggestions = state.list_repo.list_pending_suggestions(page_id).await?;
+    suggestions.retain(|s| visible.contains(&s.list_id));
+
+    Ok(Json(suggestions))
+}
+
+// Accept a suggestion, adding it to the end of its list
+async fn approve_suggestion(
+    State(state): State<ListsRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path((page_id, suggestion_id)): Path<(Uuid, Uuid)>,
+) -> Result<(StatusCode, Json<ListItem>), AppError> {
+    require_scope(&claims, Scope::ListsWrite)?;
+    require_page(&claims, page_id, true)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    let suggestion = state
+        .list_repo
+        .find_pending_suggestion(suggestion_id, page_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    require_list(&state, page_id, suggestion.list_id, user_id, PageAction::Edit).await?;
+
+    let item = state
+        .list_repo
+        .approve_suggestion(suggestion_id, user_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    Ok((StatusCode::CREATED, Json(item)))
+}
+
+// Decline a suggestion; it stays on record but leaves the queue
+async fn reject_suggestion(
+    State(state): State<ListsRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path((page_id, suggestion_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ItemSuggestion>, AppError> {
+    require_scope(&claims, Scope::ListsWrite)?;
+    require_page(&claims, page_id, true)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    let suggestion = state
+        .list_repo
+        .find_pending_suggestion(suggestion_id, page_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    require_list(&state, page_id, suggestion.list_id, user_id, PageAction::Edit).await?;
+
+    let suggestion = state
+        .list_repo
+        .reject_suggestion(suggestion_id, user_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    Ok(Json(suggestion))
 }
 
 // List all items in a list
@@ -183,6 +354,8 @@ async fn list_items(
     Extension(claims): Extension<Claims>,
     Path(list_id): Path<Uuid>,
 ) -> Result<Json<Vec<ListItem>>, AppError> {
+    require_scope(&claims, Scope::ListsRead)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
@@ -193,10 +366,8 @@ async fn list_items(
         .await?
         .ok_or(AppError::NotFound)?;
 
-    // Check if user has access to page
-    if !state.page_repo.check_access(page_id, user_id).await? {
-        return Err(AppError::Forbidden);
-    }
+    require_page(&claims, page_id, false)?;
+    require_list(&state, page_id, list_id, user_id, PageAction::View).await?;
 
     let items = state.list_repo.list_items_by_list_id(list_id).await?;
 
@@ -210,6 +381,8 @@ async fn create_item(
     Path(list_id): Path<Uuid>,
     Json(payload): Json<CreateListItem>,
 ) -> Result<Json<ListItem>, AppError> {
+    require_scope(&claims, Scope::ListsWrite)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
@@ -220,14 +393,8 @@ async fn create_item(
         .await?
         .ok_or(AppError::NotFound)?;
 
-    // Check if user can edit
-    if !state
-        .page_repo
-        .check_edit_permission(page_id, user_id)
-        .await?
-    {
-        return Err(AppError::Forbidden);
-    }
+    require_page(&claims, page_id, true)?;
+    require_list(&state, page_id, list_id, user_id, PageAction::Edit).await?;
 
     // Validate content
     let content = validate_item_content(&payload.content)?;
@@ -245,6 +412,8 @@ async fn get_item(
     Extension(claims): Extension<Claims>,
     Path((list_id, item_id)): Path<(Uuid, Uuid)>,
 ) -> Result<Json<ListItem>, AppError> {
+    require_scope(&claims, Scope::ListsRead)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
@@ -255,10 +424,8 @@ async fn get_item(
         .await?
         .ok_or(AppError::NotFound)?;
 
-    // Check if user has access to page
-    if !state.page_repo.check_access(page_id, user_id).await? {
-        return Err(AppError::Forbidden);
-    }
+    require_page(&claims, page_id, false)?;
+    require_list(&state, page_id, list_id, user_id, PageAction::View).await?;
 
     let item = state
         .list_repo
@@ -276,6 +443,8 @@ async fn update_item(
     Path((list_id, item_id)): Path<(Uuid, Uuid)>,
     Json(payload): Json<UpdateListItem>,
 ) -> Result<Json<ListItem>, AppError> {
+    require_scope(&claims, Scope::ListsWrite)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
@@ -286,14 +455,15 @@ async fn update_item(
         .await?
         .ok_or(AppError::NotFound)?;
 
-    // Check if user can edit
-    if !state
-        .page_repo
-        .check_edit_permission(page_id, user_id)
-        .await?
-    {
-        return Err(AppError::Forbidden);
-    }
+    require_page(&claims, page_id, true)?;
+
+    // Ticking an item is enough for checkers; anything else is an edit
+    let action = if payload.content.is_none() && payload.position.is_none() {
+        PageAction::CheckItems
+    } else {
+        PageAction::Edit
+    };
+    require_list(&state, page_id, list_id, user_id, action).await?;
 
     // Validate content if provided
     let mut payload = payload;
@@ -316,6 +486,8 @@ async fn delete_item(
     Extension(claims): Extension<Claims>,
     Path((list_id, item_id)): Path<(Uuid, Uuid)>,
 ) -> Result<StatusCode, AppError> {
+    require_scope(&claims, Scope::ListsWrite)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
@@ -326,18 +498,44 @@ async fn delete_item(
         .await?
         .ok_or(AppError::NotFound)?;
 
-    // Check if user can edit
-    if !state
+    require_page(&claims, page_id, true)?;
+    require_list(&state, page_id, list_id, user_id, PageAction::Edit).await?;
+
+    state.list_repo.delete_item(item_id, list_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Checks `action` on the page and that the list is visible to the user. Lists
+/// hidden from them are reported as missing rather than forbidden.
+async fn require_list(
+    state: &ListsRouterState,
+    page_id: Uuid,
+    list_id: Uuid,
+    user_id: Uuid,
+    action: PageAction,
+) -> Result<List, AppError> {
+    let role = state
         .page_repo
-        .check_edit_permission(page_id, user_id)
+        .get_role(page_id, user_id)
         .await?
-    {
+        .ok_or(AppError::Forbidden)?;
+    if !role.allows(action) {
         return Err(AppError::Forbidden);
     }
 
-    state.list_repo.delete_item(item_id, list_id).await?;
+    let list = state
+        .list_repo
+        .find_by_id(list_id, page_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let is_member = list.visibility == ListVisibility::Restricted
+        && state.list_repo.is_list_member(list_id, user_id).await?;
+    if !list.visible_to(role, is_member) {
+        return Err(AppError::NotFound);
+    }
 
-    Ok(StatusCode::NO_CONTENT)
+    Ok(list)
 }
 
 #[cfg(test)]
@@ -357,7 +555,7 @@ mod tests {
         let user_repo = crate::repositories::UserRepository::new(pool.clone());
         user_repo
             .create(CreateUser {
-                twitch_id: twitch_id.to_string(),
+                twitch_id: Some(twitch_id.to_string()),
                 username: username.to_string(),
                 display_name: None,
                 profile_image_url: None,
@@ -369,10 +567,11 @@ mod tests {
     fn create_claims(user: &crate::models::User) -> Claims {
         Claims {
             sub: user.id.to_string(),
-            twitch_id: user.twitch_id.clone(),
             username: user.username.clone(),
             exp: 9999999999,
             scopes: None,
+            pages: None,
+            sid: None,
         }
     }
 
@@ -665,4 +864,492 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_page_bound_api_key() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let owner = create_test_user(&pool, "bound", "bound").await?;
+
+        let page_repo = Arc::new(crate::repositories::PageRepository::new(pool.clone()));
+        let list_repo = Arc::new(crate::repositories::ListRepository::new(pool.clone()));
+
+        let allowed = page_repo
+            .create(
+                owner.id,
+                CreatePage {
+                    title: "Shopping".to_string(),
+                    description: None,
+                },
+            )
+            .await?;
+        let other = page_repo
+            .create(
+                owner.id,
+                CreatePage {
+                    title: "Budget".to_string(),
+                    description: None,
+                },
+            )
+            .await?;
+
+        let app = lists_router(ListsRouterState {
+            page_repo,
+            list_repo,
+        });
+
+        // Key bound read-only to the shopping page
+        let mut claims = create_claims(&owner);
+        claims.scopes = Some(vec!["lists:read".to_string(), "lists:write".to_string()]);
+        claims.pages = Some(vec![crate::models::ApiKeyPage {
+            page_id: allowed.id,
+            read_only: true,
+        }]);
+
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(format!("/pages/{}/lists", allowed.id))
+            .extension(claims.clone())
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("/pages/{}/lists", allowed.id))
+            .header("content-type", "application/json")
+            .extension(claims.clone())
+            .body(Body::from(serde_json::to_vec(&serde_json::json!({ "title": "New" }))?))?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 403);
+
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(format!("/pages/{}/lists", other.id))
+            .extension(claims)
+            .body(Body::empty())?;
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 403);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_checker_can_only_tick_items() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let owner = create_test_user(&pool, "owner", "owner").await?;
+        let checker = create_test_user(&pool, "checker", "checker").await?;
+
+        let page_repo = Arc::new(crate::repositories::PageRepository::new(pool.clone()));
+        let list_repo = Arc::new(crate::repositories::ListRepository::new(pool.clone()));
+
+        let page = page_repo
+            .create(
+                owner.id,
+                CreatePage {
+                    title: "Chores".to_string(),
+                    description: None,
+                },
+            )
+            .await?;
+        page_repo
+            .grant_permission(page.id, checker.id, crate::models::PageRole::Checker, owner.id, None)
+            .await?;
+
+        let list = list_repo
+            .create_list(
+                page.id,
+                crate::models::CreateList {
+                    title: "List".to_string(),
+                    position: None,
+                    show_checkboxes: None,
+                    show_progress: None,
+                },
+            )
+            .await?;
+        let item = list_repo
+            .create_item(
+                list.id,
+                CreateListItem {
+                    content: "Dishes".to_string(),
+                    position: None,
+                },
+            )
+            .await?;
+
+        let app = lists_router(ListsRouterState {
+            page_repo,
+            list_repo,
+        });
+        let claims = create_claims(&checker);
+
+        // Ticking is allowed
+        let req = Request::builder()
+            .method(Method::PATCH)
+            .uri(format!("/lists/{}/items/{}", list.id, item.id))
+            .header("content-type", "application/json")
+            .extension(claims.clone())
+            .body(Body::from(serde_json::to_vec(&serde_json::json!({ "checked": true }))?))?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 200);
+
+        // Changing the text is not
+        let req = Request::builder()
+            .method(Method::PATCH)
+            .uri(format!("/lists/{}/items/{}", list.id, item.id))
+            .header("content-type", "application/json")
+            .extension(claims.clone())
+            .body(Body::from(serde_json::to_vec(&serde_json::json!({ "content": "Laundry" }))?))?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 403);
+
+        // Neither is adding items
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("/lists/{}/items", list.id))
+            .header("content-type", "application/json")
+            .extension(claims)
+            .body(Body::from(serde_json::to_vec(&serde_json::json!({ "content": "Laundry" }))?))?;
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 403);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_public_write_flags_need_share() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let owner = create_test_user(&pool, "owner", "owner").await?;
+        let editor = create_test_user(&pool, "editor", "editor").await?;
+
+        let page_repo = Arc::new(crate::repositories::PageRepository::new(pool.clone()));
+        let list_repo = Arc::new(crate::repositories::ListRepository::new(pool.clone()));
+
+        let page = page_repo
+            .create(
+                owner.id,
+                CreatePage {
+                    title: "Stream".to_string(),
+                    description: None,
+                },
+            )
+            .await?;
+        page_repo
+            .grant_permission(page.id, editor.id, crate::models::PageRole::Editor, owner.id, None)
+            .await?;
+        let list = list_repo
+            .create_list(
+                page.id,
+                crate::models::CreateList {
+                    title: "Requests".to_string(),
+                    position: None,
+                    show_checkboxes: None,
+                    show_progress: None,
+                },
+            )
+            .await?;
+
+        let app = lists_router(ListsRouterState {
+            page_repo,
+            list_repo,
+        });
+        let update = |claims: Claims, body: serde_json::Value| {
+            let app = app.clone();
+            async move {
+                let req = Request::builder()
+                    .method(Method::PATCH)
+                    .uri(format!("/pages/{}/lists/{}", page.id, list.id))
+                    .header("content-type", "application/json")
+                    .extension(claims)
+                    .body(Body::from(serde_json::to_vec(&body)?))?;
+                anyhow::Ok(app.oneshot(req).await.unwrap().status())
+            }
+        };
+
+        // Editors can rename the list but not open it to anonymous visitors
+        let status = update(create_claims(&editor), serde_json::json!({ "title": "Song requests" })).await?;
+        assert_eq!(status, 200);
+        let status = update(create_claims(&editor), serde_json::json!({ "public_checking": true })).await?;
+        assert_eq!(status, 403);
+        let status = update(create_claims(&editor), serde_json::json!({ "public_suggestions": true })).await?;
+        assert_eq!(status, 403);
+
+        // Nor can a key without sharing:admin
+        let mut key = create_claims(&owner);
+        key.scopes = Some(vec!["lists:write".to_string()]);
+        let status = update(key, serde_json::json!({ "public_suggestions": true })).await?;
+        assert_eq!(status, 403);
+
+        let status = update(create_claims(&owner), serde_json::json!({ "public_suggestions": true })).await?;
+        assert_eq!(status, 200);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list_visibility_overrides() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let owner = create_test_user(&pool, "owner", "owner").await?;
+        let manager = create_test_user(&pool, "manager", "manager").await?;
+        let viewer = create_test_user(&pool, "viewer", "viewer").await?;
+
+        let page_repo = Arc::new(crate::repositories::PageRepository::new(pool.clone()));
+        let list_repo = Arc::new(crate::repositories::ListRepository::new(pool.clone()));
+
+        let page = page_repo
+            .create(
+                owner.id,
+                CreatePage {
+                    title: "Wedding".to_string(),
+                    description: None,
+                },
+            )
+            .await?;
+        page_repo
+            .grant_permission(page.id, manager.id, crate::models::PageRole::Manager, owner.id, None)
+            .await?;
+        page_repo
+            .grant_permission(page.id, viewer.id, crate::models::PageRole::Viewer, owner.id, None)
+            .await?;
+
+        let mut lists = Vec::new();
+        for title in ["Guest list", "Budget", "Diary"] {
+            lists.push(
+                list_repo
+                    .create_list(
+                        page.id,
+                        crate::models::CreateList {
+                            title: title.to_string(),
+                            position: None,
+                            show_checkboxes: None,
+                            show_progress: None,
+                        },
+                    )
+                    .await?,
+            );
+        }
+        let (guests, budget, diary) = (&lists[0], &lists[1], &lists[2]);
+
+        let app = lists_router(ListsRouterState {
+            page_repo,
+            list_repo,
+        });
+        let set_access = |claims: Claims, list_id: Uuid, body: serde_json::Value| {
+            let app = app.clone();
+            let uri = format!("/pages/{}/lists/{}/access", page.id, list_id);
+            async move {
+                let req = Request::builder()
+                    .method(Method::PUT)
+                    .uri(uri)
+                    .header("content-type", "application/json")
+                    .extension(claims)
+                    .body(Body::from(serde_json::to_vec(&body)?))?;
+                anyhow::Ok(app.oneshot(req).await.unwrap().status())
+            }
+        };
+        let visible_titles = |claims: Claims| {
+            let app = app.clone();
+            let uri = format!("/pages/{}/lists", page.id);
+            async move {
+                let req = Request::builder()
+                    .method(Method::GET)
+                    .uri(uri)
+                    .extension(claims)
+                    .body(Body::empty())?;
+                let resp = app.oneshot(req).await.unwrap();
+                let bytes = body::to_bytes(resp.into_body(), 64 * 1024).await?;
+                let lists: Vec<serde_json::Value> = serde_json::from_slice(&bytes)?;
+                anyhow::Ok(
+                    lists
+                        .iter()
+                        .map(|l| l["title"].as_str().unwrap_or_default().to_string())
+                        .collect::<Vec<_>>(),
+                )
+            }
+        };
+
+        // Viewers can't restrict lists
+        let status = set_access(
+            create_claims(&viewer),
+            budget.id,
+            serde_json::json!({ "visibility": "private" }),
+        )
+        .await?;
+        assert_eq!(status, 403);
+
+        // Budget for managers and up, diary for the owner alone
+        let status = set_access(
+            create_claims(&manager),
+            budget.id,
+            serde_json::json!({ "visibility": "restricted", "min_role": "manager" }),
+        )
+        .await?;
+        assert_eq!(status, 200);
+        let status = set_access(
+            create_claims(&owner),
+            diary.id,
+            serde_json::json!({ "visibility": "private", "user_ids": [viewer.id] }),
+        )
+        .await?;
+        assert_eq!(status, 400);
+        let status = set_access(
+            create_claims(&owner),
+            diary.id,
+            serde_json::json!({ "visibility": "private" }),
+        )
+        .await?;
+        assert_eq!(status, 200);
+
+        assert_eq!(visible_titles(create_claims(&owner)).await?.len(), 3);
+        assert_eq!(visible_titles(create_claims(&manager)).await?, ["Guest list", "Budget"]);
+        assert_eq!(visible_titles(create_claims(&viewer)).await?, ["Guest list"]);
+
+        // Hidden lists look missing, items included
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(format!("/lists/{}/items", budget.id))
+            .extension(create_claims(&viewer))
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 404);
+
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(format!("/pages/{}/lists/{}", page.id, diary.id))
+            .extension(create_claims(&manager))
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 404);
+
+        // Named members see a restricted list regardless of their role
+        let status = set_access(
+            create_claims(&owner),
+            budget.id,
+            serde_json::json!({
+                "visibility": "restricted",
+                "min_role": "manager",
+                "user_ids": [viewer.id]
+            }),
+        )
+        .await?;
+        assert_eq!(status, 200);
+        assert_eq!(visible_titles(create_claims(&viewer)).await?, ["Guest list", "Budget"]);
+        assert_eq!(guests.visibility, ListVisibility::Page);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_moderate_suggestions() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let owner = create_test_user(&pool, "owner", "owner").await?;
+        let viewer = create_test_user(&pool, "viewer", "viewer").await?;
+
+        let page_repo = Arc::new(crate::repositories::PageRepository::new(pool.clone()));
+        let list_repo = Arc::new(crate::repositories::ListRepository::new(pool.clone()));
+
+        let page = page_repo
+            .create(
+                owner.id,
+                CreatePage {
+                    title: "Potluck".to_string(),
+                    description: None,
+                },
+            )
+            .await?;
+        page_repo
+            .grant_permission(page.id, viewer.id, crate::models::PageRole::Viewer, owner.id, None)
+            .await?;
+        let list = list_repo
+            .create_list(
+                page.id,
+                crate::models::CreateList {
+                    title: "Dishes".to_string(),
+                    position: None,
+                    show_checkboxes: None,
+                    show_progress: None,
+                },
+            )
+            .await?;
+        list_repo
+            .create_item(
+                list.id,
+                CreateListItem {
+                    content: "Salad".to_string(),
+                    position: None,
+                },
+            )
+            .await?;
+
+        let mut suggestions = Vec::new();
+        for content in ["Lasagna", "Glitter"] {
+            suggestions.push(
+                list_repo
+                    .create_suggestion(
+                        list.id,
+                        crate::models::CreateItemSuggestion {
+                            content: content.to_string(),
+                            author_name: Some("Guest".to_string()),
+                        },
+                    )
+                    .await?,
+            );
+        }
+
+        let app = lists_router(ListsRouterState {
+            page_repo,
+            list_repo: list_repo.clone(),
+        });
+        let send = |claims: Claims, method: Method, uri: String| {
+            let app = app.clone();
+            async move {
+                let req = Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .extension(claims)
+                    .body(Body::empty())?;
+                let resp = app.oneshot(req).await.unwrap();
+                let status = resp.status();
+                let bytes = body::to_bytes(resp.into_body(), 64 * 1024).await?;
+                let json: serde_json::Value =
+                    serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null);
+                anyhow::Ok((status, json))
+            }
+        };
+        let queue = format!("/pages/{}/suggestions", page.id);
+        let approve = format!("/pages/{}/suggestions/{}/approve", page.id, suggestions[0].id);
+        let reject = format!("/pages/{}/suggestions/{}/reject", page.id, suggestions[1].id);
+
+        // Viewers can neither see nor decide on the queue
+        let (status, _) = send(create_claims(&viewer), Method::GET, queue.clone()).await?;
+        assert_eq!(status, 403);
+        let (status, _) = send(create_claims(&viewer), Method::POST, approve.clone()).await?;
+        assert_eq!(status, 403);
+
+        let (status, json) = send(create_claims(&owner), Method::GET, queue.clone()).await?;
+        assert_eq!(status, 200);
+        assert_eq!(json.as_array().map(|a| a.len()), Some(2));
+
+        let (status, json) = send(create_claims(&owner), Method::POST, approve.clone()).await?;
+        assert_eq!(status, 201);
+        assert_eq!(json["content"].as_str(), Some("Lasagna"));
+        assert_eq!(json["position"].as_i64(), Some(1));
+
+        let (status, json) = send(create_claims(&owner), Method::POST, reject).await?;
+        assert_eq!(status, 200);
+        assert_eq!(json["status"].as_str(), Some("rejected"));
+
+        // Decided suggestions leave the queue and can't be approved twice
+        let (_, json) = send(create_claims(&owner), Method::GET, queue).await?;
+        assert_eq!(json.as_array().map(|a| a.len()), Some(0));
+        let (status, _) = send(create_claims(&owner), Method::POST, approve).await?;
+        assert_eq!(status, 404);
+
+        let items = list_repo.list_items_by_list_id(list.id).await?;
+        assert_eq!(
+            items.iter().map(|i| i.content.as_str()).collect::<Vec<_>>(),
+            ["Salad", "Lasagna"]
+        );
+
+        Ok(())
+    }
 }
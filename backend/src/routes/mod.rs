@@ -1,13 +1,21 @@
+pub mod access_requests;
 pub mod auth;
 pub mod api_keys;
+pub mod invites;
 pub mod lists;
 pub mod pages;
 pub mod public;
+pub mod sessions;
+pub mod teams;
 pub mod users;
 
+pub use access_requests::{access_requests_router, AccessRequestsRouterState};
 pub use auth::{auth_router, AuthRouterState};
 pub use api_keys::{api_keys_router, ApiKeysRouterState};
+pub use invites::{invites_router, InvitesRouterState};
 pub use lists::{lists_router, ListsRouterState};
 pub use pages::{pages_router, PagesRouterState};
-pub use public::{public_router, PublicRouterState};
+pub use public::{public_router, shared_link_router, PublicRouterState};
+pub use sessions::{sessions_router, SessionsRouterState};
+pub use teams::{teams_router, TeamsRouterState};
 pub use users::{users_router, UsersRouterState};
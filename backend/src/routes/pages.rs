@@ -1,17 +1,25 @@
 use crate::error::AppError;
 use crate::models::{
-    Claims, CreatePage, GrantPermission, Page, PagePermissionWithUser,
-    PageWithPermission, SetPublicSlug, UpdatePage, UpdatePermission,
+    requested_role, Claims, CreatePage, CreatePageTransfer, GrantPermission, Page, PageAction,
+    PagePermissionWithUser, PageRole, PageTransfer, PageTransferWithPage, PageWithPermission,
+    PendingPermission, Scope, SetPublicSlug, UpdateMembership, UpdatePage, UpdatePermission,
+    LOGIN_SHARE_PROVIDERS,
+};
+use crate::middleware::{require_page, require_scope};
+use crate::repositories::{PageRepository, TeamRepository};
+use crate::services::public_link;
+use crate::validators::{
+    validate_description, validate_expiry, validate_public_password, validate_public_slug,
+    validate_title,
 };
-use crate::repositories::PageRepository;
-use crate::validators::{validate_title, validate_description, validate_public_slug};
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
-    response::Json,
-    routing::{get, patch, put},
+    response::{IntoResponse, Json, Response},
+    routing::{delete, get, patch, post, put},
     Extension, Router,
 };
+use serde::Deserialize;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -20,6 +28,9 @@ use uuid::Uuid;
 #[derive(Clone)]
 pub struct PagesRouterState {
     pub page_repo: Arc<PageRepository>,
+    pub team_repo: Arc<TeamRepository>,
+    /// How long another page's released public slug stays off limits.
+    pub slug_cooldown: chrono::Duration,
 }
 
 
@@ -31,6 +42,10 @@ pub fn pages_router(state: PagesRouterState) -> Router {
             get(get_page).patch(update_page).delete(delete_page),
         )
         .route("/pages/:id/public-slug", put(set_public_slug))
+        .route(
+            "/pages/:id/membership",
+            patch(update_membership).delete(leave_page),
+        )
         .route(
             "/pages/:id/permissions",
             get(list_permissions).post(grant_permission),
@@ -39,18 +54,48 @@ pub fn pages_router(state: PagesRouterState) -> Router {
             "/pages/:id/permissions/:permission_id",
             patch(update_permission).delete(revoke_permission),
         )
+        .route("/pages/:id/pending-permissions", get(list_pending_permissions))
+        .route(
+            "/pages/:id/pending-permissions/:pending_id",
+            delete(revoke_pending_permission),
+        )
+        .route(
+            "/pages/:id/transfer",
+            get(get_transfer).post(create_transfer).delete(cancel_transfer),
+        )
+        .route("/pages/:id/transfer/accept", post(accept_transfer))
+        .route("/page-transfers", get(list_incoming_transfers))
         .with_state(state)
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ListPagesQuery {
+    #[serde(default)]
+    pub include_hidden: bool,
+}
+
 // List all pages user has access to
 async fn list_pages(
     State(state): State<PagesRouterState>,
     Extension(claims): Extension<Claims>,
+    Query(query): Query<ListPagesQuery>,
 ) -> Result<Json<Vec<PageWithPermission>>, AppError> {
+    require_scope(&claims, Scope::PagesRead)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
-    let pages = state.page_repo.list_for_user(user_id).await?;
+    let mut pages = state.page_repo.list_for_user(user_id).await?;
+
+    if !query.include_hidden {
+        pages.retain(|p| !p.hidden);
+    }
+
+    // Page-bound API keys only see the pages they were issued for
+    pages.retain(|p| claims.allows_page(p.page.id, false));
+    for p in pages.iter_mut() {
+        p.can_edit = p.can_edit && claims.allows_page(p.page.id, true);
+    }
 
     Ok(Json(pages))
 }
@@ -61,6 +106,8 @@ async fn create_page(
     Extension(claims): Extension<Claims>,
     Json(payload): Json<CreatePage>,
 ) -> Result<Json<Page>, AppError> {
+    require_scope(&claims, Scope::PagesWrite)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
@@ -100,6 +147,9 @@ async fn get_page(
     Extension(claims): Extension<Claims>,
     Path(page_id): Path<Uuid>,
 ) -> Result<Json<PageWithPermission>, AppError> {
+    require_scope(&claims, Scope::PagesRead)?;
+    require_page(&claims, page_id, false)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
@@ -109,22 +159,21 @@ async fn get_page(
         .await?
         .ok_or(AppError::NotFound)?;
 
-    // Check if user is creator
-    let is_creator = page.creator_id == user_id;
-
-    // Check if user has permission
-    let permission = state.page_repo.get_user_permission(page_id, user_id).await?;
-
-    let can_edit = is_creator || permission.as_ref().map(|p| p.can_edit).unwrap_or(false);
+    let role = state
+        .page_repo
+        .get_role(page_id, user_id)
+        .await?
+        .ok_or(AppError::Forbidden)?;
 
-    if !is_creator && permission.is_none() {
-        return Err(AppError::Forbidden);
-    }
+    let can_edit = role.allows(PageAction::Edit) && claims.allows_page(page_id, true);
+    let hidden = state.page_repo.is_hidden(page_id, user_id).await?;
 
     Ok(Json(PageWithPermission {
+        is_creator: page.creator_id == user_id,
         page,
-        is_creator,
         can_edit,
+        role,
+        hidden,
     }))
 }
 
@@ -135,11 +184,14 @@ async fn update_page(
     Path(page_id): Path<Uuid>,
     Json(payload): Json<UpdatePage>,
 ) -> Result<Json<Page>, AppError> {
+    require_scope(&claims, Scope::PagesWrite)?;
+    require_page(&claims, page_id, true)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
     // Check if user can edit
-    if !state.page_repo.check_edit_permission(page_id, user_id).await? {
+    if !state.page_repo.can(page_id, user_id, PageAction::Edit).await? {
         return Err(AppError::Forbidden);
     }
 
@@ -181,17 +233,20 @@ async fn delete_page(
     Extension(claims): Extension<Claims>,
     Path(page_id): Path<Uuid>,
 ) -> Result<StatusCode, AppError> {
+    require_scope(&claims, Scope::PagesWrite)?;
+    require_page(&claims, page_id, true)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
-    // Only creator can delete
-    let page = state
+    // Only the owner can delete
+    state
         .page_repo
         .find_by_id(page_id)
         .await?
         .ok_or(AppError::NotFound)?;
 
-    if page.creator_id != user_id {
+    if !state.page_repo.can(page_id, user_id, PageAction::Delete).await? {
         return Err(AppError::Forbidden);
     }
 
@@ -207,17 +262,20 @@ async fn set_public_slug(
     Path(page_id): Path<Uuid>,
     Json(mut payload): Json<SetPublicSlug>,
 ) -> Result<Json<Page>, AppError> {
+    require_scope(&claims, Scope::SharingAdmin)?;
+    require_page(&claims, page_id, true)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
-    // Only creator can set public slug
-    let page = state
+    // Sharing is for managers and the owner
+    state
         .page_repo
         .find_by_id(page_id)
         .await?
         .ok_or(AppError::NotFound)?;
 
-    if page.creator_id != user_id {
+    if !state.page_repo.can(page_id, user_id, PageAction::Share).await? {
         return Err(AppError::Forbidden);
     }
 
@@ -230,9 +288,35 @@ async fn set_public_slug(
         }
     }
 
+    // Only the hash of a link password is stored
+    let password_hash = match payload.password {
+        Some(Some(ref password)) => {
+            let password = validate_public_password(password)?;
+            let hash = tokio::task::spawn_blocking(move || public_link::hash_password(&password))
+                .await
+                .map_err(|e| AppError::Internal(e.to_string()))?;
+            Some(Some(hash))
+        }
+        Some(None) => Some(None),
+        None => None,
+    };
+    if let Some(ref expires_at) = payload.expires_at {
+        validate_expiry(expires_at)?;
+    }
+
+    // Old links to another page keep redirecting there until the cooldown is over
+    if let Some(ref slug) = payload.public_slug {
+        let since = chrono::Utc::now() - state.slug_cooldown;
+        if state.page_repo.slug_reserved_by_other(slug, page_id, since).await? {
+            return Err(AppError::BadRequest(
+                "This slug was used by another page recently".to_string(),
+            ));
+        }
+    }
+
     let updated_page = state
         .page_repo
-        .set_public_slug(page_id, payload.public_slug)
+        .set_public_slug(page_id, payload.public_slug, password_hash, payload.expires_at)
         .await
         .map_err(|e| {
             if let Some(sqlx::Error::Database(db_err)) = e.downcast_ref::<sqlx::Error>() {
@@ -252,17 +336,20 @@ async fn list_permissions(
     Extension(claims): Extension<Claims>,
     Path(page_id): Path<Uuid>,
 ) -> Result<Json<Vec<PagePermissionWithUser>>, AppError> {
+    require_scope(&claims, Scope::SharingAdmin)?;
+    require_page(&claims, page_id, true)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
-    // Check if user is creator
-    let page = state
+    // Sharing is for managers and the owner
+    state
         .page_repo
         .find_by_id(page_id)
         .await?
         .ok_or(AppError::NotFound)?;
 
-    if page.creator_id != user_id {
+    if !state.page_repo.can(page_id, user_id, PageAction::Share).await? {
         return Err(AppError::Forbidden);
     }
 
@@ -277,28 +364,101 @@ async fn grant_permission(
     Extension(claims): Extension<Claims>,
     Path(page_id): Path<Uuid>,
     Json(payload): Json<GrantPermission>,
-) -> Result<Json<PagePermissionWithUser>, AppError> {
+) -> Result<Response, AppError> {
+    require_scope(&claims, Scope::SharingAdmin)?;
+    require_page(&claims, page_id, true)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
-    // Check if user is creator
-    let page = state
+    // Sharing is for managers and the owner
+    state
         .page_repo
         .find_by_id(page_id)
         .await?
         .ok_or(AppError::NotFound)?;
 
-    if page.creator_id != user_id {
+    if !state.page_repo.can(page_id, user_id, PageAction::Share).await? {
         return Err(AppError::Forbidden);
     }
 
+    let role = requested_role(payload.role, payload.can_edit);
+    if role == PageRole::Owner {
+        return Err(AppError::BadRequest(
+            "The owner role cannot be granted".to_string(),
+        ));
+    }
+    validate_expiry(&payload.expires_at)?;
+
+    // Sharing with a team is limited to its members
+    if let Some(team_id) = payload.team_id {
+        state
+            .team_repo
+            .get_role(team_id, user_id)
+            .await?
+            .ok_or_else(|| AppError::BadRequest("You are not a member of this team".to_string()))?;
+
+        let existing = state.page_repo.get_team_permission(page_id, team_id).await?;
+        if existing.is_some_and(|p| !p.is_expired()) {
+            return Err(AppError::BadRequest(
+                "Permission already exists".to_string(),
+            ));
+        }
+
+        let permission = state
+            .page_repo
+            .grant_team_permission(page_id, team_id, role, user_id, payload.expires_at)
+            .await?;
+        let team = state.team_repo.find_by_id(team_id).await?;
+
+        return Ok(Json(PagePermissionWithUser {
+            permission,
+            user: None,
+            team,
+        })
+        .into_response());
+    }
+
+    // Either an existing user, or a provider login that may not have signed up yet
+    let target_id = match (payload.user_id, payload.login.as_deref()) {
+        (Some(target_id), _) => target_id,
+        (None, Some(login)) => {
+            let provider = payload.provider.as_deref().unwrap_or("twitch");
+            let login = login.trim().trim_start_matches('@');
+            if login.is_empty() {
+                return Err(AppError::BadRequest("Login cannot be empty".to_string()));
+            }
+            if !LOGIN_SHARE_PROVIDERS.contains(&provider) {
+                return Err(AppError::BadRequest(
+                    "Sharing by login is only supported for Twitch accounts".to_string(),
+                ));
+            }
+            match state.page_repo.find_user_by_login(provider, login).await? {
+                Some(user) => user.id,
+                None => {
+                    let pending = state
+                        .page_repo
+                        .grant_pending_permission(page_id, provider, login, role, user_id, payload.expires_at)
+                        .await?;
+                    return Ok((StatusCode::ACCEPTED, Json(pending)).into_response());
+                }
+            }
+        }
+        (None, None) => {
+            return Err(AppError::BadRequest(
+                "One of user_id, team_id or login is required".to_string(),
+            ))
+        }
+    };
+
     // Check if permission already exists
     let existing = state
         .page_repo
-        .get_user_permission(page_id, payload.user_id)
+        .get_user_permission(page_id, target_id)
         .await?;
 
-    if existing.is_some() {
+    // An expired permission is only waiting for the purge and can be granted again
+    if existing.is_some_and(|p| !p.is_expired()) {
         return Err(AppError::BadRequest(
             "Permission already exists".to_string(),
         ));
@@ -306,7 +466,7 @@ async fn grant_permission(
 
     let permission = state
         .page_repo
-        .grant_permission(page_id, payload.user_id, payload.can_edit, user_id)
+        .grant_permission(page_id, target_id, role, user_id, payload.expires_at)
         .await?;
 
     // We need the user info too - repository should probably have a method for this but let's just use what we have or add it.
@@ -319,7 +479,64 @@ async fn grant_permission(
         .find(|p| p.permission.id == permission.id)
         .ok_or_else(|| anyhow::anyhow!("Failed to retrieve created permission"))?;
 
-    Ok(Json(permission_with_user))
+    Ok(Json(permission_with_user).into_response())
+}
+
+// Shares waiting for someone to sign in for the first time
+async fn list_pending_permissions(
+    State(state): State<PagesRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path(page_id): Path<Uuid>,
+) -> Result<Json<Vec<PendingPermission>>, AppError> {
+    require_scope(&claims, Scope::SharingAdmin)?;
+    require_page(&claims, page_id, true)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    // Sharing is for managers and the owner
+    state
+        .page_repo
+        .find_by_id(page_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    if !state.page_repo.can(page_id, user_id, PageAction::Share).await? {
+        return Err(AppError::Forbidden);
+    }
+
+    let pending = state.page_repo.list_pending_permissions(page_id).await?;
+
+    Ok(Json(pending))
+}
+
+async fn revoke_pending_permission(
+    State(state): State<PagesRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path((page_id, pending_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    require_scope(&claims, Scope::SharingAdmin)?;
+    require_page(&claims, page_id, true)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    // Sharing is for managers and the owner
+    state
+        .page_repo
+        .find_by_id(page_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    if !state.page_repo.can(page_id, user_id, PageAction::Share).await? {
+        return Err(AppError::Forbidden);
+    }
+
+    if !state.page_repo.revoke_pending_permission(page_id, pending_id).await? {
+        return Err(AppError::NotFound);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
 }
 
 // Update permission
@@ -329,23 +546,36 @@ async fn update_permission(
     Path((page_id, permission_id)): Path<(Uuid, Uuid)>,
     Json(payload): Json<UpdatePermission>,
 ) -> Result<Json<PagePermissionWithUser>, AppError> {
+    require_scope(&claims, Scope::SharingAdmin)?;
+    require_page(&claims, page_id, true)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
-    // Check if user is creator
-    let page = state
+    // Sharing is for managers and the owner
+    state
         .page_repo
         .find_by_id(page_id)
         .await?
         .ok_or(AppError::NotFound)?;
 
-    if page.creator_id != user_id {
+    if !state.page_repo.can(page_id, user_id, PageAction::Share).await? {
         return Err(AppError::Forbidden);
     }
 
+    let role = requested_role(payload.role, payload.can_edit);
+    if role == PageRole::Owner {
+        return Err(AppError::BadRequest(
+            "The owner role cannot be granted".to_string(),
+        ));
+    }
+    if let Some(expires_at) = &payload.expires_at {
+        validate_expiry(expires_at)?;
+    }
+
     let _permission = state
         .page_repo
-        .update_permission(page_id, permission_id, payload.can_edit)
+        .update_permission(page_id, permission_id, role, payload.expires_at)
         .await?
         .ok_or(AppError::NotFound)?;
 
@@ -364,17 +594,20 @@ async fn revoke_permission(
     Extension(claims): Extension<Claims>,
     Path((page_id, permission_id)): Path<(Uuid, Uuid)>,
 ) -> Result<StatusCode, AppError> {
+    require_scope(&claims, Scope::SharingAdmin)?;
+    require_page(&claims, page_id, true)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
-    // Check if user is creator
-    let page = state
+    // Sharing is for managers and the owner
+    state
         .page_repo
         .find_by_id(page_id)
         .await?
         .ok_or(AppError::NotFound)?;
 
-    if page.creator_id != user_id {
+    if !state.page_repo.can(page_id, user_id, PageAction::Share).await? {
         return Err(AppError::Forbidden);
     }
 
@@ -383,6 +616,205 @@ async fn revoke_permission(
     Ok(StatusCode::NO_CONTENT)
 }
 
+// Offer ownership to an existing collaborator; takes effect once they accept
+async fn create_transfer(
+    State(state): State<PagesRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path(page_id): Path<Uuid>,
+    Json(payload): Json<CreatePageTransfer>,
+) -> Result<Json<PageTransfer>, AppError> {
+    require_scope(&claims, Scope::SharingAdmin)?;
+    require_page(&claims, page_id, true)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    state
+        .page_repo
+        .find_by_id(page_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    if !state.page_repo.can(page_id, user_id, PageAction::Transfer).await? {
+        return Err(AppError::Forbidden);
+    }
+
+    if payload.user_id == user_id {
+        return Err(AppError::BadRequest("You already own this page".to_string()));
+    }
+
+    if state
+        .page_repo
+        .get_user_permission(page_id, payload.user_id)
+        .await?
+        .is_none_or(|p| p.is_expired())
+    {
+        return Err(AppError::BadRequest(
+            "Ownership can only be transferred to a collaborator".to_string(),
+        ));
+    }
+
+    let transfer = state
+        .page_repo
+        .create_transfer(page_id, user_id, payload.user_id)
+        .await?;
+
+    Ok(Json(transfer))
+}
+
+// Pending transfer for a page, visible to the owner and the recipient
+async fn get_transfer(
+    State(state): State<PagesRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path(page_id): Path<Uuid>,
+) -> Result<Json<PageTransfer>, AppError> {
+    require_scope(&claims, Scope::PagesRead)?;
+    require_page(&claims, page_id, false)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    let transfer = state
+        .page_repo
+        .find_transfer(page_id)
+        .await?
+        .filter(|t| t.from_user_id == user_id || t.to_user_id == user_id)
+        .ok_or(AppError::NotFound)?;
+
+    Ok(Json(transfer))
+}
+
+// The owner withdraws the offer, or the recipient declines it
+async fn cancel_transfer(
+    State(state): State<PagesRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path(page_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    require_scope(&claims, Scope::SharingAdmin)?;
+    require_page(&claims, page_id, true)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    state
+        .page_repo
+        .find_transfer(page_id)
+        .await?
+        .filter(|t| t.from_user_id == user_id || t.to_user_id == user_id)
+        .ok_or(AppError::NotFound)?;
+
+    state.page_repo.delete_transfer(page_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// The recipient takes over the page; the previous owner becomes an editor
+async fn accept_transfer(
+    State(state): State<PagesRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path(page_id): Path<Uuid>,
+) -> Result<Json<PageWithPermission>, AppError> {
+    require_scope(&claims, Scope::SharingAdmin)?;
+    require_page(&claims, page_id, true)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    let page = state
+        .page_repo
+        .accept_transfer(page_id, user_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let hidden = state.page_repo.is_hidden(page_id, user_id).await?;
+
+    Ok(Json(PageWithPermission {
+        page,
+        is_creator: true,
+        can_edit: true,
+        role: PageRole::Owner,
+        hidden,
+    }))
+}
+
+// A collaborator removes their own access; the owner has to transfer or delete instead
+async fn leave_page(
+    State(state): State<PagesRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path(page_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    require_scope(&claims, Scope::PagesWrite)?;
+    require_page(&claims, page_id, false)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    let page = state
+        .page_repo
+        .find_by_id(page_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    if page.creator_id == user_id {
+        return Err(AppError::BadRequest(
+            "The owner cannot leave their own page".to_string(),
+        ));
+    }
+
+    if !state.page_repo.leave_page(page_id, user_id).await? {
+        // Access through a team is left by leaving the team
+        return Err(AppError::NotFound);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Hide (or unhide) a page from the caller's dashboard without giving up access
+async fn update_membership(
+    State(state): State<PagesRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path(page_id): Path<Uuid>,
+    Json(payload): Json<UpdateMembership>,
+) -> Result<StatusCode, AppError> {
+    require_scope(&claims, Scope::PagesRead)?;
+    require_page(&claims, page_id, false)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    state
+        .page_repo
+        .find_by_id(page_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    if !state.page_repo.can(page_id, user_id, PageAction::View).await? {
+        return Err(AppError::Forbidden);
+    }
+
+    state
+        .page_repo
+        .set_hidden(page_id, user_id, payload.hidden)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Transfers offered to the current user
+async fn list_incoming_transfers(
+    State(state): State<PagesRouterState>,
+    Extension(claims): Extension<Claims>,
+) -> Result<Json<Vec<PageTransferWithPage>>, AppError> {
+    require_scope(&claims, Scope::PagesRead)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    let mut transfers = state.page_repo.list_incoming_transfers(user_id).await?;
+    transfers.retain(|t| claims.allows_page(t.page.id, false));
+
+    Ok(Json(transfers))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -401,15 +833,14 @@ mod tests {
     ) -> anyhow::Result<(crate::models::User, String)> {
         let user_repo = crate::repositories::UserRepository::new(pool.clone());
         let auth_service = AuthService::new(
-            "test_secret".to_string(),
-            "test_client".to_string(),
             "test_secret".to_string(),
             user_repo.clone(),
+            crate::repositories::SessionRepository::new(pool.clone()),
         );
 
         let user = user_repo
             .create(CreateUser {
-                twitch_id: twitch_id.to_string(),
+                twitch_id: Some(twitch_id.to_string()),
                 username: username.to_string(),
                 display_name: None,
                 profile_image_url: None,
@@ -417,7 +848,7 @@ mod tests {
             })
             .await?;
 
-        let jwt = auth_service.create_jwt(&user)?;
+        let jwt = auth_service.start_session(&user, None, None).await?.access_token;
         Ok((user, jwt))
     }
 
@@ -429,6 +860,8 @@ mod tests {
 
         let state = PagesRouterState {
             page_repo: page_repo.clone(),
+            team_repo: Arc::new(crate::repositories::TeamRepository::new(pool.clone())),
+            slug_cooldown: chrono::Duration::days(30),
         };
         let app = pages_router(state);
 
@@ -444,10 +877,11 @@ mod tests {
             .header("content-type", "application/json")
             .extension(Claims {
                 sub: user.id.to_string(),
-                twitch_id: user.twitch_id.clone(),
                 username: user.username.clone(),
                 exp: 9999999999,
                 scopes: None,
+                pages: None,
+                sid: None,
             })
             .body(Body::from(serde_json::to_vec(&create_payload)?))?;
 
@@ -464,10 +898,11 @@ mod tests {
             .uri("/pages")
             .extension(Claims {
                 sub: user.id.to_string(),
-                twitch_id: user.twitch_id.clone(),
                 username: user.username.clone(),
                 exp: 9999999999,
                 scopes: None,
+                pages: None,
+                sid: None,
             })
             .body(Body::empty())?;
 
@@ -505,6 +940,8 @@ mod tests {
 
         let state = PagesRouterState {
             page_repo: page_repo.clone(),
+            team_repo: Arc::new(crate::repositories::TeamRepository::new(pool.clone())),
+            slug_cooldown: chrono::Duration::days(30),
         };
         let app = pages_router(state.clone());
 
@@ -514,10 +951,11 @@ mod tests {
             .header("content-type", "application/json")
             .extension(Claims {
                 sub: user.id.to_string(),
-                twitch_id: user.twitch_id.clone(),
                 username: user.username.clone(),
                 exp: 9999999999,
                 scopes: None,
+                pages: None,
+                sid: None,
             })
             .body(Body::from(serde_json::to_vec(&update_payload)?))?;
 
@@ -536,10 +974,11 @@ mod tests {
             .uri(format!("/pages/{}", page.id))
             .extension(Claims {
                 sub: user.id.to_string(),
-                twitch_id: user.twitch_id.clone(),
                 username: user.username.clone(),
                 exp: 9999999999,
                 scopes: None,
+                pages: None,
+                sid: None,
             })
             .body(Body::empty())?;
 
@@ -549,6 +988,72 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_released_slug_cooldown() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let page_repo = Arc::new(crate::repositories::PageRepository::new(pool.clone()));
+        let (alice, _jwt) = create_test_user_with_jwt(&pool, "tw1", "alice").await?;
+        let (bob, _jwt) = create_test_user_with_jwt(&pool, "tw2", "bob").await?;
+
+        let mut pages = Vec::new();
+        for (owner, title) in [(&alice, "Alice"), (&bob, "Bob")] {
+            pages.push(
+                page_repo
+                    .create(owner.id, CreatePage {
+                        title: title.to_string(),
+                        description: None,
+                    })
+                    .await?,
+            );
+        }
+
+        let app = pages_router(PagesRouterState {
+            page_repo: page_repo.clone(),
+            team_repo: Arc::new(crate::repositories::TeamRepository::new(pool.clone())),
+            slug_cooldown: chrono::Duration::days(30),
+        });
+        let set_slug = |user: &crate::models::User, page_id: Uuid, slug: &str| {
+            let app = app.clone();
+            let claims = Claims {
+                sub: user.id.to_string(),
+                username: user.username.clone(),
+                exp: 9999999999,
+                scopes: None,
+                pages: None,
+                sid: None,
+            };
+            let body = serde_json::json!({ "public_slug": slug });
+            async move {
+                let req = Request::builder()
+                    .method(Method::PUT)
+                    .uri(format!("/pages/{}/public-slug", page_id))
+                    .header("content-type", "application/json")
+                    .extension(claims)
+                    .body(Body::from(serde_json::to_vec(&body)?))?;
+                anyhow::Ok(app.oneshot(req).await.unwrap().status())
+            }
+        };
+
+        assert_eq!(set_slug(&alice, pages[0].id, "party").await?, 200);
+        assert_eq!(set_slug(&alice, pages[0].id, "party-2026").await?, 200);
+
+        // Bob can't grab the slug Alice's old links still point at
+        assert_eq!(set_slug(&bob, pages[1].id, "party").await?, 400);
+        // Alice can take it back
+        assert_eq!(set_slug(&alice, pages[0].id, "party").await?, 200);
+        assert_eq!(set_slug(&alice, pages[0].id, "party-2026").await?, 200);
+
+        // Once the cooldown is over the slug is free again
+        sqlx::query("UPDATE public_slug_history SET released_at = $1")
+            .bind(chrono::Utc::now() - chrono::Duration::days(31))
+            .execute(&pool)
+            .await?;
+        assert_eq!(set_slug(&bob, pages[1].id, "party").await?, 200);
+        assert!(page_repo.find_by_previous_slug("party").await?.is_none());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_set_public_slug() -> anyhow::Result<()> {
         let pool = setup_db().await;
@@ -564,6 +1069,8 @@ mod tests {
 
         let state = PagesRouterState {
             page_repo: page_repo.clone(),
+            team_repo: Arc::new(crate::repositories::TeamRepository::new(pool.clone())),
+            slug_cooldown: chrono::Duration::days(30),
         };
         let app = pages_router(state);
 
@@ -578,9 +1085,12 @@ mod tests {
             .header("content-type", "application/json")
             .extension(Claims {
                 sub: user.id.to_string(),
-                twitch_id: user.twitch_id.clone(),
                 username: user.username.clone(),
-                exp: 9999999999,                scopes: None,            })
+                exp: 9999999999,
+                scopes: None,
+                pages: None,
+                sid: None,
+            })
             .body(Body::from(serde_json::to_vec(&slug_payload)?))?;
 
         let resp = app.clone().oneshot(req).await.unwrap();
@@ -589,6 +1099,31 @@ mod tests {
         let bytes = body::to_bytes(resp.into_body(), 64 * 1024).await?;
         let updated: serde_json::Value = serde_json::from_slice(&bytes)?;
         assert_eq!(updated["public_slug"].as_str(), Some("my-public-page"));
+        assert_eq!(updated["public_password_protected"].as_bool(), Some(false));
+
+        // Add a password; only whether one is set comes back
+        let req = Request::builder()
+            .method(Method::PUT)
+            .uri(format!("/pages/{}/public-slug", page.id))
+            .header("content-type", "application/json")
+            .extension(Claims {
+                sub: user.id.to_string(),
+                username: user.username.clone(),
+                exp: 9999999999,
+                scopes: None,
+                pages: None,
+                sid: None,
+            })
+            .body(Body::from(serde_json::to_vec(&serde_json::json!({
+                "public_slug": "my-public-page",
+                "password": "secret-pass"
+            }))?))?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 200);
+        let bytes = body::to_bytes(resp.into_body(), 64 * 1024).await?;
+        let updated: serde_json::Value = serde_json::from_slice(&bytes)?;
+        assert_eq!(updated["public_password_protected"].as_bool(), Some(true));
+        assert!(!String::from_utf8_lossy(&bytes).contains("pbkdf2"));
 
         // Test invalid slug format
         let invalid_slug = serde_json::json!({
@@ -601,9 +1136,12 @@ mod tests {
             .header("content-type", "application/json")
             .extension(Claims {
                 sub: user.id.to_string(),
-                twitch_id: user.twitch_id.clone(),
                 username: user.username.clone(),
-                exp: 9999999999,                scopes: None,            })
+                exp: 9999999999,
+                scopes: None,
+                pages: None,
+                sid: None,
+            })
             .body(Body::from(serde_json::to_vec(&invalid_slug)?))?;
 
         let resp = app.oneshot(req).await.unwrap();
@@ -620,6 +1158,8 @@ mod tests {
 
         let state = PagesRouterState {
             page_repo: page_repo.clone(),
+            team_repo: Arc::new(crate::repositories::TeamRepository::new(pool.clone())),
+            slug_cooldown: chrono::Duration::days(30),
         };
         let app = pages_router(state);
 
@@ -636,10 +1176,11 @@ mod tests {
             .header("content-type", "application/json")
             .extension(Claims {
                 sub: user.id.to_string(),
-                twitch_id: user.twitch_id.clone(),
                 username: user.username.clone(),
                 exp: 9999999999,
                 scopes: None,
+                pages: None,
+                sid: None,
             })
             .body(Body::from(serde_json::to_vec(&create_payload)?))?;
 
@@ -673,6 +1214,8 @@ mod tests {
 
         let state = PagesRouterState {
             page_repo: page_repo.clone(),
+            team_repo: Arc::new(crate::repositories::TeamRepository::new(pool.clone())),
+            slug_cooldown: chrono::Duration::days(30),
         };
         
         // Grant permission
@@ -688,10 +1231,11 @@ mod tests {
             .header("content-type", "application/json")
             .extension(Claims {
                 sub: owner.id.to_string(),
-                twitch_id: owner.twitch_id.clone(),
                 username: owner.username.clone(),
                 exp: 9999999999,
                 scopes: None,
+                pages: None,
+                sid: None,
             })
             .body(Body::from(serde_json::to_vec(&grant_payload)?))?;
 
@@ -703,7 +1247,8 @@ mod tests {
         
         // The response is PagePermissionWithUser, which has both permission fields and user
         let permission_id = perm["id"].as_str().unwrap().to_string();
-        assert_eq!(perm["can_edit"].as_bool(), Some(true));
+        // The legacy can_edit flag maps onto the editor role
+        assert_eq!(perm["role"].as_str(), Some("editor"));
 
         // List permissions
         let app = pages_router(state.clone());
@@ -712,10 +1257,11 @@ mod tests {
             .uri(format!("/pages/{}/permissions", page.id))
             .extension(Claims {
                 sub: owner.id.to_string(),
-                twitch_id: owner.twitch_id.clone(),
                 username: owner.username.clone(),
                 exp: 9999999999,
                 scopes: None,
+                pages: None,
+                sid: None,
             })
             .body(Body::empty())?;
 
@@ -738,10 +1284,11 @@ mod tests {
             .header("content-type", "application/json")
             .extension(Claims {
                 sub: owner.id.to_string(),
-                twitch_id: owner.twitch_id.clone(),
                 username: owner.username.clone(),
                 exp: 9999999999,
                 scopes: None,
+                pages: None,
+                sid: None,
             })
             .body(Body::from(serde_json::to_vec(&update_payload)?))?;
 
@@ -755,10 +1302,11 @@ mod tests {
             .uri(format!("/pages/{}/permissions/{}", page.id, permission_id))
             .extension(Claims {
                 sub: owner.id.to_string(),
-                twitch_id: owner.twitch_id.clone(),
                 username: owner.username.clone(),
                 exp: 9999999999,
                 scopes: None,
+                pages: None,
+                sid: None,
             })
             .body(Body::empty())?;
 
@@ -767,4 +1315,450 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_manager_can_share_but_not_delete() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let page_repo = Arc::new(crate::repositories::PageRepository::new(pool.clone()));
+        let (owner, _) = create_test_user_with_jwt(&pool, "owner", "owner").await?;
+        let (manager, _) = create_test_user_with_jwt(&pool, "manager", "manager").await?;
+        let (viewer, _) = create_test_user_with_jwt(&pool, "viewer", "viewer").await?;
+
+        let page = page_repo
+            .create(owner.id, CreatePage {
+                title: "Team Page".to_string(),
+                description: None,
+            })
+            .await?;
+        page_repo
+            .grant_permission(page.id, manager.id, PageRole::Manager, owner.id, None)
+            .await?;
+
+        let app = pages_router(PagesRouterState {
+            page_repo: page_repo.clone(),
+            team_repo: Arc::new(crate::repositories::TeamRepository::new(pool.clone())),
+            slug_cooldown: chrono::Duration::days(30),
+        });
+        let manager_claims = Claims {
+            sub: manager.id.to_string(),
+            username: manager.username.clone(),
+            exp: 9999999999,
+            scopes: None,
+            pages: None,
+            sid: None,
+        };
+
+        // The role is reported on the page
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(format!("/pages/{}", page.id))
+            .extension(manager_claims.clone())
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 200);
+        let bytes = body::to_bytes(resp.into_body(), 64 * 1024).await?;
+        let v: serde_json::Value = serde_json::from_slice(&bytes)?;
+        assert_eq!(v["role"].as_str(), Some("manager"));
+        assert_eq!(v["can_edit"].as_bool(), Some(true));
+        assert_eq!(v["is_creator"].as_bool(), Some(false));
+
+        // Managers can share
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("/pages/{}/permissions", page.id))
+            .header("content-type", "application/json")
+            .extension(manager_claims.clone())
+            .body(Body::from(serde_json::to_vec(&serde_json::json!({
+                "user_id": viewer.id.to_string(),
+                "role": "viewer"
+            }))?))?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 200);
+
+        // ...but not hand out ownership
+        let req = Request::builder()
+            .method(Method::PATCH)
+            .uri(format!(
+                "/pages/{}/permissions/{}",
+                page.id,
+                page_repo.get_user_permission(page.id, viewer.id).await?.unwrap().id
+            ))
+            .header("content-type", "application/json")
+            .extension(manager_claims.clone())
+            .body(Body::from(serde_json::to_vec(&serde_json::json!({ "role": "owner" }))?))?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 400);
+
+        // ...or delete the page
+        let req = Request::builder()
+            .method(Method::DELETE)
+            .uri(format!("/pages/{}", page.id))
+            .extension(manager_claims)
+            .body(Body::empty())?;
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 403);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_grant_by_login() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let page_repo = Arc::new(crate::repositories::PageRepository::new(pool.clone()));
+        let (owner, _) = create_test_user_with_jwt(&pool, "owner", "owner").await?;
+        let auth_service = AuthService::new(
+            "test_secret".to_string(),
+            crate::repositories::UserRepository::new(pool.clone()),
+            crate::repositories::SessionRepository::new(pool.clone()),
+        );
+        let known = auth_service
+            .get_or_create_user(crate::models::ExternalIdentity {
+                provider: "twitch".to_string(),
+                subject: "tw_known".to_string(),
+                username: "known".to_string(),
+                display_name: None,
+                profile_image_url: None,
+                email: None,
+            })
+            .await?;
+
+        let page = page_repo
+            .create(owner.id, CreatePage {
+                title: "Stream Page".to_string(),
+                description: None,
+            })
+            .await?;
+
+        let app = pages_router(PagesRouterState {
+            page_repo: page_repo.clone(),
+            team_repo: Arc::new(crate::repositories::TeamRepository::new(pool.clone())),
+            slug_cooldown: chrono::Duration::days(30),
+        });
+        let owner_claims = Claims {
+            sub: owner.id.to_string(),
+            username: owner.username.clone(),
+            exp: 9999999999,
+            scopes: None,
+            pages: None,
+            sid: None,
+        };
+        let grant = |login: &str| -> anyhow::Result<Request<Body>> {
+            Ok(Request::builder()
+                .method(Method::POST)
+                .uri(format!("/pages/{}/permissions", page.id))
+                .header("content-type", "application/json")
+                .extension(owner_claims.clone())
+                .body(Body::from(serde_json::to_vec(&serde_json::json!({
+                    "login": login,
+                    "role": "checker"
+                }))?))?)
+        };
+
+        // A login that already signed in is granted right away
+        let resp = app.clone().oneshot(grant("@Known")?).await.unwrap();
+        assert_eq!(resp.status(), 200);
+        assert_eq!(page_repo.get_role(page.id, known.id).await?, Some(PageRole::Checker));
+
+        // An unknown login is kept until that person signs in
+        let resp = app.clone().oneshot(grant("newcomer")?).await.unwrap();
+        assert_eq!(resp.status(), 202);
+
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(format!("/pages/{}/pending-permissions", page.id))
+            .extension(owner_claims.clone())
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        let bytes = body::to_bytes(resp.into_body(), 64 * 1024).await?;
+        let pending: Vec<serde_json::Value> = serde_json::from_slice(&bytes)?;
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0]["login"].as_str(), Some("newcomer"));
+        assert_eq!(pending[0]["provider"].as_str(), Some("twitch"));
+
+        // OIDC usernames aren't unique: two accounts can both be "jane"
+        let mut janes = Vec::new();
+        for (subject, email) in [("sub-a", "jane@a.example"), ("sub-b", "jane@b.example")] {
+            janes.push(
+                auth_service
+                    .get_or_create_user(crate::models::ExternalIdentity {
+                        provider: "oidc".to_string(),
+                        subject: subject.to_string(),
+                        username: "jane".to_string(),
+                        display_name: None,
+                        profile_image_url: None,
+                        email: Some(email.to_string()),
+                    })
+                    .await?,
+            );
+        }
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("/pages/{}/permissions", page.id))
+            .header("content-type", "application/json")
+            .extension(owner_claims.clone())
+            .body(Body::from(serde_json::to_vec(&serde_json::json!({
+                "login": "jane",
+                "provider": "oidc",
+                "role": "checker"
+            }))?))?;
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 400);
+        for jane in &janes {
+            assert_eq!(page_repo.get_role(page.id, jane.id).await?, None);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transfer_ownership() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let page_repo = Arc::new(crate::repositories::PageRepository::new(pool.clone()));
+        let (owner, _) = create_test_user_with_jwt(&pool, "owner", "owner").await?;
+        let (heir, _) = create_test_user_with_jwt(&pool, "heir", "heir").await?;
+        let (stranger, _) = create_test_user_with_jwt(&pool, "stranger", "stranger").await?;
+
+        let page = page_repo
+            .create(owner.id, CreatePage {
+                title: "Team Page".to_string(),
+                description: None,
+            })
+            .await?;
+        page_repo
+            .grant_permission(page.id, heir.id, PageRole::Viewer, owner.id, None)
+            .await?;
+
+        let app = pages_router(PagesRouterState {
+            page_repo: page_repo.clone(),
+            team_repo: Arc::new(crate::repositories::TeamRepository::new(pool.clone())),
+            slug_cooldown: chrono::Duration::days(30),
+        });
+        let claims_for = |user: &crate::models::User| Claims {
+            sub: user.id.to_string(),
+            username: user.username.clone(),
+            exp: 9999999999,
+            scopes: None,
+            pages: None,
+            sid: None,
+        };
+        let transfer_to = |user: &crate::models::User| -> anyhow::Result<Body> {
+            Ok(Body::from(serde_json::to_vec(&serde_json::json!({
+                "user_id": user.id.to_string()
+            }))?))
+        };
+
+        // Only collaborators can receive a page
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("/pages/{}/transfer", page.id))
+            .header("content-type", "application/json")
+            .extension(claims_for(&owner))
+            .body(transfer_to(&stranger)?)?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 400);
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("/pages/{}/transfer", page.id))
+            .header("content-type", "application/json")
+            .extension(claims_for(&owner))
+            .body(transfer_to(&heir)?)?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 200);
+
+        // Nothing changes until the recipient accepts
+        assert_eq!(page_repo.get_role(page.id, owner.id).await?, Some(PageRole::Owner));
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri("/page-transfers")
+            .extension(claims_for(&heir))
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        let bytes = body::to_bytes(resp.into_body(), 64 * 1024).await?;
+        let incoming: Vec<serde_json::Value> = serde_json::from_slice(&bytes)?;
+        assert_eq!(incoming.len(), 1);
+        assert_eq!(incoming[0]["page"]["title"].as_str(), Some("Team Page"));
+
+        // Somebody else cannot accept it
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("/pages/{}/transfer/accept", page.id))
+            .extension(claims_for(&stranger))
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 404);
+
+        // Nor can the recipient once their own access has lapsed
+        let accept = || {
+            Request::builder()
+                .method(Method::POST)
+                .uri(format!("/pages/{}/transfer/accept", page.id))
+                .extension(claims_for(&heir))
+                .body(Body::empty())
+        };
+        let lapsed = chrono::Utc::now() - chrono::Duration::minutes(1);
+        page_repo
+            .grant_permission(page.id, heir.id, PageRole::Viewer, owner.id, Some(lapsed))
+            .await?;
+        let resp = app.clone().oneshot(accept()?).await.unwrap();
+        assert_eq!(resp.status(), 404);
+        assert_eq!(page_repo.get_role(page.id, owner.id).await?, Some(PageRole::Owner));
+
+        page_repo
+            .grant_permission(page.id, heir.id, PageRole::Viewer, owner.id, None)
+            .await?;
+        let resp = app.oneshot(accept()?).await.unwrap();
+        assert_eq!(resp.status(), 200);
+
+        assert_eq!(page_repo.get_role(page.id, heir.id).await?, Some(PageRole::Owner));
+        assert_eq!(page_repo.get_role(page.id, owner.id).await?, Some(PageRole::Editor));
+        assert!(page_repo.get_user_permission(page.id, heir.id).await?.is_none());
+        assert!(page_repo.find_transfer(page.id).await?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_hide_and_leave_page() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let page_repo = Arc::new(crate::repositories::PageRepository::new(pool.clone()));
+        let (owner, _) = create_test_user_with_jwt(&pool, "owner", "owner").await?;
+        let (guest, _) = create_test_user_with_jwt(&pool, "guest", "guest").await?;
+
+        let page = page_repo
+            .create(owner.id, CreatePage {
+                title: "Raid Plans".to_string(),
+                description: None,
+            })
+            .await?;
+        page_repo
+            .grant_permission(page.id, guest.id, PageRole::Editor, owner.id, None)
+            .await?;
+
+        let app = pages_router(PagesRouterState {
+            page_repo: page_repo.clone(),
+            team_repo: Arc::new(crate::repositories::TeamRepository::new(pool.clone())),
+            slug_cooldown: chrono::Duration::days(30),
+        });
+        let claims_for = |user: &crate::models::User| Claims {
+            sub: user.id.to_string(),
+            username: user.username.clone(),
+            exp: 9999999999,
+            scopes: None,
+            pages: None,
+            sid: None,
+        };
+        let list_len = |uri: &'static str| {
+            let app = app.clone();
+            let claims = claims_for(&guest);
+            async move {
+                let req = Request::builder()
+                    .method(Method::GET)
+                    .uri(uri)
+                    .extension(claims)
+                    .body(Body::empty())?;
+                let resp = app.oneshot(req).await.unwrap();
+                let bytes = body::to_bytes(resp.into_body(), 64 * 1024).await?;
+                let pages: Vec<serde_json::Value> = serde_json::from_slice(&bytes)?;
+                anyhow::Ok(pages.len())
+            }
+        };
+
+        // Hidden pages keep their access but drop out of the default listing
+        let req = Request::builder()
+            .method(Method::PATCH)
+            .uri(format!("/pages/{}/membership", page.id))
+            .header("content-type", "application/json")
+            .extension(claims_for(&guest))
+            .body(Body::from(serde_json::to_vec(&serde_json::json!({ "hidden": true }))?))?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 204);
+        assert_eq!(list_len("/pages").await?, 0);
+        assert_eq!(list_len("/pages?include_hidden=true").await?, 1);
+        assert!(page_repo.can(page.id, guest.id, PageAction::Edit).await?);
+
+        // The owner cannot leave
+        let req = Request::builder()
+            .method(Method::DELETE)
+            .uri(format!("/pages/{}/membership", page.id))
+            .extension(claims_for(&owner))
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 400);
+
+        let req = Request::builder()
+            .method(Method::DELETE)
+            .uri(format!("/pages/{}/membership", page.id))
+            .extension(claims_for(&guest))
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 204);
+        assert_eq!(page_repo.get_role(page.id, guest.id).await?, None);
+        assert!(!page_repo.is_hidden(page.id, guest.id).await?);
+
+        // Nothing left to leave
+        let req = Request::builder()
+            .method(Method::DELETE)
+            .uri(format!("/pages/{}/membership", page.id))
+            .extension(claims_for(&guest))
+            .body(Body::empty())?;
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 404);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_api_key_scope_enforced() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let page_repo = Arc::new(crate::repositories::PageRepository::new(pool.clone()));
+        let (user, _) = create_test_user_with_jwt(&pool, "tws", "scoped").await?;
+
+        let page = page_repo
+            .create(user.id, CreatePage {
+                title: "Scoped Page".to_string(),
+                description: None,
+            })
+            .await?;
+
+        let app = pages_router(PagesRouterState {
+            page_repo: page_repo.clone(),
+            team_repo: Arc::new(crate::repositories::TeamRepository::new(pool.clone())),
+            slug_cooldown: chrono::Duration::days(30),
+        });
+        let read_only = Claims {
+            sub: user.id.to_string(),
+            username: user.username.clone(),
+            exp: 9999999999,
+            scopes: Some(vec!["pages:read".to_string()]),
+            pages: None,
+            sid: None,
+        };
+
+        // Reading is allowed
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(format!("/pages/{}", page.id))
+            .extension(read_only.clone())
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 200);
+
+        // Deleting requires pages:write
+        let req = Request::builder()
+            .method(Method::DELETE)
+            .uri(format!("/pages/{}", page.id))
+            .extension(read_only)
+            .body(Body::empty())?;
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 403);
+
+        let bytes = body::to_bytes(resp.into_body(), 64 * 1024).await?;
+        let v: serde_json::Value = serde_json::from_slice(&bytes)?;
+        assert_eq!(v["error"].as_str(), Some("insufficient_scope"));
+        assert_eq!(v["required_scope"].as_str(), Some("pages:write"));
+        assert!(page_repo.find_by_id(page.id).await?.is_some());
+
+        Ok(())
+    }
 }
@@ -1,58 +1,429 @@
 use crate::error::AppError;
-use crate::models::{ListWithItems, PublicPageData};
+use crate::middleware::{client_ip, cookie_value, TrustedProxies};
+use crate::models::{
+    CreateItemSuggestion, ItemSuggestion, List, Page, PublicList, PublicListItem,
+    PublicPageData, SetPublicItemChecked, UnlockPublicPage, UpdateListItem,
+};
 use crate::repositories::{ListRepository, PageRepository};
+use crate::services::{preview_image, public_html, public_link, PreviewCache, RateLimiter};
+use crate::validators::{validate_author_name, validate_item_content};
 use axum::{
-    extract::{Path, State},
-    response::Json,
-    routing::get,
+    extract::{ConnectInfo, OriginalUri, Path, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode, Uri},
+    response::{Html, IntoResponse, Json, Response},
+    routing::{get, patch, post},
     Router,
 };
+use cookie::{time::Duration as CookieDuration, Cookie};
+use std::net::SocketAddr;
 use std::sync::Arc;
+use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct PublicRouterState {
     pub page_repo: Arc<PageRepository>,
     pub list_repo: Arc<ListRepository>,
+    /// Mark unlock cookies `Secure`; off for local development over http.
+    pub secure_cookies: bool,
+    /// Base of the app's own `/p/:slug` links, used as canonical URL in the HTML view.
+    pub frontend_url: String,
+    pub preview_cache: Arc<PreviewCache>,
+    /// Per-IP budget for anonymous writes.
+    pub rate_limiter: Arc<RateLimiter>,
+    /// Password attempts per protected link and client IP.
+    pub unlock_limiter: Arc<RateLimiter>,
+    pub trusted_proxies: Arc<TrustedProxies>,
 }
 
 pub fn public_router(state: PublicRouterState) -> Router {
     Router::new()
         .route("/public/:slug", get(get_public_page))
+        .route("/public/:slug/unlock", post(unlock_public_page))
+        .route("/public/:slug/preview.png", get(get_preview_image))
+        .route(
+            "/public/:slug/lists/:list_id/items/:item_id",
+            patch(set_public_item_checked),
+        )
+        .route(
+            "/public/:slug/lists/:list_id/suggestions",
+            post(suggest_public_item),
+        )
+        .with_state(state)
+}
+
+/// The `/p/:slug` links people actually share, mounted outside `/api`. The web
+/// server sends link preview crawlers here; everyone else gets the app.
+pub fn shared_link_router(state: PublicRouterState) -> Router {
+    Router::new()
+        .route("/p/:slug", get(get_shared_link))
         .with_state(state)
 }
 
-// Get page by public slug (no authentication required)
+// Get page by public slug (no authentication required). Browsers and link preview
+// crawlers asking for HTML get a server-rendered page instead of JSON.
 async fn get_public_page(
     State(state): State<PublicRouterState>,
     Path(slug): Path<String>,
-) -> Result<Json<PublicPageData>, AppError> {
-    // Find page by slug
-    let page = state
-        .page_repo
-        .find_by_public_slug(&slug)
-        .await?
-        .ok_or(AppError::NotFound)?;
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let result = load_public_page(&state, &slug, &uri, &headers).await;
+    let vary = [(header::VARY, "Accept")];
+    if !wants_html(&headers) {
+        return result.map(|data| (vary, Json(data)).into_response());
+    }
 
-    // Get all lists for this page
-    let lists = state.list_repo.list_by_page_id(page.id).await?;
+    render_public_page(&state, &slug, result).map(|html| (vary, html).into_response())
+}
 
-    // Get items for each list
-    let mut lists_with_items = Vec::new();
+// A shared link as seen by a crawler: always the server-rendered page
+async fn get_shared_link(
+    State(state): State<PublicRouterState>,
+    Path(slug): Path<String>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let result = load_public_page(&state, &slug, &uri, &headers).await;
+    render_public_page(&state, &slug, result)
+}
+
+/// The HTML view of a public page, or of why it can't be shown.
+fn render_public_page(
+    state: &PublicRouterState,
+    slug: &str,
+    result: Result<PublicPageData, AppError>,
+) -> Result<Response, AppError> {
+    let base = state.frontend_url.trim_end_matches('/');
+    let url = format!("{}/p/{}", base, slug);
+    let (status, html) = match result {
+        Ok(data) => {
+            // The app serves the API under the same origin
+            let image_url = format!("{}/api/public/{}/preview.png", base, slug);
+            let html = public_html::render_public_page(&data, &url, Some(&image_url));
+            (StatusCode::OK, html)
+        }
+        Err(AppError::NotFound) => (
+            StatusCode::NOT_FOUND,
+            public_html::render_public_error(
+                "Page not found",
+                "The page you're looking for doesn't exist or has been removed.",
+                &url,
+            ),
+        ),
+        Err(AppError::Gone) => (
+            StatusCode::GONE,
+            public_html::render_public_error(
+                "This link has expired",
+                "Ask the page owner for a new link.",
+                &url,
+            ),
+        ),
+        Err(AppError::Unauthorized) => (
+            StatusCode::UNAUTHORIZED,
+            public_html::render_public_error(
+                "This page is password protected",
+                "Open it in the app to enter the password.",
+                &url,
+            ),
+        ),
+        Err(e) => return Err(e),
+    };
+    Ok((status, Html(html)).into_response())
+}
+
+// Open Graph preview image of a public page (no authentication required)
+async fn get_preview_image(
+    State(state): State<PublicRouterState>,
+    Path(slug): Path<String>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let data = load_public_page(&state, &slug, &uri, &headers).await?;
+    let updated_at = data.page.updated_at;
+
+    // updated_at only has second precision, so a change later in the same second
+    // would keep it; previews of a page touched that recently are neither cached
+    // nor tagged
+    let settled = updated_at < chrono::Utc::now() - chrono::Duration::seconds(1);
+    let etag = format!("\"{}\"", updated_at.timestamp());
+    if settled
+        && headers
+            .get(header::IF_NONE_MATCH)
+            .is_some_and(|v| v.as_bytes() == etag.as_bytes())
+    {
+        return Ok(StatusCode::NOT_MODIFIED.into_response());
+    }
+
+    let png = match state.preview_cache.get(&slug, updated_at) {
+        Some(png) => png,
+        None => {
+            let svg = preview_image::render_svg(&data);
+            let png = tokio::task::spawn_blocking(move || preview_image::render_png(&svg))
+                .await
+                .map_err(|e| AppError::Internal(e.to_string()))?
+                .map_err(|e| AppError::Internal(format!("Failed to render preview: {}", e)))?;
+            let png = Arc::new(png);
+            if settled {
+                state.preview_cache.insert(&slug, updated_at, png.clone());
+            }
+            png
+        }
+    };
+
+    let mut response = (
+        [
+            (header::CONTENT_TYPE, "image/png"),
+            (header::CACHE_CONTROL, "public, max-age=300"),
+        ],
+        png.as_ref().clone(),
+    )
+        .into_response();
+    if settled {
+        response
+            .headers_mut()
+            .insert(header::ETAG, HeaderValue::from_str(&etag).unwrap());
+    }
+    Ok(response)
+}
+
+async fn load_public_page(
+    state: &PublicRouterState,
+    slug: &str,
+    uri: &Uri,
+    headers: &HeaderMap,
+) -> Result<PublicPageData, AppError> {
+    let page = find_public_page(state, slug, uri).await?;
+
+    require_public_access(&page, slug, headers)?;
+
+    // Restricted and private lists never show on the public page
+    let mut lists = state.list_repo.list_by_page_id(page.id).await?;
+    lists.retain(|l| l.is_public());
+
+    // Get items for each list, reduced to what the list shows publicly
+    let mut public_lists = Vec::new();
     for list in lists {
         let items = state.list_repo.list_items_by_list_id(list.id).await?;
-        lists_with_items.push(ListWithItems { list, items });
+        public_lists.push(PublicList::new(list, items));
+    }
+
+    Ok(PublicPageData {
+        page: page.into(),
+        lists: public_lists,
+    })
+}
+
+/// Whether the client prefers HTML over JSON, going by the order in `Accept`
+/// (browsers and crawlers list `text/html` first; the app's fetches don't list it).
+fn wants_html(headers: &HeaderMap) -> bool {
+    let Some(accept) = headers.get(header::ACCEPT).and_then(|h| h.to_str().ok()) else {
+        return false;
+    };
+    match (accept.find("text/html"), accept.find("application/json")) {
+        (Some(html), Some(json)) => html < json,
+        (Some(_), None) => true,
+        _ => false,
+    }
+}
+
+// Trade the link password for a short-lived unlock cookie
+async fn unlock_public_page(
+    State(state): State<PublicRouterState>,
+    Path(slug): Path<String>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    Json(payload): Json<UnlockPublicPage>,
+) -> Result<Response, AppError> {
+    let page = find_public_page(&state, &slug, &uri).await?;
+
+    if page.is_public_link_expired() {
+        return Err(AppError::Gone);
+    }
+    let Some(password_hash) = page.public_password_hash.clone() else {
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    };
+
+    let key = format!("{}|{}", page.id, client_key(&state, &headers, connect_info));
+    if !state.unlock_limiter.check(&key) {
+        return Err(AppError::TooManyRequests);
+    }
+    // Password hashing is deliberately slow; keep it off the async workers
+    let password = payload.password;
+    let stored = password_hash.clone();
+    let valid = tokio::task::spawn_blocking(move || public_link::verify_password(&password, &stored))
+        .await
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+    if !valid {
+        return Err(AppError::Unauthorized);
+    }
+
+    let token = public_link::issue_unlock_token(&slug, &password_hash);
+    let c = Cookie::build(public_link::unlock_cookie_name(&slug), token)
+        .path("/")
+        .http_only(true)
+        .same_site(cookie::SameSite::Lax)
+        .max_age(CookieDuration::minutes(public_link::UNLOCK_TTL_MINUTES))
+        .secure(state.secure_cookies)
+        .finish();
+
+    let mut response = StatusCode::NO_CONTENT.into_response();
+    response.headers_mut().append(
+        header::SET_COOKIE,
+        HeaderValue::from_str(&c.to_string()).unwrap(),
+    );
+    Ok(response)
+}
+
+// Tick or untick an item on a list that allows it (no authentication required)
+async fn set_public_item_checked(
+    State(state): State<PublicRouterState>,
+    Path((slug, list_id, item_id)): Path<(String, Uuid, Uuid)>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    Json(payload): Json<SetPublicItemChecked>,
+) -> Result<Json<PublicListItem>, AppError> {
+    require_rate_limit(&state, &headers, connect_info)?;
+    let list = require_public_list(&state, &slug, &uri, list_id, &headers).await?;
+    if !(list.public_checking && list.show_checkboxes) {
+        return Err(AppError::Forbidden);
+    }
+
+    let item = state
+        .list_repo
+        .update_item(
+            item_id,
+            list_id,
+            UpdateListItem {
+                content: None,
+                checked: Some(payload.checked),
+                position: None,
+            },
+        )
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    Ok(Json(PublicListItem::new(item, true)))
+}
+
+// Propose an item for the list's editors to approve (no authentication required)
+async fn suggest_public_item(
+    State(state): State<PublicRouterState>,
+    Path((slug, list_id)): Path<(String, Uuid)>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    Json(mut payload): Json<CreateItemSuggestion>,
+) -> Result<(StatusCode, Json<ItemSuggestion>), AppError> {
+    require_rate_limit(&state, &headers, connect_info)?;
+    let list = require_public_list(&state, &slug, &uri, list_id, &headers).await?;
+    if !list.public_suggestions {
+        return Err(AppError::Forbidden);
     }
 
-    Ok(Json(PublicPageData {
-        page,
-        lists: lists_with_items,
-    }))
+    payload.content = validate_item_content(&payload.content)?;
+    payload.author_name = validate_author_name(&payload.author_name)?;
+
+    let suggestion = state.list_repo.create_suggestion(list_id, payload).await?;
+    Ok((StatusCode::ACCEPTED, Json(suggestion)))
+}
+
+/// Rate limits are counted per client IP: the connection's peer address, or the
+/// forwarded one when the peer is a trusted proxy. Requests without one share a bucket.
+fn client_key(
+    state: &PublicRouterState,
+    headers: &HeaderMap,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+) -> String {
+    client_ip(headers, connect_info.map(|ConnectInfo(addr)| addr), &state.trusted_proxies)
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn require_rate_limit(
+    state: &PublicRouterState,
+    headers: &HeaderMap,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+) -> Result<(), AppError> {
+    if !state.rate_limiter.check(&client_key(state, headers, connect_info)) {
+        return Err(AppError::TooManyRequests);
+    }
+    Ok(())
+}
+
+/// A list shown on the page behind `slug`, checked the same way as reading the page.
+async fn require_public_list(
+    state: &PublicRouterState,
+    slug: &str,
+    uri: &Uri,
+    list_id: Uuid,
+    headers: &HeaderMap,
+) -> Result<List, AppError> {
+    let page = find_public_page(state, slug, uri).await?;
+    require_public_access(&page, slug, headers)?;
+
+    state
+        .list_repo
+        .find_by_id(list_id, page.id)
+        .await?
+        .filter(|list| list.is_public())
+        .ok_or(AppError::NotFound)
+}
+
+/// The page behind `slug`. A slug the page used before answers with a permanent
+/// redirect to the same request under the current slug.
+async fn find_public_page(
+    state: &PublicRouterState,
+    slug: &str,
+    uri: &Uri,
+) -> Result<Page, AppError> {
+    if let Some(page) = state.page_repo.find_by_public_slug(slug).await? {
+        return Ok(page);
+    }
+
+    let current = state
+        .page_repo
+        .find_by_previous_slug(slug)
+        .await?
+        .and_then(|page| page.public_slug)
+        .ok_or(AppError::NotFound)?;
+    // Same path with the new slug, whether under the API or a shared `/p/` link
+    let location = ["/public/", "/p/"]
+        .into_iter()
+        .find_map(|prefix| {
+            let old_prefix = format!("{}{}", prefix, slug);
+            let at = uri.path().find(&old_prefix)?;
+            let rest = &uri.path()[at + old_prefix.len()..];
+            let query = uri.query().map(|q| format!("?{}", q)).unwrap_or_default();
+            Some(format!("{}{}{}{}{}", &uri.path()[..at], prefix, current, rest, query))
+        })
+        .unwrap_or_else(|| format!("/public/{}", current));
+
+    Err(AppError::MovedPermanently {
+        moved_to: current,
+        location,
+    })
+}
+
+/// Expired links are gone for everyone; password-protected ones need a valid unlock cookie.
+fn require_public_access(page: &Page, slug: &str, headers: &HeaderMap) -> Result<(), AppError> {
+    if page.is_public_link_expired() {
+        return Err(AppError::Gone);
+    }
+    if let Some(password_hash) = page.public_password_hash.as_deref() {
+        let unlocked = cookie_value(headers, &public_link::unlock_cookie_name(slug))
+            .is_some_and(|token| public_link::verify_unlock_token(&token, slug, password_hash));
+        if !unlocked {
+            return Err(AppError::Unauthorized);
+        }
+    }
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{CreateList, CreateListItem, CreatePage, CreateUser};
+    use crate::models::{CreateList, CreateListItem, CreatePage, CreateUser, ListVisibility};
     use crate::tests_utils::setup_db;
     use axum::body::{self, Body};
     use axum::http::{Method, Request};
@@ -68,7 +439,7 @@ mod tests {
         // Create data: user, page, list, items
         let creator = user_repo
             .create(CreateUser {
-                twitch_id: "pub1".to_string(),
+                twitch_id: Some("pub1".to_string()),
                 username: "pubuser".to_string(),
                 display_name: None,
                 profile_image_url: None,
@@ -88,7 +459,7 @@ mod tests {
 
         // set public slug
         page_repo
-            .set_public_slug(page.id, Some("public-slug".to_string()))
+            .set_public_slug(page.id, Some("public-slug".to_string()), None, None)
             .await?;
 
         let list = list_repo
@@ -134,10 +505,32 @@ mod tests {
             )
             .await?;
 
+        // A restricted list stays off the public page
+        let budget = list_repo
+            .create_list(
+                page.id,
+                CreateList {
+                    title: "Budget".to_string(),
+                    position: None,
+                    show_checkboxes: None,
+                    show_progress: None,
+                },
+            )
+            .await?;
+        list_repo
+            .set_list_access(budget.id, page.id, ListVisibility::Restricted, None, &[])
+            .await?;
+
         // Build router
         let state = PublicRouterState {
             page_repo: std::sync::Arc::new(page_repo),
             list_repo: std::sync::Arc::new(list_repo),
+            secure_cookies: false,
+            frontend_url: "http://localhost:5173".to_string(),
+            preview_cache: Arc::new(PreviewCache::new()),
+            rate_limiter: Arc::new(RateLimiter::new(30, std::time::Duration::from_secs(60))),
+            unlock_limiter: Arc::new(RateLimiter::new(10, std::time::Duration::from_secs(60))),
+            trusted_proxies: Default::default(),
         };
         let app = public_router(state);
 
@@ -173,6 +566,727 @@ mod tests {
         assert_eq!(json["lists"][1]["show_checkboxes"].as_bool(), Some(false));
         assert_eq!(json["lists"][1]["show_progress"].as_bool(), Some(false));
 
+        // Hidden settings are enforced in the payload, not just in the UI
+        assert_eq!(json["lists"][0]["items"][0]["checked"].as_bool(), Some(false));
+        assert_eq!(json["lists"][0]["progress"]["total"].as_u64(), Some(1));
+        assert!(json["lists"][1]["items"][0].get("checked").is_none());
+        assert!(json["lists"][1].get("progress").is_none());
+        assert!(json["lists"][0]["items"][0].get("created_at").is_none());
+        assert!(json["page"].get("creator_id").is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_public_list_dto_honors_display_settings() {
+        let now = chrono::Utc::now();
+        let list = |show_checkboxes: bool, show_progress: bool| List {
+            id: Uuid::new_v4(),
+            page_id: Uuid::new_v4(),
+            title: "Groceries".to_string(),
+            position: 0,
+            show_checkboxes,
+            show_progress,
+            created_at: now,
+            updated_at: now,
+            visibility: ListVisibility::Page,
+            min_role: None,
+            public_checking: true,
+            public_suggestions: false,
+        };
+        let items = |list: &List| {
+            [("Milk", true), ("Eggs", false)]
+                .into_iter()
+                .enumerate()
+                .map(|(i, (content, checked))| crate::models::ListItem {
+                    id: Uuid::new_v4(),
+                    list_id: list.id,
+                    content: content.to_string(),
+                    checked,
+                    position: i as i32,
+                    created_at: now,
+                    updated_at: now,
+                })
+                .collect::<Vec<_>>()
+        };
+        let to_json = |list: List| {
+            let items = items(&list);
+            serde_json::to_value(PublicList::new(list, items)).unwrap()
+        };
+
+        let shown = to_json(list(true, true));
+        assert_eq!(shown["items"][0]["checked"].as_bool(), Some(true));
+        assert_eq!(shown["progress"]["checked"].as_u64(), Some(1));
+        assert_eq!(shown["progress"]["total"].as_u64(), Some(2));
+        assert_eq!(shown["public_checking"].as_bool(), Some(true));
+
+        // Hidden checkboxes: no tick state, and no ticking either
+        let no_checkboxes = to_json(list(false, true));
+        assert!(no_checkboxes["items"][0].get("checked").is_none());
+        assert!(no_checkboxes["items"][1].get("checked").is_none());
+        assert_eq!(no_checkboxes["public_checking"].as_bool(), Some(false));
+        assert!(no_checkboxes.get("progress").is_some());
+
+        let no_progress = to_json(list(true, false));
+        assert!(no_progress.get("progress").is_none());
+        assert!(no_progress["items"][0].get("checked").is_some());
+
+        // Internal fields never make it into the payload
+        for field in ["page_id", "position", "created_at", "updated_at", "visibility", "min_role"] {
+            assert!(shown.get(field).is_none(), "list leaks {}", field);
+        }
+        for field in ["list_id", "position", "created_at", "updated_at"] {
+            assert!(shown["items"][0].get(field).is_none(), "item leaks {}", field);
+        }
+
+        let page = crate::models::PublicPage::from(Page {
+            id: Uuid::new_v4(),
+            creator_id: Uuid::new_v4(),
+            title: "Party".to_string(),
+            description: None,
+            public_slug: Some("party".to_string()),
+            created_at: now,
+            updated_at: now,
+            public_password_hash: Some("pbkdf2$secret".to_string()),
+            public_expires_at: None,
+        });
+        let page = serde_json::to_value(page).unwrap();
+        for field in ["id", "creator_id", "created_at", "public_password_hash", "public_password_protected"] {
+            assert!(page.get(field).is_none(), "page leaks {}", field);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_password_protected_and_expired_links() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let user_repo = crate::repositories::UserRepository::new(pool.clone());
+        let page_repo = Arc::new(crate::repositories::PageRepository::new(pool.clone()));
+        let list_repo = Arc::new(crate::repositories::ListRepository::new(pool.clone()));
+
+        let creator = user_repo
+            .create(CreateUser {
+                twitch_id: Some("pub1".to_string()),
+                username: "pubuser".to_string(),
+                display_name: None,
+                profile_image_url: None,
+                email: None,
+            })
+            .await?;
+        let page = page_repo
+            .create(
+                creator.id,
+                CreatePage {
+                    title: "Party".to_string(),
+                    description: None,
+                },
+            )
+            .await?;
+        page_repo
+            .set_public_slug(
+                page.id,
+                Some("party".to_string()),
+                Some(Some(public_link::hash_password("letmein"))),
+                None,
+            )
+            .await?;
+
+        let app = public_router(PublicRouterState {
+            page_repo: page_repo.clone(),
+            list_repo,
+            secure_cookies: false,
+            frontend_url: "http://localhost:5173".to_string(),
+            preview_cache: Arc::new(PreviewCache::new()),
+            rate_limiter: Arc::new(RateLimiter::new(30, std::time::Duration::from_secs(60))),
+            unlock_limiter: Arc::new(RateLimiter::new(10, std::time::Duration::from_secs(60))),
+            trusted_proxies: Default::default(),
+        });
+        let get_page = |cookie: Option<String>| {
+            let app = app.clone();
+            async move {
+                let mut req = Request::builder().method(Method::GET).uri("/public/party");
+                if let Some(cookie) = cookie {
+                    req = req.header("cookie", cookie);
+                }
+                anyhow::Ok(app.oneshot(req.body(Body::empty())?).await.unwrap().status())
+            }
+        };
+        let unlock = |password: &'static str| {
+            let app = app.clone();
+            async move {
+                let req = Request::builder()
+                    .method(Method::POST)
+                    .uri("/public/party/unlock")
+                    .header("content-type", "application/json")
+                    .body(Body::from(serde_json::to_vec(
+                        &serde_json::json!({ "password": password }),
+                    )?))?;
+                anyhow::Ok(app.oneshot(req).await.unwrap())
+            }
+        };
+
+        assert_eq!(get_page(None).await?, 401);
+        assert_eq!(unlock("wrong").await?.status(), 401);
+
+        let resp = unlock("letmein").await?;
+        assert_eq!(resp.status(), 204);
+        let set_cookie = resp.headers()[header::SET_COOKIE].to_str()?.to_string();
+        assert!(set_cookie.starts_with("public_unlock_party="));
+        assert!(set_cookie.contains("HttpOnly"));
+        let cookie = set_cookie.split(';').next().unwrap().to_string();
+        assert_eq!(get_page(Some(cookie.clone())).await?, 200);
+        assert_eq!(get_page(Some("public_unlock_party=1.00".to_string())).await?, 401);
+
+        // A new password locks out earlier unlocks
+        page_repo
+            .set_public_slug(
+                page.id,
+                Some("party".to_string()),
+                Some(Some(public_link::hash_password("changed"))),
+                None,
+            )
+            .await?;
+        assert_eq!(get_page(Some(cookie)).await?, 401);
+
+        // Guessing is throttled per link and client, right or wrong
+        for _ in 0..8 {
+            assert_eq!(unlock("wrong").await?.status(), 401);
+        }
+        assert_eq!(unlock("changed").await?.status(), 429);
+
+        // Expired links are gone, password or not
+        page_repo
+            .set_public_slug(
+                page.id,
+                Some("party".to_string()),
+                Some(None),
+                Some(Some(chrono::Utc::now() - chrono::Duration::minutes(1))),
+            )
+            .await?;
+        assert_eq!(get_page(None).await?, 410);
+        assert_eq!(unlock("changed").await?.status(), 410);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_anonymous_checking_and_suggestions() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let user_repo = crate::repositories::UserRepository::new(pool.clone());
+        let page_repo = Arc::new(crate::repositories::PageRepository::new(pool.clone()));
+        let list_repo = Arc::new(crate::repositories::ListRepository::new(pool.clone()));
+
+        let creator = user_repo
+            .create(CreateUser {
+                twitch_id: Some("pub1".to_string()),
+                username: "pubuser".to_string(),
+                display_name: None,
+                profile_image_url: None,
+                email: None,
+            })
+            .await?;
+        let page = page_repo
+            .create(
+                creator.id,
+                CreatePage {
+                    title: "Gifts".to_string(),
+                    description: None,
+                },
+            )
+            .await?;
+        page_repo
+            .set_public_slug(page.id, Some("gifts".to_string()), None, None)
+            .await?;
+
+        let mut lists = Vec::new();
+        for title in ["Open", "Closed"] {
+            let list = list_repo
+                .create_list(
+                    page.id,
+                    CreateList {
+                        title: title.to_string(),
+                        position: None,
+                        show_checkboxes: None,
+                        show_progress: None,
+                    },
+                )
+                .await?;
+            let item = list_repo
+                .create_item(
+                    list.id,
+                    CreateListItem {
+                        content: "Book".to_string(),
+                        position: None,
+                    },
+                )
+                .await?;
+            lists.push((list, item));
+        }
+        let (open, open_item) = &lists[0];
+        let (closed, closed_item) = &lists[1];
+        list_repo
+            .update_list(
+                open.id,
+                page.id,
+                crate::models::UpdateList {
+                    title: None,
+                    position: None,
+                    show_checkboxes: None,
+                    show_progress: None,
+                    public_checking: Some(true),
+                    public_suggestions: Some(true),
+                },
+            )
+            .await?;
+
+        let app = public_router(PublicRouterState {
+            page_repo,
+            list_repo: list_repo.clone(),
+            secure_cookies: false,
+            frontend_url: "http://localhost:5173".to_string(),
+            preview_cache: Arc::new(PreviewCache::new()),
+            rate_limiter: Arc::new(RateLimiter::new(4, std::time::Duration::from_secs(60))),
+            unlock_limiter: Arc::new(RateLimiter::new(10, std::time::Duration::from_secs(60))),
+            trusted_proxies: Default::default(),
+        });
+        // Every request claims a different X-Forwarded-For, which untrusted peers can't use
+        let spoofed = std::sync::atomic::AtomicU8::new(1);
+        let request = |peer: &str, method: Method, uri: String, body: &serde_json::Value| {
+            let hop = spoofed.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            let peer: SocketAddr = format!("{}:50000", peer).parse()?;
+            anyhow::Ok(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .header("content-type", "application/json")
+                    .header("x-forwarded-for", format!("203.0.113.{}", hop))
+                    .extension(ConnectInfo(peer))
+                    .body(Body::from(serde_json::to_vec(body)?))?,
+            )
+        };
+        let send = |method: Method, uri: String, body: serde_json::Value| {
+            let app = app.clone();
+            let req = request("192.0.2.10", method, uri, &body);
+            async move { anyhow::Ok(app.oneshot(req?).await.unwrap().status()) }
+        };
+
+        // Ticking only works on lists that opted in
+        let status = send(
+            Method::PATCH,
+            format!("/public/gifts/lists/{}/items/{}", open.id, open_item.id),
+            serde_json::json!({ "checked": true }),
+        )
+        .await?;
+        assert_eq!(status, 200);
+        assert!(list_repo.find_item_by_id(open_item.id, open.id).await?.unwrap().checked);
+        let status = send(
+            Method::PATCH,
+            format!("/public/gifts/lists/{}/items/{}", closed.id, closed_item.id),
+            serde_json::json!({ "checked": true }),
+        )
+        .await?;
+        assert_eq!(status, 403);
+
+        // Suggestions land in the moderation queue, not on the list
+        let status = send(
+            Method::POST,
+            format!("/public/gifts/lists/{}/suggestions", open.id),
+            serde_json::json!({ "content": "Scarf", "author_name": "  " }),
+        )
+        .await?;
+        assert_eq!(status, 202);
+        let queue = list_repo.list_pending_suggestions(page.id).await?;
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue[0].author_name, None);
+        assert_eq!(list_repo.list_items_by_list_id(open.id).await?.len(), 1);
+
+        // The same client is throttled once it used up its budget
+        let status = send(
+            Method::POST,
+            format!("/public/gifts/lists/{}/suggestions", closed.id),
+            serde_json::json!({ "content": "Scarf" }),
+        )
+        .await?;
+        assert_eq!(status, 403);
+        let status = send(
+            Method::POST,
+            format!("/public/gifts/lists/{}/suggestions", open.id),
+            serde_json::json!({ "content": "Socks" }),
+        )
+        .await?;
+        assert_eq!(status, 429);
+
+        // Other clients have their own budget
+        let req = request(
+            "192.0.2.20",
+            Method::POST,
+            format!("/public/gifts/lists/{}/suggestions", open.id),
+            &serde_json::json!({ "content": "Socks" }),
+        )?;
+        assert_eq!(app.oneshot(req).await.unwrap().status(), 202);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_old_slugs_redirect() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let user_repo = crate::repositories::UserRepository::new(pool.clone());
+        let page_repo = Arc::new(crate::repositories::PageRepository::new(pool.clone()));
+        let list_repo = Arc::new(crate::repositories::ListRepository::new(pool.clone()));
+
+        let creator = user_repo
+            .create(CreateUser {
+                twitch_id: Some("pub1".to_string()),
+                username: "pubuser".to_string(),
+                display_name: None,
+                profile_image_url: None,
+                email: None,
+            })
+            .await?;
+        let page = page_repo
+            .create(
+                creator.id,
+                CreatePage {
+                    title: "Stream".to_string(),
+                    description: None,
+                },
+            )
+            .await?;
+        for slug in ["stream-old", "stream-new"] {
+            page_repo
+                .set_public_slug(page.id, Some(slug.to_string()), None, None)
+                .await?;
+        }
+
+        let app = public_router(PublicRouterState {
+            page_repo: page_repo.clone(),
+            list_repo,
+            secure_cookies: false,
+            frontend_url: "http://localhost:5173".to_string(),
+            preview_cache: Arc::new(PreviewCache::new()),
+            rate_limiter: Arc::new(RateLimiter::new(30, std::time::Duration::from_secs(60))),
+            unlock_limiter: Arc::new(RateLimiter::new(10, std::time::Duration::from_secs(60))),
+            trusted_proxies: Default::default(),
+        });
+        let get = |uri: &'static str| {
+            let app = app.clone();
+            async move {
+                let req = Request::builder().method(Method::GET).uri(uri).body(Body::empty())?;
+                anyhow::Ok(app.oneshot(req).await.unwrap())
+            }
+        };
+
+        let resp = get("/public/stream-old").await?;
+        assert_eq!(resp.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(resp.headers()[header::LOCATION], "/public/stream-new");
+        let bytes = body::to_bytes(resp.into_body(), 64 * 1024).await?;
+        let json: serde_json::Value = serde_json::from_slice(&bytes)?;
+        assert_eq!(json["moved_to"].as_str(), Some("stream-new"));
+        assert_eq!(get("/public/stream-new").await?.status(), 200);
+
+        // Taking the link down ends the redirect as well
+        page_repo.set_public_slug(page.id, None, None, None).await?;
+        assert_eq!(get("/public/stream-old").await?.status(), 404);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_public_page_html() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let user_repo = crate::repositories::UserRepository::new(pool.clone());
+        let page_repo = Arc::new(crate::repositories::PageRepository::new(pool.clone()));
+        let list_repo = Arc::new(crate::repositories::ListRepository::new(pool.clone()));
+
+        let creator = user_repo
+            .create(CreateUser {
+                twitch_id: Some("pub1".to_string()),
+                username: "pubuser".to_string(),
+                display_name: None,
+                profile_image_url: None,
+                email: None,
+            })
+            .await?;
+        let page = page_repo
+            .create(
+                creator.id,
+                CreatePage {
+                    title: "Movie night".to_string(),
+                    description: Some("Vote for Friday's film".to_string()),
+                },
+            )
+            .await?;
+        page_repo
+            .set_public_slug(page.id, Some("movies".to_string()), None, None)
+            .await?;
+        let list = list_repo
+            .create_list(
+                page.id,
+                CreateList {
+                    title: "Candidates".to_string(),
+                    position: None,
+                    show_checkboxes: None,
+                    show_progress: None,
+                },
+            )
+            .await?;
+        list_repo
+            .create_item(
+                list.id,
+                CreateListItem {
+                    content: "Alien".to_string(),
+                    position: None,
+                },
+            )
+            .await?;
+
+        let app = public_router(PublicRouterState {
+            page_repo,
+            list_repo,
+            secure_cookies: false,
+            frontend_url: "https://lists.example/".to_string(),
+            preview_cache: Arc::new(PreviewCache::new()),
+            rate_limiter: Arc::new(RateLimiter::new(30, std::time::Duration::from_secs(60))),
+            unlock_limiter: Arc::new(RateLimiter::new(10, std::time::Duration::from_secs(60))),
+            trusted_proxies: Default::default(),
+        });
+        let get = |uri: &'static str, accept: &'static str| {
+            let app = app.clone();
+            async move {
+                let req = Request::builder()
+                    .method(Method::GET)
+                    .uri(uri)
+                    .header("accept", accept)
+                    .body(Body::empty())?;
+                let resp = app.oneshot(req).await.unwrap();
+                let status = resp.status();
+                let content_type = resp.headers()[header::CONTENT_TYPE].to_str()?.to_string();
+                let bytes = body::to_bytes(resp.into_body(), 64 * 1024).await?;
+                anyhow::Ok((status, content_type, String::from_utf8(bytes.to_vec())?))
+            }
+        };
+
+        // What a browser or a chat app's crawler sends
+        let (status, content_type, html) = get(
+            "/public/movies",
+            "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8",
+        )
+        .await?;
+        assert_eq!(status, 200);
+        assert!(content_type.starts_with("text/html"));
+        assert!(html.contains("<meta property=\"og:title\" content=\"Movie night\">"));
+        assert!(html.contains("content=\"Vote for Friday&#39;s film\""));
+        assert!(html.contains("<link rel=\"canonical\" href=\"https://lists.example/p/movies\">"));
+        assert!(html.contains("<h2>Candidates</h2>"));
+        assert!(html.contains("<progress value=\"0\" max=\"1\">"));
+        assert!(html.contains(
+            "<meta property=\"og:image\" content=\"https://lists.example/api/public/movies/preview.png\">"
+        ));
+
+        // The app itself keeps getting JSON
+        let (_, content_type, _) = get("/public/movies", "*/*").await?;
+        assert!(content_type.starts_with("application/json"));
+
+        let (status, content_type, _) = get("/public/nope", "text/html").await?;
+        assert_eq!(status, 404);
+        assert!(content_type.starts_with("text/html"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_shared_link_html() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let user_repo = crate::repositories::UserRepository::new(pool.clone());
+        let page_repo = Arc::new(crate::repositories::PageRepository::new(pool.clone()));
+        let list_repo = Arc::new(crate::repositories::ListRepository::new(pool.clone()));
+
+        let creator = user_repo
+            .create(CreateUser {
+                twitch_id: Some("pub1".to_string()),
+                username: "pubuser".to_string(),
+                display_name: None,
+                profile_image_url: None,
+                email: None,
+            })
+            .await?;
+        let page = page_repo
+            .create(
+                creator.id,
+                CreatePage {
+                    title: "Movie night".to_string(),
+                    description: None,
+                },
+            )
+            .await?;
+        page_repo
+            .set_public_slug(page.id, Some("films".to_string()), None, None)
+            .await?;
+        page_repo
+            .set_public_slug(page.id, Some("movies".to_string()), None, None)
+            .await?;
+
+        let app = shared_link_router(PublicRouterState {
+            page_repo,
+            list_repo,
+            secure_cookies: false,
+            frontend_url: "https://lists.example".to_string(),
+            preview_cache: Arc::new(PreviewCache::new()),
+            rate_limiter: Arc::new(RateLimiter::new(30, std::time::Duration::from_secs(60))),
+            unlock_limiter: Arc::new(RateLimiter::new(10, std::time::Duration::from_secs(60))),
+            trusted_proxies: Default::default(),
+        });
+        let get = |uri: &'static str| {
+            let app = app.clone();
+            async move {
+                // Crawlers don't always ask for HTML first
+                let req = Request::builder()
+                    .method(Method::GET)
+                    .uri(uri)
+                    .header("user-agent", "Mozilla/5.0 (compatible; Discordbot/2.0)")
+                    .header("accept", "*/*")
+                    .body(Body::empty())?;
+                anyhow::Ok(app.oneshot(req).await.unwrap())
+            }
+        };
+
+        // The URL the share dialog hands out
+        let resp = get("/p/movies").await?;
+        assert_eq!(resp.status(), 200);
+        assert!(resp.headers()[header::CONTENT_TYPE].to_str()?.starts_with("text/html"));
+        let bytes = body::to_bytes(resp.into_body(), 64 * 1024).await?;
+        let html = String::from_utf8(bytes.to_vec())?;
+        assert!(html.contains("<meta property=\"og:title\" content=\"Movie night\">"));
+        assert!(html.contains(
+            "<meta property=\"og:image\" content=\"https://lists.example/api/public/movies/preview.png\">"
+        ));
+
+        // Links posted before the slug changed follow it
+        let resp = get("/p/films").await?;
+        assert_eq!(resp.status(), 308);
+        assert_eq!(resp.headers()[header::LOCATION], "/p/movies");
+
+        let resp = get("/p/nope").await?;
+        assert_eq!(resp.status(), 404);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_preview_image() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let user_repo = crate::repositories::UserRepository::new(pool.clone());
+        let page_repo = Arc::new(crate::repositories::PageRepository::new(pool.clone()));
+        let list_repo = Arc::new(crate::repositories::ListRepository::new(pool.clone()));
+
+        let creator = user_repo
+            .create(CreateUser {
+                twitch_id: Some("pub1".to_string()),
+                username: "pubuser".to_string(),
+                display_name: None,
+                profile_image_url: None,
+                email: None,
+            })
+            .await?;
+        let page = page_repo
+            .create(
+                creator.id,
+                CreatePage {
+                    title: "Chores".to_string(),
+                    description: None,
+                },
+            )
+            .await?;
+        page_repo
+            .set_public_slug(page.id, Some("chores".to_string()), None, None)
+            .await?;
+        let list = list_repo
+            .create_list(
+                page.id,
+                CreateList {
+                    title: "Kitchen".to_string(),
+                    position: None,
+                    show_checkboxes: None,
+                    show_progress: None,
+                },
+            )
+            .await?;
+        let item = list_repo
+            .create_item(
+                list.id,
+                CreateListItem {
+                    content: "Dishes".to_string(),
+                    position: None,
+                },
+            )
+            .await?;
+
+        // Previews are only cached once a page settled, so pretend the last change
+        // was a while ago. The pages trigger would reset any manual updated_at.
+        sqlx::query("DROP TRIGGER update_pages_updated_at").execute(&pool).await?;
+        let backdate = |minutes: i64| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query("UPDATE pages SET updated_at = $1 WHERE id = $2")
+                    .bind(chrono::Utc::now() - chrono::Duration::minutes(minutes))
+                    .bind(page.id)
+                    .execute(&pool)
+                    .await?;
+                anyhow::Ok(())
+            }
+        };
+        backdate(10).await?;
+
+        let app = public_router(PublicRouterState {
+            page_repo: page_repo.clone(),
+            list_repo: list_repo.clone(),
+            secure_cookies: false,
+            frontend_url: "http://localhost:5173".to_string(),
+            preview_cache: Arc::new(PreviewCache::new()),
+            rate_limiter: Arc::new(RateLimiter::new(30, std::time::Duration::from_secs(60))),
+            unlock_limiter: Arc::new(RateLimiter::new(10, std::time::Duration::from_secs(60))),
+            trusted_proxies: Default::default(),
+        });
+        let get = |etag: Option<String>| {
+            let app = app.clone();
+            async move {
+                let mut req = Request::builder().method(Method::GET).uri("/public/chores/preview.png");
+                if let Some(etag) = etag {
+                    req = req.header("if-none-match", etag);
+                }
+                anyhow::Ok(app.oneshot(req.body(Body::empty())?).await.unwrap())
+            }
+        };
+
+        let resp = get(None).await?;
+        assert_eq!(resp.status(), 200);
+        assert_eq!(resp.headers()[header::CONTENT_TYPE], "image/png");
+        let etag = resp.headers()[header::ETAG].to_str()?.to_string();
+        let bytes = body::to_bytes(resp.into_body(), 1024 * 1024).await?;
+        assert!(bytes.starts_with(b"\x89PNG"));
+        assert_eq!(get(Some(etag.clone())).await?.status(), 304);
+
+        // Ticking an item touches the page, so the old preview is stale
+        list_repo
+            .update_item(
+                item.id,
+                list.id,
+                UpdateListItem {
+                    content: None,
+                    checked: Some(true),
+                    position: None,
+                },
+            )
+            .await?;
+        let resp = get(Some(etag.clone())).await?;
+        assert_eq!(resp.status(), 200);
+        assert!(resp.headers().get(header::ETAG).is_none());
+
+        backdate(5).await?;
+        let resp = get(Some(etag.clone())).await?;
+        assert_eq!(resp.status(), 200);
+        assert_ne!(resp.headers()[header::ETAG].to_str()?, etag);
+
         Ok(())
     }
 }
@@ -0,0 +1,227 @@
+use crate::error::AppError;
+use crate::middleware::require_scope;
+use crate::models::{Claims, Scope, SessionResponse};
+use crate::services::AuthService;
+use axum::{
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    response::Json as ResponseJson,
+    routing::{delete, get},
+    Router,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct SessionsRouterState {
+    pub auth_service: Arc<AuthService>,
+}
+
+pub fn sessions_router(state: SessionsRouterState) -> Router {
+    Router::new()
+        .route("/settings/sessions", get(list_sessions).delete(revoke_other_sessions))
+        .route("/settings/sessions/:id", delete(revoke_session))
+        .with_state(state)
+}
+
+// Sessions are account credentials, so API keys need the same scope as for managing keys
+async fn list_sessions(
+    State(state): State<SessionsRouterState>,
+    Extension(claims): Extension<Claims>,
+) -> Result<ResponseJson<Vec<SessionResponse>>, AppError> {
+    require_scope(&claims, Scope::KeysManage)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    let sessions = state
+        .auth_service
+        .list_user_sessions(user_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to list sessions: {}", e);
+            AppError::Internal("Failed to list sessions".to_string())
+        })?;
+
+    Ok(ResponseJson(
+        sessions
+            .iter()
+            .map(|s| s.to_response(claims.sid.as_deref()))
+            .collect(),
+    ))
+}
+
+async fn revoke_session(
+    State(state): State<SessionsRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    require_scope(&claims, Scope::KeysManage)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    let revoked = state
+        .auth_service
+        .revoke_user_session(&id, user_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to revoke session: {}", e);
+            AppError::Internal("Failed to revoke session".to_string())
+        })?;
+
+    if !revoked {
+        return Err(AppError::NotFound);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Sign out everywhere else: the calling session stays signed in. API keys have no
+// session of their own, and "everywhere else" would mean every browser.
+async fn revoke_other_sessions(
+    State(state): State<SessionsRouterState>,
+    Extension(claims): Extension<Claims>,
+) -> Result<StatusCode, AppError> {
+    require_scope(&claims, Scope::KeysManage)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+    let current = claims.sid.as_deref().ok_or_else(|| {
+        AppError::BadRequest("Only a signed-in session can sign out other sessions".to_string())
+    })?;
+
+    state
+        .auth_service
+        .revoke_other_sessions(user_id, current)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to revoke sessions: {}", e);
+            AppError::Internal("Failed to revoke sessions".to_string())
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CreateUser, User};
+    use crate::repositories::{SessionRepository, UserRepository};
+    use crate::tests_utils::{session_id, setup_db};
+    use axum::body::{self, Body};
+    use axum::http::{Method, Request};
+    use tower::util::ServiceExt;
+
+    fn claims_for(user: &User, sid: Option<&str>) -> Claims {
+        Claims {
+            sub: user.id.to_string(),
+            username: user.username.clone(),
+            exp: 9999999999,
+            scopes: None,
+            pages: None,
+            sid: sid.map(str::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_and_revoke_sessions() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let user_repo = UserRepository::new(pool.clone());
+        let auth_service = Arc::new(AuthService::new(
+            "test_secret".to_string(),
+            user_repo.clone(),
+            SessionRepository::new(pool.clone()),
+        ));
+
+        let user = user_repo
+            .create(CreateUser {
+                twitch_id: Some("tw1".to_string()),
+                username: "user1".to_string(),
+                display_name: None,
+                profile_image_url: None,
+                email: None,
+            })
+            .await?;
+        let other = user_repo
+            .create(CreateUser {
+                twitch_id: Some("tw2".to_string()),
+                username: "user2".to_string(),
+                display_name: None,
+                profile_image_url: None,
+                email: None,
+            })
+            .await?;
+
+        let laptop = auth_service.start_session(&user, Some("Laptop"), Some("10.0.0.1")).await?;
+        let phone = auth_service.start_session(&user, Some("Phone"), Some("10.0.0.2")).await?;
+        let tablet = auth_service.start_session(&user, Some("Tablet"), None).await?;
+        let foreign = auth_service.start_session(&other, None, None).await?;
+        let [laptop, phone, tablet, foreign] =
+            [laptop, phone, tablet, foreign].map(|tokens| session_id(&auth_service, &tokens));
+
+        let app = sessions_router(SessionsRouterState { auth_service: auth_service.clone() });
+
+        // List from the laptop
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri("/settings/sessions")
+            .extension(claims_for(&user, Some(&laptop)))
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 200);
+        let bytes = body::to_bytes(resp.into_body(), 64 * 1024).await?;
+        let sessions: Vec<serde_json::Value> = serde_json::from_slice(&bytes)?;
+        assert_eq!(sessions.len(), 3);
+        let current: Vec<_> = sessions.iter().filter(|s| s["current"] == true).collect();
+        assert_eq!(current.len(), 1);
+        assert_eq!(current[0]["user_agent"].as_str(), Some("Laptop"));
+        assert_eq!(current[0]["ip"].as_str(), Some("10.0.0.1"));
+
+        // Another user's session cannot be revoked
+        let req = Request::builder()
+            .method(Method::DELETE)
+            .uri(format!("/settings/sessions/{}", foreign))
+            .extension(claims_for(&user, Some(&laptop)))
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 404);
+        assert!(auth_service.is_session_active(&foreign).await?);
+
+        // Revoke the phone
+        let req = Request::builder()
+            .method(Method::DELETE)
+            .uri(format!("/settings/sessions/{}", phone))
+            .extension(claims_for(&user, Some(&laptop)))
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 204);
+        assert!(!auth_service.is_session_active(&phone).await?);
+
+        // Sign out everywhere else
+        let req = Request::builder()
+            .method(Method::DELETE)
+            .uri("/settings/sessions")
+            .extension(claims_for(&user, Some(&laptop)))
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 204);
+        assert!(auth_service.is_session_active(&laptop).await?);
+        assert!(!auth_service.is_session_active(&tablet).await?);
+        assert!(auth_service.is_session_active(&foreign).await?);
+
+        // An API key has no session to keep, so it can't sign out "everything else"
+        let mut key_claims = claims_for(&user, None);
+        key_claims.scopes = Some(vec!["keys:manage".to_string()]);
+        let req = Request::builder()
+            .method(Method::DELETE)
+            .uri("/settings/sessions")
+            .extension(key_claims)
+            .body(Body::empty())?;
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 400);
+        assert!(auth_service.is_session_active(&laptop).await?);
+
+        Ok(())
+    }
+}
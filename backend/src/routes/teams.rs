@@ -0,0 +1,519 @@
+use crate::error::AppError;
+use crate::models::{
+    Claims, CreateTeam, InviteTeamMember, Scope, Team, TeamInvitation, TeamInvitationWithTeam,
+    TeamRole, TeamWithMembers, TeamWithRole, UpdateTeam, UpdateTeamMember,
+};
+use crate::middleware::require_scope;
+use crate::repositories::TeamRepository;
+use crate::validators::validate_team_name;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{delete, get, patch, post},
+    Extension, Router,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct TeamsRouterState {
+    pub team_repo: Arc<TeamRepository>,
+}
+
+pub fn teams_router(state: TeamsRouterState) -> Router {
+    Router::new()
+        .route("/teams", get(list_teams).post(create_team))
+        .route(
+            "/teams/:id",
+            get(get_team).patch(update_team).delete(delete_team),
+        )
+        .route("/teams/:id/members", post(invite_member))
+        .route(
+            "/teams/:id/members/:user_id",
+            patch(update_member).delete(remove_member),
+        )
+        .route("/teams/:id/invitations/:user_id", delete(cancel_invitation))
+        .route("/teams/:id/invitations/accept", post(accept_invitation))
+        .route("/team-invitations", get(list_incoming_invitations))
+        .with_state(state)
+}
+
+// The caller's role in the team; non-members get 404 so teams can't be probed
+async fn require_team_role(
+    state: &TeamsRouterState,
+    team_id: Uuid,
+    user_id: Uuid,
+    required: TeamRole,
+) -> Result<TeamRole, AppError> {
+    let role = state
+        .team_repo
+        .get_role(team_id, user_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    if role < required {
+        return Err(AppError::Forbidden);
+    }
+    Ok(role)
+}
+
+// Teams the current user belongs to
+async fn list_teams(
+    State(state): State<TeamsRouterState>,
+    Extension(claims): Extension<Claims>,
+) -> Result<Json<Vec<TeamWithRole>>, AppError> {
+    require_scope(&claims, Scope::UsersRead)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    let teams = state.team_repo.list_for_user(user_id).await?;
+
+    Ok(Json(teams))
+}
+
+async fn create_team(
+    State(state): State<TeamsRouterState>,
+    Extension(claims): Extension<Claims>,
+    Json(payload): Json<CreateTeam>,
+) -> Result<Json<Team>, AppError> {
+    require_scope(&claims, Scope::SharingAdmin)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    let name = validate_team_name(&payload.name)?;
+    let team = state.team_repo.create(&name, user_id).await?;
+
+    Ok(Json(team))
+}
+
+async fn get_team(
+    State(state): State<TeamsRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path(team_id): Path<Uuid>,
+) -> Result<Json<TeamWithMembers>, AppError> {
+    require_scope(&claims, Scope::UsersRead)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    require_team_role(&state, team_id, user_id, TeamRole::Member).await?;
+
+    let team = state
+        .team_repo
+        .find_by_id(team_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let members = state.team_repo.list_members(team_id).await?;
+    let invitations = state.team_repo.list_invitations(team_id).await?;
+
+    Ok(Json(TeamWithMembers {
+        team,
+        members,
+        invitations,
+    }))
+}
+
+async fn update_team(
+    State(state): State<TeamsRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path(team_id): Path<Uuid>,
+    Json(payload): Json<UpdateTeam>,
+) -> Result<Json<Team>, AppError> {
+    require_scope(&claims, Scope::SharingAdmin)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    require_team_role(&state, team_id, user_id, TeamRole::Admin).await?;
+
+    let name = validate_team_name(&payload.name)?;
+    let team = state
+        .team_repo
+        .rename(team_id, &name)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    Ok(Json(team))
+}
+
+// Deleting a team also removes every page permission granted to it
+async fn delete_team(
+    State(state): State<TeamsRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path(team_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    require_scope(&claims, Scope::SharingAdmin)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    require_team_role(&state, team_id, user_id, TeamRole::Owner).await?;
+
+    state.team_repo.delete(team_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Invite a user to the team; they only join once they accept
+async fn invite_member(
+    State(state): State<TeamsRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path(team_id): Path<Uuid>,
+    Json(payload): Json<InviteTeamMember>,
+) -> Result<Json<TeamInvitation>, AppError> {
+    require_scope(&claims, Scope::SharingAdmin)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    require_team_role(&state, team_id, user_id, TeamRole::Admin).await?;
+
+    let role = payload.role.unwrap_or(TeamRole::Member);
+    if role == TeamRole::Owner {
+        return Err(AppError::BadRequest(
+            "A team has exactly one owner".to_string(),
+        ));
+    }
+
+    if state.team_repo.get_role(team_id, payload.user_id).await?.is_some() {
+        return Err(AppError::BadRequest(
+            "User is already a member".to_string(),
+        ));
+    }
+
+    let invitation = state
+        .team_repo
+        .create_invitation(team_id, payload.user_id, role, user_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    Ok(Json(invitation))
+}
+
+// An admin withdraws an invitation, or the invited user declines it
+async fn cancel_invitation(
+    State(state): State<TeamsRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path((team_id, invitee_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    require_scope(&claims, Scope::SharingAdmin)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    if invitee_id != user_id {
+        require_team_role(&state, team_id, user_id, TeamRole::Admin).await?;
+    }
+
+    if !state.team_repo.delete_invitation(team_id, invitee_id).await? {
+        return Err(AppError::NotFound);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// The invited user joins the team with the role they were offered
+async fn accept_invitation(
+    State(state): State<TeamsRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path(team_id): Path<Uuid>,
+) -> Result<Json<TeamWithRole>, AppError> {
+    require_scope(&claims, Scope::SharingAdmin)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    let member = state
+        .team_repo
+        .accept_invitation(team_id, user_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let team = state
+        .team_repo
+        .find_by_id(team_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    Ok(Json(TeamWithRole {
+        team,
+        role: member.role,
+    }))
+}
+
+// Invitations waiting for the current user
+async fn list_incoming_invitations(
+    State(state): State<TeamsRouterState>,
+    Extension(claims): Extension<Claims>,
+) -> Result<Json<Vec<TeamInvitationWithTeam>>, AppError> {
+    require_scope(&claims, Scope::UsersRead)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    let invitations = state.team_repo.list_incoming_invitations(user_id).await?;
+
+    Ok(Json(invitations))
+}
+
+async fn update_member(
+    State(state): State<TeamsRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path((team_id, member_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateTeamMember>,
+) -> Result<StatusCode, AppError> {
+    require_scope(&claims, Scope::SharingAdmin)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    require_team_role(&state, team_id, user_id, TeamRole::Admin).await?;
+
+    let current = state
+        .team_repo
+        .get_role(team_id, member_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    if current == TeamRole::Owner || payload.role == TeamRole::Owner {
+        return Err(AppError::BadRequest(
+            "A team has exactly one owner".to_string(),
+        ));
+    }
+
+    state
+        .team_repo
+        .update_member(team_id, member_id, payload.role)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Admins remove members; anyone but the owner may remove themselves
+async fn remove_member(
+    State(state): State<TeamsRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path((team_id, member_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    require_scope(&claims, Scope::SharingAdmin)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    let required = if member_id == user_id {
+        TeamRole::Member
+    } else {
+        TeamRole::Admin
+    };
+    require_team_role(&state, team_id, user_id, required).await?;
+
+    let role = state
+        .team_repo
+        .get_role(team_id, member_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    if role == TeamRole::Owner {
+        return Err(AppError::BadRequest(
+            "The team owner cannot be removed".to_string(),
+        ));
+    }
+
+    state.team_repo.remove_member(team_id, member_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CreatePage, CreateUser, PageRole, User};
+    use crate::repositories::{PageRepository, UserRepository};
+    use crate::routes::{pages_router, PagesRouterState};
+    use crate::tests_utils::setup_db;
+    use axum::body::{self, Body};
+    use axum::http::{Method, Request};
+    use tower::util::ServiceExt;
+
+    fn claims_for(user: &User) -> Claims {
+        Claims {
+            sub: user.id.to_string(),
+            username: user.username.clone(),
+            exp: 9999999999,
+            scopes: None,
+            pages: None,
+            sid: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_page_shared_with_team() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let user_repo = UserRepository::new(pool.clone());
+        let page_repo = Arc::new(PageRepository::new(pool.clone()));
+        let team_repo = Arc::new(TeamRepository::new(pool.clone()));
+
+        let mut users = Vec::new();
+        for name in ["lead", "mod", "outsider"] {
+            users.push(
+                user_repo
+                    .create(CreateUser {
+                        twitch_id: Some(format!("tw_{}", name)),
+                        username: name.to_string(),
+                        display_name: None,
+                        profile_image_url: None,
+                        email: None,
+                    })
+                    .await?,
+            );
+        }
+        let (lead, moderator, outsider) = (&users[0], &users[1], &users[2]);
+
+        let teams = teams_router(TeamsRouterState {
+            team_repo: team_repo.clone(),
+        });
+        let pages = pages_router(PagesRouterState {
+            page_repo: page_repo.clone(),
+            team_repo: team_repo.clone(),
+            slug_cooldown: chrono::Duration::days(30),
+        });
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/teams")
+            .header("content-type", "application/json")
+            .extension(claims_for(lead))
+            .body(Body::from(serde_json::to_vec(&serde_json::json!({ "name": "Mods" }))?))?;
+        let resp = teams.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 200);
+        let bytes = body::to_bytes(resp.into_body(), 64 * 1024).await?;
+        let team: serde_json::Value = serde_json::from_slice(&bytes)?;
+        let team_id = team["id"].as_str().unwrap().to_string();
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("/teams/{}/members", team_id))
+            .header("content-type", "application/json")
+            .extension(claims_for(lead))
+            .body(Body::from(serde_json::to_vec(&serde_json::json!({
+                "user_id": moderator.id.to_string()
+            }))?))?;
+        let resp = teams.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 200);
+
+        // Inviting someone doesn't make them a member yet
+        assert_eq!(team_repo.get_role(team_id.parse()?, moderator.id).await?, None);
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(format!("/teams/{}", team_id))
+            .extension(claims_for(moderator))
+            .body(Body::empty())?;
+        let resp = teams.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 404);
+
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri("/team-invitations")
+            .extension(claims_for(moderator))
+            .body(Body::empty())?;
+        let resp = teams.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 200);
+        let bytes = body::to_bytes(resp.into_body(), 64 * 1024).await?;
+        let invitations: serde_json::Value = serde_json::from_slice(&bytes)?;
+        assert_eq!(invitations[0]["team"]["name"], "Mods");
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("/teams/{}/invitations/accept", team_id))
+            .extension(claims_for(moderator))
+            .body(Body::empty())?;
+        let resp = teams.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 200);
+        assert_eq!(
+            team_repo.get_role(team_id.parse()?, moderator.id).await?,
+            Some(TeamRole::Member)
+        );
+
+        // Uninvited users can't join, and declined invitations are gone
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("/teams/{}/invitations/accept", team_id))
+            .extension(claims_for(outsider))
+            .body(Body::empty())?;
+        let resp = teams.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 404);
+        team_repo
+            .create_invitation(team_id.parse()?, outsider.id, TeamRole::Member, lead.id)
+            .await?;
+        let req = Request::builder()
+            .method(Method::DELETE)
+            .uri(format!("/teams/{}/invitations/{}", team_id, outsider.id))
+            .extension(claims_for(outsider))
+            .body(Body::empty())?;
+        let resp = teams.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 204);
+        assert!(team_repo.list_invitations(team_id.parse()?).await?.is_empty());
+
+        // Members can't manage the team, outsiders can't see it
+        let req = Request::builder()
+            .method(Method::PATCH)
+            .uri(format!("/teams/{}", team_id))
+            .header("content-type", "application/json")
+            .extension(claims_for(moderator))
+            .body(Body::from(serde_json::to_vec(&serde_json::json!({ "name": "Renamed" }))?))?;
+        let resp = teams.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 403);
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(format!("/teams/{}", team_id))
+            .extension(claims_for(outsider))
+            .body(Body::empty())?;
+        let resp = teams.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 404);
+
+        // Share a page with the whole team
+        let page = page_repo
+            .create(lead.id, CreatePage {
+                title: "Mod notes".to_string(),
+                description: None,
+            })
+            .await?;
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("/pages/{}/permissions", page.id))
+            .header("content-type", "application/json")
+            .extension(claims_for(lead))
+            .body(Body::from(serde_json::to_vec(&serde_json::json!({
+                "team_id": team_id,
+                "role": "editor"
+            }))?))?;
+        let resp = pages.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 200);
+
+        assert_eq!(page_repo.get_role(page.id, moderator.id).await?, Some(PageRole::Editor));
+        assert_eq!(page_repo.get_role(page.id, outsider.id).await?, None);
+        let listed = page_repo.list_for_user(moderator.id).await?;
+        assert_eq!(listed.len(), 1);
+        assert!(listed[0].can_edit);
+
+        // A direct grant and a team grant combine to the higher role
+        page_repo
+            .grant_permission(page.id, moderator.id, PageRole::Manager, lead.id, None)
+            .await?;
+        assert_eq!(page_repo.get_role(page.id, moderator.id).await?, Some(PageRole::Manager));
+        assert_eq!(page_repo.list_for_user(moderator.id).await?.len(), 1);
+
+        // Leaving the team drops the access it gave
+        let req = Request::builder()
+            .method(Method::DELETE)
+            .uri(format!("/teams/{}/members/{}", team_id, moderator.id))
+            .extension(claims_for(moderator))
+            .body(Body::empty())?;
+        let resp = teams.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 204);
+        let perm = page_repo.get_user_permission(page.id, moderator.id).await?.unwrap();
+        page_repo.revoke_permission(page.id, perm.id).await?;
+        assert_eq!(page_repo.get_role(page.id, moderator.id).await?, None);
+
+        Ok(())
+    }
+}
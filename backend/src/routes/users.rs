@@ -1,11 +1,13 @@
-use crate::models::{Claims, User};
+use crate::middleware::require_scope;
+use crate::models::{Claims, Scope, User, UserIdentity};
 use crate::repositories::UserRepository;
 use crate::validators::{validate_display_name, validate_username};
 use crate::error::FieldError;
 use axum::{
-    extract::{Query, State, Json},
+    extract::{Path, Query, State, Json},
+    http::StatusCode,
     response::Json as ResponseJson,
-    routing::get,
+    routing::{delete, get},
     Extension, Router,
 };
 use serde::Deserialize;
@@ -27,6 +29,8 @@ pub fn users_router(state: UsersRouterState) -> Router {
     Router::new()
         .route("/users/search", get(search_users))
         .route("/users/me", get(get_current_user).patch(update_current_user))
+        .route("/users/me/identities", get(list_identities))
+        .route("/users/me/identities/:id", delete(unlink_identity))
         .with_state(state)
 }
 
@@ -35,6 +39,8 @@ async fn search_users(
     Extension(claims): Extension<Claims>,
     Query(query): Query<SearchQuery>,
  ) -> Result<ResponseJson<Vec<User>>, AppError> {
+    require_scope(&claims, Scope::UsersRead)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
@@ -59,6 +65,8 @@ async fn get_current_user(
     State(state): State<UsersRouterState>,
     Extension(claims): Extension<Claims>,
 ) -> Result<ResponseJson<User>, AppError> {
+    require_scope(&claims, Scope::UsersRead)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
@@ -76,6 +84,8 @@ async fn update_current_user(
     Extension(claims): Extension<Claims>,
     Json(payload): Json<UpdateUserRequest>,
 ) -> Result<ResponseJson<User>, AppError> {
+    require_scope(&claims, Scope::UsersWrite)?;
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
@@ -125,6 +135,45 @@ async fn update_current_user(
     Ok(Json(updated))
 }
 
+// Login providers linked to the current user
+async fn list_identities(
+    State(state): State<UsersRouterState>,
+    Extension(claims): Extension<Claims>,
+) -> Result<ResponseJson<Vec<UserIdentity>>, AppError> {
+    require_scope(&claims, Scope::UsersRead)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    let identities = state.user_repo.list_identities(user_id).await?;
+
+    Ok(Json(identities))
+}
+
+// The last identity cannot be unlinked, otherwise the account could never sign in again
+async fn unlink_identity(
+    State(state): State<UsersRouterState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    require_scope(&claims, Scope::UsersWrite)?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    let identities = state.user_repo.list_identities(user_id).await?;
+    if !identities.iter().any(|i| i.id == id) {
+        return Err(AppError::NotFound);
+    }
+    if !state.user_repo.delete_identity(&id, user_id).await? {
+        return Err(AppError::BadRequest(
+            "Cannot unlink the only sign-in method".to_string(),
+        ));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,10 +186,11 @@ mod tests {
     fn create_claims(user: &crate::models::User) -> Claims {
         Claims {
             sub: user.id.to_string(),
-            twitch_id: user.twitch_id.clone(),
             username: user.username.clone(),
             exp: 9999999999,
             scopes: None,
+            pages: None,
+            sid: None,
         }
     }
 
@@ -152,7 +202,7 @@ mod tests {
         // Create test users
         let user1 = user_repo
             .create(CreateUser {
-                twitch_id: "tw1".to_string(),
+                twitch_id: Some("tw1".to_string()),
                 username: "alice".to_string(),
                 display_name: Some("Alice".to_string()),
                 profile_image_url: None,
@@ -162,7 +212,7 @@ mod tests {
 
         user_repo
             .create(CreateUser {
-                twitch_id: "tw2".to_string(),
+                twitch_id: Some("tw2".to_string()),
                 username: "bob".to_string(),
                 display_name: Some("Bob".to_string()),
                 profile_image_url: None,
@@ -172,7 +222,7 @@ mod tests {
 
         user_repo
             .create(CreateUser {
-                twitch_id: "tw3".to_string(),
+                twitch_id: Some("tw3".to_string()),
                 username: "charlie".to_string(),
                 display_name: Some("Charlie".to_string()),
                 profile_image_url: None,
@@ -223,7 +273,7 @@ mod tests {
 
         let user = user_repo
             .create(CreateUser {
-                twitch_id: "tw1".to_string(),
+                twitch_id: Some("tw1".to_string()),
                 username: "testuser".to_string(),
                 display_name: Some("Test User".to_string()),
                 profile_image_url: Some("http://image.url".to_string()),
@@ -259,7 +309,7 @@ mod tests {
 
         let user = user_repo
             .create(CreateUser {
-                twitch_id: "tw1".to_string(),
+                twitch_id: Some("tw1".to_string()),
                 username: "oldname".to_string(),
                 display_name: Some("Old Name".to_string()),
                 profile_image_url: None,
@@ -304,7 +354,7 @@ mod tests {
 
         let user = user_repo
             .create(CreateUser {
-                twitch_id: "tw1".to_string(),
+                twitch_id: Some("tw1".to_string()),
                 username: "validuser".to_string(),
                 display_name: Some("Old Name".to_string()),
                 profile_image_url: None,
@@ -1,6 +1,7 @@
-use crate::models::{ApiKey, CreateApiKeyResponse};
+use crate::models::{ApiKey, ApiKeyPage, CreateApiKeyResponse, RotateApiKeyResponse};
 use crate::repositories::ApiKeyRepository;
 use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
 use hex;
 use rand::{distributions::Alphanumeric, Rng};
 use sha2::{Digest, Sha256};
@@ -24,6 +25,8 @@ impl ApiKeyService {
         user_id: Uuid,
         name: Option<String>,
         scopes: Vec<String>,
+        expires_at: Option<DateTime<Utc>>,
+        pages: Vec<ApiKeyPage>,
     ) -> Result<CreateApiKeyResponse> {
         let scopes_str = scopes.join(",");
         
@@ -31,11 +34,12 @@ impl ApiKeyService {
             let token = self.generate_token();
             let token_hash = self.hash_token(&token);
 
-            match self.repo.create(user_id, name.as_deref(), &token_hash, &scopes_str).await {
+            match self.repo.create(user_id, name.as_deref(), &token_hash, &scopes_str, expires_at, &pages).await {
                 Ok(api_key) => {
                     return Ok(CreateApiKeyResponse {
                         id: api_key.id,
                         token,
+                        expires_at: api_key.expires_at,
                     });
                 }
                 Err(e) if attempt < MAX_RETRIES && self.is_token_collision(&e) => {
@@ -51,14 +55,68 @@ impl ApiKeyService {
         Err(anyhow::anyhow!("Failed to create API key after {} retries", MAX_RETRIES))
     }
 
-    pub async fn verify_token(&self, token: &str) -> Result<Option<(Uuid, Vec<String>)>> {
-        let token_hash = self.hash_token(token);
-        
-        if let Some(api_key) = self.repo.find_by_token_hash(&token_hash).await? {
-            Ok(Some((api_key.user_id, api_key.scopes_as_vec())))
+    /// Issue a new secret for an existing key. The old secret keeps working for
+    /// `grace` so consumers can be redeployed; a zero grace invalidates it at once.
+    pub async fn rotate_api_key(
+        &self,
+        id: &str,
+        user_id: Uuid,
+        grace: Duration,
+    ) -> Result<Option<RotateApiKeyResponse>> {
+        let previous_expires_at = if grace > Duration::zero() {
+            Some(Utc::now() + grace)
         } else {
-            Ok(None)
+            None
+        };
+
+        for attempt in 1..=MAX_RETRIES {
+            let token = self.generate_token();
+            let token_hash = self.hash_token(&token);
+
+            match self.repo.rotate(id, user_id, &token_hash, previous_expires_at).await {
+                Ok(Some(api_key)) => {
+                    return Ok(Some(RotateApiKeyResponse {
+                        id: api_key.id,
+                        token,
+                        previous_token_expires_at: api_key.previous_token_expires_at,
+                    }));
+                }
+                Ok(None) => return Ok(None),
+                Err(e) if attempt < MAX_RETRIES && self.is_token_collision(&e) => {
+                    continue;
+                }
+                Err(e) => {
+                    return Err(e).context("Failed to rotate API key");
+                }
+            }
         }
+
+        Err(anyhow::anyhow!("Failed to rotate API key after {} retries", MAX_RETRIES))
+    }
+
+    /// Resolve a raw token to its key record. Revoked and expired keys yield
+    /// `None`; successful lookups stamp `last_used_at` / `last_used_ip`.
+    pub async fn verify_token(
+        &self,
+        token: &str,
+        client_ip: Option<&str>,
+    ) -> Result<Option<ApiKey>> {
+        let token_hash = self.hash_token(token);
+
+        let api_key = match self.repo.find_by_token_hash(&token_hash).await? {
+            Some(api_key) if !api_key.is_expired() => api_key,
+            _ => return Ok(None),
+        };
+
+        if let Err(e) = self.repo.record_usage(&api_key.id, client_ip).await {
+            tracing::warn!("Failed to record API key usage: {:?}", e);
+        }
+
+        Ok(Some(api_key))
+    }
+
+    pub async fn list_key_pages(&self, api_key_id: &str) -> Result<Vec<ApiKeyPage>> {
+        self.repo.list_pages(api_key_id).await
     }
 
     pub async fn list_user_api_keys(&self, user_id: Uuid) -> Result<Vec<ApiKey>> {
@@ -73,7 +131,6 @@ impl ApiKeyService {
         self.repo.delete(id, user_id).await
     }
 
-    #[allow(dead_code)]
     pub async fn get_api_key(&self, id: &str, user_id: Uuid) -> Result<Option<ApiKey>> {
         self.repo.find_by_id(id, user_id).await
     }
@@ -105,6 +162,54 @@ mod tests {
     use crate::repositories::UserRepository;
     use crate::tests_utils::setup_db;
 
+    #[tokio::test]
+    async fn test_legacy_scopes_migration() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+
+        let user_repo = UserRepository::new(pool.clone());
+        let user = user_repo.create(CreateUser {
+            twitch_id: Some("test123".to_string()),
+            username: "testuser".to_string(),
+            display_name: None,
+            profile_image_url: None,
+            email: None,
+        }).await?;
+
+        let service = ApiKeyService::new(ApiKeyRepository::new(pool.clone()));
+        let mut keys = Vec::new();
+        for scopes in [vec!["read"], vec!["read", " write"], vec!["lists:read"]] {
+            let scopes = scopes.into_iter().map(String::from).collect();
+            keys.push(service.create_api_key(user.id, None, scopes, None, Vec::new()).await?);
+        }
+
+        // Re-run the upgrade on keys stored the old way
+        sqlx::raw_sql(include_str!("../../migrations/023_legacy_api_key_scopes.sql"))
+            .execute(&pool)
+            .await?;
+
+        let scopes_of = |token: String| {
+            let service = &service;
+            async move {
+                let key = service.verify_token(&token, None).await?.unwrap();
+                anyhow::Ok(key.scopes_as_vec())
+            }
+        };
+        assert_eq!(
+            scopes_of(keys[0].token.clone()).await?,
+            ["pages:read", "lists:read", "users:read"]
+        );
+        assert_eq!(
+            scopes_of(keys[1].token.clone()).await?,
+            [
+                "pages:read", "lists:read", "users:read",
+                "pages:write", "lists:write", "users:write", "sharing:admin",
+            ]
+        );
+        assert_eq!(scopes_of(keys[2].token.clone()).await?, ["lists:read"]);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_create_and_verify_api_key() -> anyhow::Result<()> {
         let pool = setup_db().await;
@@ -112,7 +217,7 @@ mod tests {
         // Create test user
         let user_repo = UserRepository::new(pool.clone());
         let user = user_repo.create(CreateUser {
-            twitch_id: "test123".to_string(),
+            twitch_id: Some("test123".to_string()),
             username: "testuser".to_string(),
             display_name: None,
             profile_image_url: None,
@@ -126,19 +231,21 @@ mod tests {
         let response = service.create_api_key(
             user.id,
             Some("test key".to_string()),
-            vec!["read".to_string(), "write".to_string()],
+            vec!["pages:read".to_string(), "pages:write".to_string()],
+            None,
+            Vec::new(),
         ).await?;
 
         assert!(!response.token.is_empty());
         assert!(!response.id.is_empty());
 
         // Verify token
-        let verification = service.verify_token(&response.token).await?;
+        let verification = service.verify_token(&response.token, None).await?;
         assert!(verification.is_some());
         
-        let (verified_user_id, scopes) = verification.unwrap();
-        assert_eq!(verified_user_id, user.id);
-        assert_eq!(scopes, vec!["read".to_string(), "write".to_string()]);
+        let verified = verification.unwrap();
+        assert_eq!(verified.user_id, user.id);
+        assert_eq!(verified.scopes_as_vec(), vec!["pages:read".to_string(), "pages:write".to_string()]);
 
         // List user API keys
         let keys = service.list_user_api_keys(user.id).await?;
@@ -150,9 +257,56 @@ mod tests {
         assert!(revoked);
 
         // Verify revoked token doesn't work
-        let verification = service.verify_token(&response.token).await?;
+        let verification = service.verify_token(&response.token, None).await?;
         assert!(verification.is_none());
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_expired_key_rejected_and_usage_recorded() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+
+        let user_repo = UserRepository::new(pool.clone());
+        let user = user_repo.create(CreateUser {
+            twitch_id: Some("exp123".to_string()),
+            username: "expuser".to_string(),
+            display_name: None,
+            profile_image_url: None,
+            email: None,
+        }).await?;
+
+        let service = ApiKeyService::new(ApiKeyRepository::new(pool.clone()));
+
+        // A live key records when and from where it was last used
+        let live = service.create_api_key(
+            user.id,
+            Some("live".to_string()),
+            vec!["pages:read".to_string()],
+            Some(Utc::now() + chrono::Duration::days(1)),
+            Vec::new(),
+        ).await?;
+        assert!(service.verify_token(&live.token, Some("203.0.113.7")).await?.is_some());
+
+        let key = service.get_api_key(&live.id, user.id).await?.expect("key exists");
+        assert!(key.last_used_at.is_some());
+        assert_eq!(key.last_used_ip.as_deref(), Some("203.0.113.7"));
+        assert!(!key.to_response().expired);
+
+        // An expired key no longer authenticates
+        let expired = service.create_api_key(
+            user.id,
+            Some("expired".to_string()),
+            vec!["pages:read".to_string()],
+            Some(Utc::now() - chrono::Duration::seconds(1)),
+            Vec::new(),
+        ).await?;
+        assert!(service.verify_token(&expired.token, None).await?.is_none());
+
+        let key = service.get_api_key(&expired.id, user.id).await?.expect("key exists");
+        assert!(key.last_used_at.is_none());
+        assert!(key.to_response().expired);
+
+        Ok(())
+    }
 }
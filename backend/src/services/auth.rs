@@ -1,155 +1,372 @@
 use crate::models::{
-    Claims, CreateUser, TwitchTokenResponse, TwitchUser, TwitchUserResponse, User,
+    Claims, CreateUser, ExternalIdentity, OAuthLoginRequest, OAuthState, Session, SessionTokens,
+    User,
 };
-use crate::repositories::UserRepository;
+use crate::repositories::{SessionRepository, UserRepository};
+use crate::services::JwtKeyring;
 use anyhow::{anyhow, Result};
-use chrono::Utc;
+use chrono::{Duration, Utc};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
-use reqwest::Client;
-
+use oauth2::{CsrfToken, PkceCodeChallenge};
+use rand::{distributions::Alphanumeric, Rng};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// How long a user has to complete the provider's consent screen.
+const OAUTH_STATE_TTL_MINUTES: i64 = 10;
+/// Access tokens are short-lived; the browser renews them via `/auth/refresh`.
+const ACCESS_TOKEN_TTL_MINUTES: i64 = 15;
+const REFRESH_TOKEN_TTL_DAYS: i64 = 30;
+const REFRESH_TOKEN_LENGTH: usize = 64;
+/// `kid` of the signing key when no keyring is configured; matches `JWT_KEY_ID`'s default.
+pub const DEFAULT_JWT_KEY_ID: &str = "default";
 
 pub struct AuthService {
+    /// Signs the short-lived OAuth state cookie; access tokens use `keyring`.
     jwt_secret: String,
-    client_id: String,
-    client_secret: String,
+    keyring: JwtKeyring,
     user_repository: UserRepository,
+    session_repository: SessionRepository,
 }
 
 impl AuthService {
     pub fn new(
         jwt_secret: String,
-        client_id: String,
-        client_secret: String,
         user_repository: UserRepository,
+        session_repository: SessionRepository,
     ) -> Self {
         Self {
+            keyring: JwtKeyring::from_secret(DEFAULT_JWT_KEY_ID, &jwt_secret),
             jwt_secret,
-            client_id,
-            client_secret,
             user_repository,
+            session_repository,
         }
     }
 
-    pub fn create_jwt(&self, user: &User) -> Result<String> {
-        let expiration = Utc::now()
-            .checked_add_signed(chrono::Duration::days(7))
-            .ok_or_else(|| anyhow!("Failed to calculate expiration"))?
-            .timestamp() as usize;
+    /// Sign and verify access tokens with `keyring` instead of the plain secret.
+    pub fn with_keyring(mut self, keyring: JwtKeyring) -> Self {
+        self.keyring = keyring;
+        self
+    }
+
+    pub fn keyring(&self) -> &JwtKeyring {
+        &self.keyring
+    }
+
+    /// Issue an access token for `session_id`. Returns the token and its expiry.
+    pub fn create_jwt(&self, user: &User, session_id: &str) -> Result<(String, chrono::DateTime<Utc>)> {
+        let expires_at = Utc::now()
+            .checked_add_signed(Duration::minutes(ACCESS_TOKEN_TTL_MINUTES))
+            .ok_or_else(|| anyhow!("Failed to calculate expiration"))?;
 
         let claims = Claims {
             sub: user.id.to_string(),
-            twitch_id: user.twitch_id.clone(),
             username: user.username.clone(),
-            exp: expiration,
+            exp: expires_at.timestamp() as usize,
             scopes: None,
+            pages: None,
+            sid: Some(session_id.to_string()),
         };
 
-        encode(
-            &Header::default(),
-            &claims,
-            &EncodingKey::from_secret(self.jwt_secret.as_bytes()),
-        )
-        .map_err(|e| anyhow!("Failed to create JWT: {}", e))
-    }
+        let token = self
+            .keyring
+            .sign(&claims)
+            .map_err(|e| anyhow!("Failed to create JWT: {}", e))?;
 
-    pub fn verify_jwt(&self, token: &str) -> Result<Claims> {
-        decode::<Claims>(
-            token,
-            &DecodingKey::from_secret(self.jwt_secret.as_bytes()),
-            &Validation::default(),
-        )
-        .map(|data| data.claims)
-        .map_err(|e| anyhow!("Failed to verify JWT: {}", e))
+        Ok((token, expires_at))
     }
 
-    pub async fn exchange_code_for_token(
+    /// Start a new login session for `user` and issue its first token pair.
+    pub async fn start_session(
         &self,
-        code: &str,
-        redirect_uri: &str,
-    ) -> Result<TwitchTokenResponse> {
-        let client = Client::new();
-        let params = [
-            ("client_id", self.client_id.as_str()),
-            ("client_secret", self.client_secret.as_str()),
-            ("code", code),
-            ("grant_type", "authorization_code"),
-            ("redirect_uri", redirect_uri),
-        ];
-
-        let response = client
-            .post("https://id.twitch.tv/oauth2/token")
-            .form(&params)
-            .send()
+        user: &User,
+        user_agent: Option<&str>,
+        ip: Option<&str>,
+    ) -> Result<SessionTokens> {
+        let refresh_token = generate_refresh_token();
+        let refresh_expires_at = Utc::now() + Duration::days(REFRESH_TOKEN_TTL_DAYS);
+
+        let session = self
+            .session_repository
+            .create(
+                user.id,
+                &hash_refresh_token(&refresh_token),
+                refresh_expires_at,
+                user_agent,
+                ip,
+            )
             .await?;
 
-        if !response.status().is_success() {
-            let error_body = response.text().await?;
-            return Err(anyhow!("Twitch token exchange failed: {}", error_body));
-        }
+        let (access_token, access_expires_at) = self.create_jwt(user, &session.id)?;
 
-        let token_response = response.json::<TwitchTokenResponse>().await?;
-        Ok(token_response)
+        Ok(SessionTokens {
+            access_token,
+            access_expires_at,
+            refresh_token,
+            refresh_expires_at: session.expires_at,
+        })
     }
 
-    pub async fn get_twitch_user(&self, access_token: &str) -> Result<TwitchUser> {
-        let client = Client::new();
-        let response = client
-            .get("https://api.twitch.tv/helix/users")
-            .header("Authorization", format!("Bearer {}", access_token))
-            .header("Client-Id", &self.client_id)
-            .send()
-            .await?;
+    /// Exchange a refresh token for a new token pair, rotating the refresh
+    /// token. Replaying an already-rotated token revokes the whole session,
+    /// since it means the token was copied. Returns `None` when the token is
+    /// unknown, revoked or expired.
+    pub async fn refresh_session(
+        &self,
+        refresh_token: &str,
+        user_agent: Option<&str>,
+        ip: Option<&str>,
+    ) -> Result<Option<SessionTokens>> {
+        let hash = hash_refresh_token(refresh_token);
+
+        let session = match self.session_repository.find_by_refresh_token_hash(&hash).await? {
+            Some(session) => session,
+            None => return Ok(None),
+        };
+
+        if session.previous_refresh_token_hash.as_deref() == Some(hash.as_str()) {
+            tracing::warn!("Refresh token reuse detected, revoking session {}", session.id);
+            self.session_repository.revoke(&session.id).await?;
+            return Ok(None);
+        }
+
+        if !session.is_active() {
+            return Ok(None);
+        }
+
+        let new_refresh_token = generate_refresh_token();
+        let session = match self
+            .session_repository
+            .rotate(
+                &session.id,
+                &hash,
+                &hash_refresh_token(&new_refresh_token),
+                user_agent,
+                ip,
+            )
+            .await?
+        {
+            Some(session) => session,
+            None => return Ok(None),
+        };
+
+        let user = match self.user_repository.find_by_id(session.user_id).await? {
+            Some(user) => user,
+            None => return Ok(None),
+        };
+
+        let (access_token, access_expires_at) = self.create_jwt(&user, &session.id)?;
 
-        if !response.status().is_success() {
-            let error_body = response.text().await?;
-            return Err(anyhow!("Failed to get Twitch user info: {}", error_body));
+        Ok(Some(SessionTokens {
+            access_token,
+            access_expires_at,
+            refresh_token: new_refresh_token,
+            refresh_expires_at: session.expires_at,
+        }))
+    }
+
+    /// Revoke the session a refresh token belongs to (logout).
+    pub async fn revoke_session_by_refresh_token(&self, refresh_token: &str) -> Result<bool> {
+        let hash = hash_refresh_token(refresh_token);
+        match self.session_repository.find_by_refresh_token_hash(&hash).await? {
+            Some(session) => self.session_repository.revoke(&session.id).await,
+            None => Ok(false),
         }
+    }
+
+    pub async fn revoke_session(&self, session_id: &str) -> Result<bool> {
+        self.session_repository.revoke(session_id).await
+    }
+
+    pub async fn list_user_sessions(&self, user_id: Uuid) -> Result<Vec<Session>> {
+        self.session_repository.list_active_by_user(user_id).await
+    }
+
+    pub async fn revoke_user_session(&self, session_id: &str, user_id: Uuid) -> Result<bool> {
+        self.session_repository.revoke_for_user(session_id, user_id).await
+    }
+
+    /// "Sign out everywhere else": revoke all of the user's sessions but `keep`.
+    pub async fn revoke_other_sessions(&self, user_id: Uuid, keep: &str) -> Result<u64> {
+        self.session_repository.revoke_all_for_user_except(user_id, keep).await
+    }
+
+    pub async fn is_session_active(&self, session_id: &str) -> Result<bool> {
+        Ok(self
+            .session_repository
+            .find_by_id(session_id)
+            .await?
+            .map(|s| s.is_active())
+            .unwrap_or(false))
+    }
+
+    /// Whether `session_id` is active and belongs to `user_id` (a token's `sub`).
+    pub async fn is_session_active_for(&self, session_id: &str, user_id: &str) -> Result<bool> {
+        Ok(self
+            .session_repository
+            .find_by_id(session_id)
+            .await?
+            .map(|s| s.is_active() && s.user_id.to_string() == user_id)
+            .unwrap_or(false))
+    }
+
+    pub fn verify_jwt(&self, token: &str) -> Result<Claims> {
+        self.keyring
+            .verify::<Claims>(token)
+            .map_err(|e| anyhow!("Failed to verify JWT: {}", e))
+    }
 
-        let user_response = response.json::<TwitchUserResponse>().await?;
+    /// Start an OAuth login: a random `state` nonce and PKCE pair, signed into a
+    /// cookie value so the callback can verify both without server-side storage.
+    /// `link_user_id` makes the callback link the identity to that user instead
+    /// of signing in.
+    pub fn begin_oauth_login(
+        &self,
+        provider: &str,
+        return_to: Option<String>,
+        link_user_id: Option<String>,
+    ) -> Result<OAuthLoginRequest> {
+        let nonce = CsrfToken::new_random();
+        let (challenge, verifier) = PkceCodeChallenge::new_random_sha256();
+
+        let expiration = Utc::now()
+            .checked_add_signed(chrono::Duration::minutes(OAUTH_STATE_TTL_MINUTES))
+            .ok_or_else(|| anyhow!("Failed to calculate expiration"))?
+            .timestamp() as usize;
+
+        let state = OAuthState {
+            nonce: nonce.secret().clone(),
+            pkce_verifier: verifier.secret().clone(),
+            provider: provider.to_string(),
+            return_to,
+            link_user_id,
+            exp: expiration,
+        };
+
+        let cookie = encode(
+            &Header::default(),
+            &state,
+            &EncodingKey::from_secret(self.jwt_secret.as_bytes()),
+        )
+        .map_err(|e| anyhow!("Failed to sign OAuth state: {}", e))?;
 
-        user_response
-            .data
-            .into_iter()
-            .next()
-            .ok_or_else(|| anyhow!("No user data received from Twitch"))
+        Ok(OAuthLoginRequest {
+            state: state.nonce,
+            code_challenge: challenge.as_str().to_string(),
+            cookie,
+        })
     }
 
-    pub async fn get_or_create_user(&self, twitch_user: TwitchUser) -> Result<User> {
-        // Try to find existing user
-        if let Some(_user) = self.user_repository.find_by_twitch_id(&twitch_user.id).await? {
-            // Update user info
+    /// Check the `state` query parameter against the signed cookie set by
+    /// `begin_oauth_login`. Fails on a bad signature, expiry or mismatch.
+    pub fn verify_oauth_state(&self, cookie: &str, state: &str) -> Result<OAuthState> {
+        let data = decode::<OAuthState>(
+            cookie,
+            &DecodingKey::from_secret(self.jwt_secret.as_bytes()),
+            &Validation::default(),
+        )
+        .map_err(|e| anyhow!("Failed to verify OAuth state: {}", e))?;
+
+        if data.claims.nonce != state {
+            return Err(anyhow!("OAuth state mismatch"));
+        }
+
+        Ok(data.claims)
+    }
+
+    /// Map a provider identity onto a local user, creating the user on first
+    /// sign-in and refreshing their profile on later ones.
+    pub async fn get_or_create_user(&self, identity: ExternalIdentity) -> Result<User> {
+        if let Some(user) = self
+            .user_repository
+            .find_by_identity(&identity.provider, &identity.subject)
+            .await?
+        {
+            self.user_repository.update_identity(&identity).await?;
+            self.user_repository
+                .claim_pending_permissions(user.id, &identity)
+                .await?;
             return self
                 .user_repository
-                .update_twitch_info(
-                    &twitch_user.id,
-                    &twitch_user.login,
-                    &twitch_user.display_name,
-                    &twitch_user.profile_image_url,
-                    twitch_user.email,
+                .update_by_id(
+                    user.id,
+                    &identity.username,
+                    identity.display_name.as_deref().or(user.display_name.as_deref()),
+                    identity
+                        .profile_image_url
+                        .as_deref()
+                        .or(user.profile_image_url.as_deref()),
+                    identity.email.clone().or(user.email),
                 )
                 .await;
         }
 
-        // Create new user
         let new_user = CreateUser {
-            twitch_id: twitch_user.id,
-            username: twitch_user.login,
-            display_name: Some(twitch_user.display_name),
-            profile_image_url: Some(twitch_user.profile_image_url),
-            email: twitch_user.email,
+            twitch_id: (identity.provider == "twitch").then(|| identity.subject.clone()),
+            username: identity.username.clone(),
+            display_name: identity.display_name.clone(),
+            profile_image_url: identity.profile_image_url.clone(),
+            email: identity.email.clone(),
         };
 
-        self.user_repository.create(new_user).await
+        let user = self
+            .user_repository
+            .create_with_identity(new_user, &identity)
+            .await?;
+        // Pages shared with this login before the account existed
+        self.user_repository
+            .claim_pending_permissions(user.id, &identity)
+            .await?;
+        Ok(user)
+    }
+
+    /// Link another provider account to an existing user. Fails if the
+    /// identity already belongs to a different user.
+    pub async fn link_identity(&self, user_id: Uuid, identity: ExternalIdentity) -> Result<User> {
+        match self
+            .user_repository
+            .find_by_identity(&identity.provider, &identity.subject)
+            .await?
+        {
+            Some(owner) if owner.id != user_id => {
+                return Err(anyhow!("Identity is already linked to another account"))
+            }
+            Some(_) => self.user_repository.update_identity(&identity).await?,
+            None => {
+                self.user_repository.add_identity(user_id, &identity).await?;
+            }
+        }
+        self.user_repository
+            .claim_pending_permissions(user_id, &identity)
+            .await?;
+
+        self.user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| anyhow!("User not found"))
     }
 
+}
+
+fn generate_refresh_token() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(REFRESH_TOKEN_LENGTH)
+        .map(char::from)
+        .collect()
+}
 
+fn hash_refresh_token(token: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(token.as_bytes());
+    hex::encode(hasher.finalize())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tests_utils::setup_db;
-    use crate::models::CreateUser;
+    use crate::tests_utils::{session_id, setup_db};
+    use crate::models::{CreateUser, PageRole, TwitchUser};
 
     #[tokio::test]
     async fn test_jwt_creation_and_verification() -> anyhow::Result<()> {
@@ -157,15 +374,14 @@ mod tests {
         let user_repo = UserRepository::new(pool.clone());
         let auth_service = AuthService::new(
             "test_secret_key".to_string(),
-            "test_client_id".to_string(),
-            "test_client_secret".to_string(),
             user_repo.clone(),
+            SessionRepository::new(pool.clone()),
         );
 
         // Create a test user
         let user = user_repo
             .create(CreateUser {
-                twitch_id: "twitch123".to_string(),
+                twitch_id: Some("twitch123".to_string()),
                 username: "testuser".to_string(),
                 display_name: Some("Test User".to_string()),
                 profile_image_url: None,
@@ -174,13 +390,13 @@ mod tests {
             .await?;
 
         // Create JWT
-        let jwt = auth_service.create_jwt(&user)?;
+        let (jwt, _) = auth_service.create_jwt(&user, "session1")?;
         assert!(!jwt.is_empty());
 
         // Verify JWT
         let claims = auth_service.verify_jwt(&jwt)?;
         assert_eq!(claims.sub, user.id.to_string());
-        assert_eq!(claims.twitch_id, user.twitch_id);
+        assert_eq!(claims.sid.as_deref(), Some("session1"));
         assert_eq!(claims.username, user.username);
 
         Ok(())
@@ -192,20 +408,18 @@ mod tests {
         let user_repo = UserRepository::new(pool.clone());
         let auth_service1 = AuthService::new(
             "secret1".to_string(),
-            "client_id".to_string(),
-            "client_secret".to_string(),
             user_repo.clone(),
+            SessionRepository::new(pool.clone()),
         );
         let auth_service2 = AuthService::new(
             "secret2".to_string(),
-            "client_id".to_string(),
-            "client_secret".to_string(),
             user_repo.clone(),
+            SessionRepository::new(pool.clone()),
         );
 
         let user = user_repo
             .create(CreateUser {
-                twitch_id: "tw456".to_string(),
+                twitch_id: Some("tw456".to_string()),
                 username: "user".to_string(),
                 display_name: None,
                 profile_image_url: None,
@@ -214,7 +428,7 @@ mod tests {
             .await?;
 
         // Create JWT with service1
-        let jwt = auth_service1.create_jwt(&user)?;
+        let (jwt, _) = auth_service1.create_jwt(&user, "session1")?;
 
         // Try to verify with service2 (different secret)
         let result = auth_service2.verify_jwt(&jwt);
@@ -228,10 +442,9 @@ mod tests {
         let pool = setup_db().await;
         let user_repo = UserRepository::new(pool.clone());
         let auth_service = AuthService::new(
-            "secret".to_string(),
-            "client".to_string(),
             "secret".to_string(),
             user_repo.clone(),
+            SessionRepository::new(pool.clone()),
         );
 
         let twitch_user = TwitchUser {
@@ -242,11 +455,16 @@ mod tests {
             email: Some("new@example.com".to_string()),
         };
 
-        // User should be created
-        let user = auth_service.get_or_create_user(twitch_user.clone()).await?;
-        assert_eq!(user.twitch_id, "tw789");
+        // User should be created, with the identity recorded
+        let user = auth_service.get_or_create_user(twitch_user.clone().into()).await?;
+        assert_eq!(user.twitch_id.as_deref(), Some("tw789"));
         assert_eq!(user.username, "newuser");
 
+        let identities = user_repo.list_identities(user.id).await?;
+        assert_eq!(identities.len(), 1);
+        assert_eq!(identities[0].provider, "twitch");
+        assert_eq!(identities[0].subject, "tw789");
+
         Ok(())
     }
 
@@ -255,21 +473,23 @@ mod tests {
         let pool = setup_db().await;
         let user_repo = UserRepository::new(pool.clone());
         let auth_service = AuthService::new(
-            "secret".to_string(),
-            "client".to_string(),
             "secret".to_string(),
             user_repo.clone(),
+            SessionRepository::new(pool.clone()),
         );
 
         // Create initial user
-        let initial = user_repo
-            .create(CreateUser {
-                twitch_id: "tw999".to_string(),
-                username: "oldname".to_string(),
-                display_name: Some("Old Name".to_string()),
-                profile_image_url: None,
-                email: None,
-            })
+        let initial = auth_service
+            .get_or_create_user(
+                TwitchUser {
+                    id: "tw999".to_string(),
+                    login: "oldname".to_string(),
+                    display_name: "Old Name".to_string(),
+                    profile_image_url: "http://old.image".to_string(),
+                    email: None,
+                }
+                .into(),
+            )
             .await?;
 
         // Call get_or_create with updated info
@@ -281,8 +501,8 @@ mod tests {
             email: Some("updated@example.com".to_string()),
         };
 
-        let user = auth_service.get_or_create_user(twitch_user).await?;
-        
+        let user = auth_service.get_or_create_user(twitch_user.into()).await?;
+
         // Should have same ID but updated info
         assert_eq!(user.id, initial.id);
         assert_eq!(user.username, "newname");
@@ -290,4 +510,254 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_first_login_claims_pending_permissions() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let user_repo = UserRepository::new(pool.clone());
+        let page_repo = crate::repositories::PageRepository::new(pool.clone());
+        let auth_service = AuthService::new(
+            "secret".to_string(),
+            user_repo.clone(),
+            SessionRepository::new(pool.clone()),
+        );
+
+        let owner = user_repo
+            .create(CreateUser {
+                twitch_id: Some("tw_owner".to_string()),
+                username: "owner".to_string(),
+                display_name: None,
+                profile_image_url: None,
+                email: None,
+            })
+            .await?;
+        let page = page_repo
+            .create(owner.id, crate::models::CreatePage {
+                title: "For a viewer".to_string(),
+                description: None,
+            })
+            .await?;
+        page_repo
+            .grant_pending_permission(page.id, "twitch", "SomeViewer", PageRole::Checker, owner.id, None)
+            .await?;
+        let create_page = |title: &str| {
+            page_repo.create(owner.id, crate::models::CreatePage {
+                title: title.to_string(),
+                description: None,
+            })
+        };
+        let temporary = create_page("For a week").await?;
+        let next_week = chrono::Utc::now() + chrono::Duration::days(7);
+        page_repo
+            .grant_pending_permission(temporary.id, "twitch", "someviewer", PageRole::Viewer, owner.id, Some(next_week))
+            .await?;
+        let lapsed = create_page("Too late").await?;
+        let yesterday = chrono::Utc::now() - chrono::Duration::days(1);
+        page_repo
+            .grant_pending_permission(lapsed.id, "twitch", "someviewer", PageRole::Viewer, owner.id, Some(yesterday))
+            .await?;
+
+        let viewer = auth_service
+            .get_or_create_user(
+                TwitchUser {
+                    id: "tw_viewer".to_string(),
+                    login: "someviewer".to_string(),
+                    display_name: "SomeViewer".to_string(),
+                    profile_image_url: "http://image.url".to_string(),
+                    email: None,
+                }
+                .into(),
+            )
+            .await?;
+
+        assert_eq!(page_repo.get_role(page.id, viewer.id).await?, Some(PageRole::Checker));
+        assert!(page_repo.list_pending_permissions(page.id).await?.is_empty());
+
+        // Temporary shares stay temporary once claimed
+        let permission = page_repo.get_user_permission(temporary.id, viewer.id).await?.unwrap();
+        assert_eq!(permission.expires_at.map(|t| t.timestamp()), Some(next_week.timestamp()));
+        assert!(page_repo.get_user_permission(lapsed.id, viewer.id).await?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_oidc_logins_do_not_claim_pending_permissions() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let user_repo = UserRepository::new(pool.clone());
+        let page_repo = crate::repositories::PageRepository::new(pool.clone());
+        let auth_service = AuthService::new(
+            "secret".to_string(),
+            user_repo.clone(),
+            SessionRepository::new(pool.clone()),
+        );
+
+        let owner = user_repo
+            .create(CreateUser {
+                twitch_id: Some("tw_owner".to_string()),
+                username: "owner".to_string(),
+                display_name: None,
+                profile_image_url: None,
+                email: None,
+            })
+            .await?;
+        let page = page_repo
+            .create(owner.id, crate::models::CreatePage {
+                title: "For jane@a.example".to_string(),
+                description: None,
+            })
+            .await?;
+        page_repo
+            .grant_pending_permission(page.id, "oidc", "jane", PageRole::Editor, owner.id, None)
+            .await?;
+
+        // Both accounts fall back to the same email local part as their username
+        for (subject, email) in [("sub-b", "jane@b.example"), ("sub-a", "jane@a.example")] {
+            let jane = auth_service
+                .get_or_create_user(ExternalIdentity {
+                    provider: "oidc".to_string(),
+                    subject: subject.to_string(),
+                    username: "jane".to_string(),
+                    display_name: None,
+                    profile_image_url: None,
+                    email: Some(email.to_string()),
+                })
+                .await?;
+            assert_eq!(page_repo.get_role(page.id, jane.id).await?, None);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_refresh_rotates_and_detects_reuse() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let user_repo = UserRepository::new(pool.clone());
+        let auth_service = AuthService::new(
+            "secret".to_string(),
+            user_repo.clone(),
+            SessionRepository::new(pool.clone()),
+        );
+
+        let user = user_repo
+            .create(CreateUser {
+                twitch_id: Some("tw555".to_string()),
+                username: "refresher".to_string(),
+                display_name: None,
+                profile_image_url: None,
+                email: None,
+            })
+            .await?;
+
+        let first = auth_service.start_session(&user, Some("agent"), Some("10.0.0.1")).await?;
+
+        // A valid refresh token yields a new pair in the same session
+        let second = auth_service
+            .refresh_session(&first.refresh_token, None, None)
+            .await?
+            .expect("refresh should succeed");
+        assert_eq!(session_id(&auth_service, &second), session_id(&auth_service, &first));
+        assert_ne!(second.refresh_token, first.refresh_token);
+        assert!(auth_service.is_session_active(&session_id(&auth_service, &second)).await?);
+
+        // Replaying the rotated-out token revokes the session
+        assert!(auth_service
+            .refresh_session(&first.refresh_token, None, None)
+            .await?
+            .is_none());
+        assert!(!auth_service.is_session_active(&session_id(&auth_service, &first)).await?);
+        assert!(auth_service
+            .refresh_session(&second.refresh_token, None, None)
+            .await?
+            .is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_session_must_belong_to_token_subject() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let user_repo = UserRepository::new(pool.clone());
+        let auth_service = AuthService::new(
+            "secret".to_string(),
+            user_repo.clone(),
+            SessionRepository::new(pool.clone()),
+        );
+
+        let mut users = Vec::new();
+        for (twitch_id, username) in [("tw601", "owner"), ("tw602", "other")] {
+            users.push(
+                user_repo
+                    .create(CreateUser {
+                        twitch_id: Some(twitch_id.to_string()),
+                        username: username.to_string(),
+                        display_name: None,
+                        profile_image_url: None,
+                        email: None,
+                    })
+                    .await?,
+            );
+        }
+
+        let pair = auth_service.start_session(&users[0], None, None).await?;
+        let sid = session_id(&auth_service, &pair);
+        assert!(auth_service.is_session_active_for(&sid, &users[0].id.to_string()).await?);
+        assert!(!auth_service.is_session_active_for(&sid, &users[1].id.to_string()).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_link_identity() -> anyhow::Result<()> {
+        let pool = setup_db().await;
+        let user_repo = UserRepository::new(pool.clone());
+        let auth_service = AuthService::new(
+            "secret".to_string(),
+            user_repo.clone(),
+            SessionRepository::new(pool.clone()),
+        );
+
+        let oidc_identity = |subject: &str| ExternalIdentity {
+            provider: "oidc".to_string(),
+            subject: subject.to_string(),
+            username: "jane".to_string(),
+            display_name: None,
+            profile_image_url: None,
+            email: Some("jane@example.com".to_string()),
+        };
+
+        let streamer = auth_service
+            .get_or_create_user(
+                TwitchUser {
+                    id: "tw1".to_string(),
+                    login: "streamer".to_string(),
+                    display_name: "Streamer".to_string(),
+                    profile_image_url: "http://image.url".to_string(),
+                    email: None,
+                }
+                .into(),
+            )
+            .await?;
+
+        // Non-streamers sign up without a Twitch account
+        let other = auth_service.get_or_create_user(oidc_identity("sub-2")).await?;
+        assert!(other.twitch_id.is_none());
+
+        // Linking a second provider lets the same user sign in with either
+        auth_service.link_identity(streamer.id, oidc_identity("sub-1")).await?;
+        let via_oidc = auth_service.get_or_create_user(oidc_identity("sub-1")).await?;
+        assert_eq!(via_oidc.id, streamer.id);
+
+        // An identity cannot be moved between users
+        assert!(auth_service.link_identity(streamer.id, oidc_identity("sub-2")).await.is_err());
+
+        // The last remaining identity cannot be unlinked
+        let identities = user_repo.list_identities(other.id).await?;
+        assert!(!user_repo.delete_identity(&identities[0].id, other.id).await?);
+        let identities = user_repo.list_identities(streamer.id).await?;
+        assert_eq!(identities.len(), 2);
+        assert!(user_repo.delete_identity(&identities[0].id, streamer.id).await?);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,72 @@
+use super::IdentityProvider;
+use crate::models::ExternalIdentity;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use oauth2::{PkceCodeChallenge, PkceCodeVerifier};
+use url::Url;
+
+/// Development-only login that needs no external service. The "consent screen"
+/// is `/api/auth/dev/authorize` on this server, where any username can be
+/// entered; the issued code is `{username}.{code_challenge}` and is only
+/// accepted together with the matching PKCE verifier from the state cookie.
+/// Never enable this in production: anyone can sign in as anyone.
+pub struct DevProvider {
+    redirect_uri: String,
+}
+
+impl DevProvider {
+    pub fn new(redirect_uri: String) -> Self {
+        Self { redirect_uri }
+    }
+
+    /// Where `/dev/authorize` sends the browser once a username was picked.
+    pub fn callback_url(&self, username: &str, state: &str, code_challenge: &str) -> Result<String> {
+        let mut url = Url::parse(&self.redirect_uri)?;
+        url.query_pairs_mut()
+            .append_pair("code", &format!("{}.{}", username, code_challenge))
+            .append_pair("state", state);
+        Ok(url.to_string())
+    }
+}
+
+#[async_trait]
+impl IdentityProvider for DevProvider {
+    fn id(&self) -> &str {
+        "dev"
+    }
+
+    fn name(&self) -> &str {
+        "Development login"
+    }
+
+    async fn authorize_url(&self, state: &str, code_challenge: &str) -> Result<String> {
+        // Sibling of the callback: `/api/auth/callback` -> `/api/auth/dev/authorize`
+        let mut url = Url::parse(&self.redirect_uri)?.join("dev/authorize")?;
+        url.query_pairs_mut()
+            .append_pair("state", state)
+            .append_pair("code_challenge", code_challenge);
+        Ok(url.to_string())
+    }
+
+    async fn fetch_identity(&self, code: &str, code_verifier: &str) -> Result<ExternalIdentity> {
+        let (username, challenge) = code
+            .rsplit_once('.')
+            .ok_or_else(|| anyhow!("Malformed dev login code"))?;
+
+        let expected = PkceCodeChallenge::from_code_verifier_sha256(&PkceCodeVerifier::new(
+            code_verifier.to_string(),
+        ));
+        if username.is_empty() || expected.as_str() != challenge {
+            return Err(anyhow!("Dev login code does not match this login attempt"));
+        }
+
+        Ok(ExternalIdentity {
+            provider: "dev".to_string(),
+            subject: username.to_string(),
+            username: username.to_string(),
+            display_name: None,
+            profile_image_url: None,
+            email: None,
+        })
+    }
+}
@@ -0,0 +1,62 @@
+//! External identity providers used for login. Each provider runs an OAuth2
+//! authorization-code flow (with PKCE) and reports the signed-in account as an
+//! [`ExternalIdentity`]; `AuthService` maps that onto a local user.
+
+pub mod dev;
+pub mod oidc;
+pub mod twitch;
+
+use crate::models::{ExternalIdentity, IdentityProviderInfo};
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+pub use dev::DevProvider;
+pub use oidc::OidcProvider;
+pub use twitch::TwitchProvider;
+
+#[async_trait]
+pub trait IdentityProvider: Send + Sync {
+    /// Stable key stored in `user_identities.provider`, e.g. `"twitch"`.
+    fn id(&self) -> &str;
+
+    /// Human-readable name for the login button.
+    fn name(&self) -> &str;
+
+    /// URL to send the browser to for the provider's consent screen.
+    async fn authorize_url(&self, state: &str, code_challenge: &str) -> Result<String>;
+
+    /// Exchange the callback `code` and fetch the signed-in account.
+    async fn fetch_identity(&self, code: &str, code_verifier: &str) -> Result<ExternalIdentity>;
+}
+
+/// The providers enabled in this deployment, in login-page order.
+#[derive(Clone, Default)]
+pub struct IdentityProviders {
+    providers: Vec<Arc<dyn IdentityProvider>>,
+}
+
+impl IdentityProviders {
+    pub fn new(providers: Vec<Arc<dyn IdentityProvider>>) -> Self {
+        Self { providers }
+    }
+
+    pub fn get(&self, id: &str) -> Option<Arc<dyn IdentityProvider>> {
+        self.providers.iter().find(|p| p.id() == id).cloned()
+    }
+
+    /// The provider used when `/auth/login` is called without `?provider=`.
+    pub fn default_provider(&self) -> Option<Arc<dyn IdentityProvider>> {
+        self.providers.first().cloned()
+    }
+
+    pub fn list(&self) -> Vec<IdentityProviderInfo> {
+        self.providers
+            .iter()
+            .map(|p| IdentityProviderInfo {
+                id: p.id().to_string(),
+                name: p.name().to_string(),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,239 @@
+use super::IdentityProvider;
+use crate::models::ExternalIdentity;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use tokio::sync::OnceCell;
+
+/// Endpoints from the issuer's `/.well-known/openid-configuration`.
+#[derive(Debug, Clone, Deserialize)]
+struct OidcDiscovery {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    userinfo_endpoint: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OidcTokenResponse {
+    access_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OidcUserInfo {
+    sub: String,
+    preferred_username: Option<String>,
+    name: Option<String>,
+    picture: Option<String>,
+    email: Option<String>,
+}
+
+/// A generic OpenID Connect provider. Endpoints are discovered from the issuer
+/// on first use; the account is read from the userinfo endpoint with the
+/// access token obtained directly from the token endpoint.
+pub struct OidcProvider {
+    id: String,
+    name: String,
+    issuer: String,
+    client_id: String,
+    client_secret: String,
+    redirect_uri: String,
+    discovery: OnceCell<OidcDiscovery>,
+}
+
+impl OidcProvider {
+    pub fn new(
+        id: String,
+        name: String,
+        issuer: String,
+        client_id: String,
+        client_secret: String,
+        redirect_uri: String,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            issuer: issuer.trim_end_matches('/').to_string(),
+            client_id,
+            client_secret,
+            redirect_uri,
+            discovery: OnceCell::new(),
+        }
+    }
+
+    async fn discovery(&self) -> Result<&OidcDiscovery> {
+        self.discovery
+            .get_or_try_init(|| async {
+                let url = format!("{}/.well-known/openid-configuration", self.issuer);
+                let response = Client::new().get(&url).send().await?;
+                if !response.status().is_success() {
+                    return Err(anyhow!("OIDC discovery failed: {}", response.status()));
+                }
+                let discovery = response.json::<OidcDiscovery>().await?;
+                if discovery.issuer.trim_end_matches('/') != self.issuer {
+                    return Err(anyhow!(
+                        "OIDC issuer mismatch: expected {}, got {}",
+                        self.issuer,
+                        discovery.issuer
+                    ));
+                }
+                Ok(discovery)
+            })
+            .await
+    }
+}
+
+#[async_trait]
+impl IdentityProvider for OidcProvider {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn authorize_url(&self, state: &str, code_challenge: &str) -> Result<String> {
+        let discovery = self.discovery().await?;
+        let mut url = url::Url::parse(&discovery.authorization_endpoint)?;
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.client_id)
+            .append_pair("redirect_uri", &self.redirect_uri)
+            .append_pair("response_type", "code")
+            .append_pair("scope", "openid profile email")
+            .append_pair("state", state)
+            .append_pair("code_challenge", code_challenge)
+            .append_pair("code_challenge_method", "S256");
+        Ok(url.to_string())
+    }
+
+    async fn fetch_identity(&self, code: &str, code_verifier: &str) -> Result<ExternalIdentity> {
+        let discovery = self.discovery().await?;
+        let client = Client::new();
+
+        let params = [
+            ("client_id", self.client_id.as_str()),
+            ("client_secret", self.client_secret.as_str()),
+            ("code", code),
+            ("grant_type", "authorization_code"),
+            ("redirect_uri", self.redirect_uri.as_str()),
+            ("code_verifier", code_verifier),
+        ];
+        let response = client
+            .post(&discovery.token_endpoint)
+            .form(&params)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let error_body = response.text().await?;
+            return Err(anyhow!("OIDC token exchange failed: {}", error_body));
+        }
+        let token = response.json::<OidcTokenResponse>().await?;
+
+        let userinfo_endpoint = discovery
+            .userinfo_endpoint
+            .as_deref()
+            .ok_or_else(|| anyhow!("OIDC provider does not expose a userinfo endpoint"))?;
+        let response = client
+            .get(userinfo_endpoint)
+            .bearer_auth(&token.access_token)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let error_body = response.text().await?;
+            return Err(anyhow!("Failed to get OIDC user info: {}", error_body));
+        }
+        let info = response.json::<OidcUserInfo>().await?;
+
+        let username = info
+            .preferred_username
+            .clone()
+            .or_else(|| {
+                info.email
+                    .as_deref()
+                    .and_then(|e| e.split('@').next())
+                    .map(str::to_string)
+            })
+            .unwrap_or_else(|| info.sub.clone());
+
+        Ok(ExternalIdentity {
+            provider: self.id.clone(),
+            subject: info.sub,
+            username,
+            display_name: info.name,
+            profile_image_url: info.picture,
+            email: info.email,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::{get, post}, Json, Router};
+
+    // Serve a minimal issuer on a random local port
+    async fn spawn_issuer() -> anyhow::Result<String> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let base = format!("http://{}", listener.local_addr()?);
+
+        let discovery = serde_json::json!({
+            "issuer": base,
+            "authorization_endpoint": format!("{}/authorize", base),
+            "token_endpoint": format!("{}/token", base),
+            "userinfo_endpoint": format!("{}/userinfo", base),
+        });
+        let app = Router::new()
+            .route(
+                "/.well-known/openid-configuration",
+                get(move || async move { Json(discovery) }),
+            )
+            .route(
+                "/token",
+                post(|| async { Json(serde_json::json!({ "access_token": "at", "token_type": "Bearer" })) }),
+            )
+            .route(
+                "/userinfo",
+                get(|| async {
+                    Json(serde_json::json!({
+                        "sub": "abc-123",
+                        "name": "Jane Doe",
+                        "email": "jane@example.com",
+                    }))
+                }),
+            );
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.ok();
+        });
+
+        Ok(base)
+    }
+
+    #[tokio::test]
+    async fn test_oidc_discovery_and_identity() -> anyhow::Result<()> {
+        let issuer = spawn_issuer().await?;
+        let provider = OidcProvider::new(
+            "oidc".to_string(),
+            "SSO".to_string(),
+            issuer.clone(),
+            "client".to_string(),
+            "secret".to_string(),
+            "http://localhost:8000/api/auth/callback".to_string(),
+        );
+
+        let url = url::Url::parse(&provider.authorize_url("st", "chal").await?)?;
+        assert_eq!(url.path(), "/authorize");
+        assert!(url.query_pairs().any(|(k, v)| k == "scope" && v.contains("openid")));
+        assert!(url.query_pairs().any(|(k, v)| k == "state" && v == "st"));
+
+        let identity = provider.fetch_identity("code", "verifier").await?;
+        assert_eq!(identity.provider, "oidc");
+        assert_eq!(identity.subject, "abc-123");
+        // No preferred_username: falls back to the email's local part
+        assert_eq!(identity.username, "jane");
+        assert_eq!(identity.display_name.as_deref(), Some("Jane Doe"));
+
+        Ok(())
+    }
+}
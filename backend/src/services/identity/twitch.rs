@@ -0,0 +1,114 @@
+use super::IdentityProvider;
+use crate::models::{ExternalIdentity, TwitchTokenResponse, TwitchUser, TwitchUserResponse};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+
+pub struct TwitchProvider {
+    client_id: String,
+    client_secret: String,
+    redirect_uri: String,
+}
+
+impl TwitchProvider {
+    pub fn new(client_id: String, client_secret: String, redirect_uri: String) -> Self {
+        Self {
+            client_id,
+            client_secret,
+            redirect_uri,
+        }
+    }
+
+    async fn exchange_code_for_token(
+        &self,
+        code: &str,
+        code_verifier: &str,
+    ) -> Result<TwitchTokenResponse> {
+        let client = Client::new();
+        let params = [
+            ("client_id", self.client_id.as_str()),
+            ("client_secret", self.client_secret.as_str()),
+            ("code", code),
+            ("grant_type", "authorization_code"),
+            ("redirect_uri", self.redirect_uri.as_str()),
+            ("code_verifier", code_verifier),
+        ];
+
+        let response = client
+            .post("https://id.twitch.tv/oauth2/token")
+            .form(&params)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_body = response.text().await?;
+            return Err(anyhow!("Twitch token exchange failed: {}", error_body));
+        }
+
+        let token_response = response.json::<TwitchTokenResponse>().await?;
+        Ok(token_response)
+    }
+
+    async fn get_twitch_user(&self, access_token: &str) -> Result<TwitchUser> {
+        let client = Client::new();
+        let response = client
+            .get("https://api.twitch.tv/helix/users")
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("Client-Id", &self.client_id)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_body = response.text().await?;
+            return Err(anyhow!("Failed to get Twitch user info: {}", error_body));
+        }
+
+        let user_response = response.json::<TwitchUserResponse>().await?;
+
+        user_response
+            .data
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("No user data received from Twitch"))
+    }
+}
+
+#[async_trait]
+impl IdentityProvider for TwitchProvider {
+    fn id(&self) -> &str {
+        "twitch"
+    }
+
+    fn name(&self) -> &str {
+        "Twitch"
+    }
+
+    async fn authorize_url(&self, state: &str, code_challenge: &str) -> Result<String> {
+        Ok(format!(
+            "https://id.twitch.tv/oauth2/authorize?client_id={}&redirect_uri={}&response_type=code&scope=user:read:email&state={}&code_challenge={}&code_challenge_method=S256",
+            self.client_id,
+            urlencoding::encode(&self.redirect_uri),
+            urlencoding::encode(state),
+            urlencoding::encode(code_challenge),
+        ))
+    }
+
+    async fn fetch_identity(&self, code: &str, code_verifier: &str) -> Result<ExternalIdentity> {
+        let token_response = self.exchange_code_for_token(code, code_verifier).await?;
+        let twitch_user = self.get_twitch_user(&token_response.access_token).await?;
+        Ok(twitch_user.into())
+    }
+}
+
+impl From<TwitchUser> for ExternalIdentity {
+    fn from(user: TwitchUser) -> Self {
+        ExternalIdentity {
+            provider: "twitch".to_string(),
+            subject: user.id,
+            username: user.login,
+            display_name: Some(user.display_name),
+            profile_image_url: Some(user.profile_image_url),
+            email: user.email,
+        }
+    }
+}
@@ -0,0 +1,310 @@
+use crate::config::Config;
+use anyhow::{anyhow, bail, Context, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, JwkSet, KeyAlgorithm,
+    OctetKeyPairParameters, OctetKeyPairType, PublicKeyUse, RSAKeyParameters, RSAKeyType,
+};
+use jsonwebtoken::{decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use ring::signature::{Ed25519KeyPair, KeyPair, RsaKeyPair, RsaPublicKeyComponents};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A verification-only key from `JWT_PREVIOUS_KEYS`: either an old HS256
+/// secret or the public JWK of an old asymmetric key (as published by our
+/// own JWKS endpoint before the rotation).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum PreviousJwtKey {
+    Secret { kid: String, secret: String },
+    Jwk(Jwk),
+}
+
+struct VerifyingKey {
+    algorithm: Algorithm,
+    key: DecodingKey,
+}
+
+/// Keys for access tokens. New tokens are signed with the current key and
+/// carry its `kid`; tokens are verified with whichever key their `kid` names,
+/// so rotating the signing key doesn't sign everybody out.
+pub struct JwtKeyring {
+    kid: String,
+    algorithm: Algorithm,
+    signing_key: EncodingKey,
+    verifying_keys: HashMap<String, VerifyingKey>,
+    /// Public halves of the asymmetric keys, for the JWKS endpoint.
+    public_keys: Vec<Jwk>,
+}
+
+impl JwtKeyring {
+    /// An HS256 keyring signing with `secret` under `kid`.
+    pub fn from_secret(kid: &str, secret: &str) -> Self {
+        let mut verifying_keys = HashMap::new();
+        verifying_keys.insert(
+            kid.to_string(),
+            VerifyingKey {
+                algorithm: Algorithm::HS256,
+                key: DecodingKey::from_secret(secret.as_bytes()),
+            },
+        );
+        Self {
+            kid: kid.to_string(),
+            algorithm: Algorithm::HS256,
+            signing_key: EncodingKey::from_secret(secret.as_bytes()),
+            verifying_keys,
+            public_keys: Vec::new(),
+        }
+    }
+
+    /// A keyring signing with an EdDSA (Ed25519) or RS256 private key in PEM
+    /// form.
+    pub fn from_private_key_pem(kid: &str, algorithm: Algorithm, pem_key: &str) -> Result<Self> {
+        let parsed = pem::parse(pem_key).context("Invalid PEM private key")?;
+
+        let (signing_key, verifying_key, params) = match algorithm {
+            Algorithm::EdDSA => {
+                let pair = Ed25519KeyPair::from_pkcs8_maybe_unchecked(parsed.contents())
+                    .map_err(|e| anyhow!("Invalid Ed25519 private key: {}", e))?;
+                let x = URL_SAFE_NO_PAD.encode(pair.public_key().as_ref());
+                (
+                    EncodingKey::from_ed_der(parsed.contents()),
+                    DecodingKey::from_ed_components(&x)?,
+                    AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                        key_type: OctetKeyPairType::OctetKeyPair,
+                        curve: EllipticCurve::Ed25519,
+                        x,
+                    }),
+                )
+            }
+            Algorithm::RS256 => {
+                let pair = match parsed.tag() {
+                    "RSA PRIVATE KEY" => RsaKeyPair::from_der(parsed.contents()),
+                    _ => RsaKeyPair::from_pkcs8(parsed.contents()),
+                }
+                .map_err(|e| anyhow!("Invalid RSA private key: {}", e))?;
+                let components = RsaPublicKeyComponents::<Vec<u8>>::from(pair.public());
+                (
+                    EncodingKey::from_rsa_pem(pem_key.as_bytes())?,
+                    DecodingKey::from_rsa_raw_components(&components.n, &components.e),
+                    AlgorithmParameters::RSA(RSAKeyParameters {
+                        key_type: RSAKeyType::RSA,
+                        n: URL_SAFE_NO_PAD.encode(&components.n),
+                        e: URL_SAFE_NO_PAD.encode(&components.e),
+                    }),
+                )
+            }
+            other => bail!("Unsupported JWT signing algorithm {:?}", other),
+        };
+
+        let mut verifying_keys = HashMap::new();
+        verifying_keys.insert(
+            kid.to_string(),
+            VerifyingKey {
+                algorithm,
+                key: verifying_key,
+            },
+        );
+        Ok(Self {
+            kid: kid.to_string(),
+            algorithm,
+            signing_key,
+            verifying_keys,
+            public_keys: vec![Jwk {
+                common: public_key_params(kid, algorithm)?,
+                algorithm: params,
+            }],
+        })
+    }
+
+    /// Build the keyring from `JWT_ALGORITHM`, `JWT_KEY_ID`, `JWT_SECRET` /
+    /// `JWT_PRIVATE_KEY_FILE` and `JWT_PREVIOUS_KEYS`.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let algorithm: Algorithm = config
+            .jwt_algorithm
+            .parse()
+            .map_err(|_| anyhow!("Unknown JWT_ALGORITHM {}", config.jwt_algorithm))?;
+
+        let mut keyring = match algorithm {
+            Algorithm::HS256 => Self::from_secret(&config.jwt_key_id, &config.jwt_secret),
+            _ => {
+                let path = config
+                    .jwt_private_key_file
+                    .as_deref()
+                    .ok_or_else(|| anyhow!("JWT_PRIVATE_KEY_FILE is required for {:?}", algorithm))?;
+                let pem_key = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read JWT private key {}", path))?;
+                Self::from_private_key_pem(&config.jwt_key_id, algorithm, &pem_key)?
+            }
+        };
+
+        if let Some(ref raw) = config.jwt_previous_keys {
+            let previous: Vec<PreviousJwtKey> =
+                serde_json::from_str(raw).context("JWT_PREVIOUS_KEYS must be a JSON array")?;
+            for key in previous {
+                keyring.add_previous_key(key)?;
+            }
+        }
+
+        Ok(keyring)
+    }
+
+    /// Accept tokens signed with an old key; it is never used for signing.
+    pub fn add_previous_key(&mut self, key: PreviousJwtKey) -> Result<()> {
+        let (kid, verifying_key) = match key {
+            PreviousJwtKey::Secret { kid, secret } => (
+                kid,
+                VerifyingKey {
+                    algorithm: Algorithm::HS256,
+                    key: DecodingKey::from_secret(secret.as_bytes()),
+                },
+            ),
+            PreviousJwtKey::Jwk(jwk) => {
+                let kid = jwk
+                    .common
+                    .key_id
+                    .clone()
+                    .ok_or_else(|| anyhow!("Previous JWK is missing \"kid\""))?;
+                let algorithm = match jwk.common.key_algorithm {
+                    Some(KeyAlgorithm::EdDSA) => Algorithm::EdDSA,
+                    Some(KeyAlgorithm::RS256) => Algorithm::RS256,
+                    other => bail!("Unsupported algorithm {:?} for previous JWK {}", other, kid),
+                };
+                let key = DecodingKey::from_jwk(&jwk)?;
+                self.public_keys.push(jwk);
+                (kid, VerifyingKey { algorithm, key })
+            }
+        };
+
+        if self.verifying_keys.contains_key(&kid) {
+            bail!("Duplicate JWT key id {}", kid);
+        }
+        self.verifying_keys.insert(kid, verifying_key);
+        Ok(())
+    }
+
+    pub fn sign<T: Serialize>(&self, claims: &T) -> Result<String> {
+        let mut header = Header::new(self.algorithm);
+        header.kid = Some(self.kid.clone());
+        encode(&header, claims, &self.signing_key).map_err(|e| anyhow!("Failed to sign JWT: {}", e))
+    }
+
+    pub fn verify<T: DeserializeOwned>(&self, token: &str) -> Result<T> {
+        let header = decode_header(token)?;
+        // Tokens from before keys had ids carry no session id either, so the
+        // auth middleware would reject them anyway
+        let kid = header.kid.ok_or_else(|| anyhow!("JWT has no key id"))?;
+        let key = self
+            .verifying_keys
+            .get(&kid)
+            .ok_or_else(|| anyhow!("Unknown JWT key id {}", kid))?;
+        // Pinning the algorithm to the key prevents alg-confusion attacks
+        decode::<T>(token, &key.key, &Validation::new(key.algorithm))
+            .map(|data| data.claims)
+            .map_err(|e| anyhow!("{}", e))
+    }
+
+    /// Public keys for `/api/auth/jwks.json`; HMAC secrets are never listed.
+    pub fn jwks(&self) -> JwkSet {
+        JwkSet {
+            keys: self.public_keys.clone(),
+        }
+    }
+}
+
+fn public_key_params(kid: &str, algorithm: Algorithm) -> Result<CommonParameters> {
+    let key_algorithm = match algorithm {
+        Algorithm::EdDSA => KeyAlgorithm::EdDSA,
+        Algorithm::RS256 => KeyAlgorithm::RS256,
+        other => bail!("No public key for {:?}", other),
+    };
+    Ok(CommonParameters {
+        public_key_use: Some(PublicKeyUse::Signature),
+        key_algorithm: Some(key_algorithm),
+        key_id: Some(kid.to_string()),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Claims;
+    use ring::rand::SystemRandom;
+
+    fn claims() -> Claims {
+        Claims {
+            sub: "user".to_string(),
+            username: "user".to_string(),
+            exp: 9999999999,
+            scopes: None,
+            pages: None,
+            sid: None,
+        }
+    }
+
+    #[test]
+    fn test_rotated_secret_still_verifies_old_tokens() -> anyhow::Result<()> {
+        let old = JwtKeyring::from_secret("2025", "old_secret");
+        let old_token = old.sign(&claims())?;
+
+        let mut current = JwtKeyring::from_secret("2026", "new_secret");
+        assert!(current.verify::<Claims>(&old_token).is_err());
+
+        current.add_previous_key(PreviousJwtKey::Secret {
+            kid: "2025".to_string(),
+            secret: "old_secret".to_string(),
+        })?;
+        assert_eq!(current.verify::<Claims>(&old_token)?.sub, "user");
+
+        let new_token = current.sign(&claims())?;
+        assert_eq!(decode_header(&new_token)?.kid.as_deref(), Some("2026"));
+        assert!(old.verify::<Claims>(&new_token).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tokens_without_kid_are_rejected() -> anyhow::Result<()> {
+        let token = encode(
+            &Header::default(),
+            &claims(),
+            &EncodingKey::from_secret(b"secret"),
+        )?;
+        let keyring = JwtKeyring::from_secret("k1", "secret");
+        assert!(keyring.verify::<Claims>(&token).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_eddsa_keyring_publishes_verifiable_jwks() -> anyhow::Result<()> {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())
+            .map_err(|_| anyhow!("keygen failed"))?;
+        let pem_key = pem::encode(&pem::Pem::new("PRIVATE KEY", pkcs8.as_ref().to_vec()));
+
+        let keyring = JwtKeyring::from_private_key_pem("ed1", Algorithm::EdDSA, &pem_key)?;
+        let token = keyring.sign(&claims())?;
+        assert_eq!(keyring.verify::<Claims>(&token)?.sub, "user");
+
+        // Another service verifies with nothing but the published JWK
+        let jwks = keyring.jwks();
+        assert_eq!(jwks.keys.len(), 1);
+        let jwk = jwks.find("ed1").unwrap();
+        let data = decode::<Claims>(&token, &DecodingKey::from_jwk(jwk)?, &Validation::new(Algorithm::EdDSA))?;
+        assert_eq!(data.claims.sub, "user");
+
+        // After rotating back to HS256 the old JWK keeps verifying
+        let mut rotated = JwtKeyring::from_secret("hs1", "secret");
+        rotated.add_previous_key(serde_json::from_value(serde_json::to_value(jwk)?)?)?;
+        assert_eq!(rotated.verify::<Claims>(&token)?.sub, "user");
+        assert_eq!(rotated.jwks().keys.len(), 1);
+
+        // A token claiming the EdDSA kid but signed with HMAC is rejected
+        let mut header = Header::new(Algorithm::HS256);
+        header.kid = Some("ed1".to_string());
+        let forged = encode(&header, &claims(), &EncodingKey::from_secret(b"secret"))?;
+        assert!(keyring.verify::<Claims>(&forged).is_err());
+        Ok(())
+    }
+}
@@ -1,5 +1,15 @@
 pub mod auth;
 pub mod api_key;
+pub mod identity;
+pub mod jwt_keys;
+pub mod public_html;
+pub mod public_link;
+pub mod preview_image;
+pub mod rate_limit;
 
 pub use auth::AuthService;
 pub use api_key::ApiKeyService;
+pub use identity::{DevProvider, IdentityProvider, IdentityProviders, OidcProvider, TwitchProvider};
+pub use jwt_keys::JwtKeyring;
+pub use preview_image::PreviewCache;
+pub use rate_limit::RateLimiter;
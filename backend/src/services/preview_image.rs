@@ -0,0 +1,197 @@
+//! Open Graph preview images for public links: an SVG summary of the page (title,
+//! list names, completion) rasterized to PNG. Rendering takes a while, so images are
+//! kept per slug until the page's `updated_at` changes.
+
+use super::public_html::{completion_percent, escape_html};
+use crate::models::PublicPageData;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use resvg::{tiny_skia, usvg};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex, OnceLock};
+
+pub const WIDTH: u32 = 1200;
+pub const HEIGHT: u32 = 630;
+
+const MAX_LISTS: usize = 4;
+const MAX_CACHED: usize = 500;
+const FONT_FAMILY: &str = "'IBM Plex Sans', sans-serif";
+
+// Light theme tokens from DESIGN.md
+const BACKGROUND: &str = "#FAFAF9";
+const SUBTLE: &str = "#F5F5F4";
+const FOREGROUND: &str = "#1C1917";
+const SECONDARY_FOREGROUND: &str = "#57534E";
+const MUTED_FOREGROUND: &str = "#78716C";
+const ACCENT: &str = "#15803D";
+
+fn truncate(input: &str, max_chars: usize) -> String {
+    if input.chars().count() <= max_chars {
+        return input.to_string();
+    }
+    let cut: String = input.chars().take(max_chars - 1).collect();
+    format!("{}…", cut.trim_end())
+}
+
+pub fn render_svg(data: &PublicPageData) -> String {
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{HEIGHT}\" viewBox=\"0 0 {WIDTH} {HEIGHT}\" font-family=\"{FONT_FAMILY}\">\n\
+         <rect width=\"{WIDTH}\" height=\"{HEIGHT}\" fill=\"{BACKGROUND}\"/>\n\
+         <rect x=\"0\" y=\"0\" width=\"{WIDTH}\" height=\"12\" fill=\"{ACCENT}\"/>\n\
+         <text x=\"80\" y=\"100\" font-size=\"28\" fill=\"{MUTED_FOREGROUND}\">Shared Lists</text>\n\
+         <text x=\"80\" y=\"180\" font-size=\"64\" font-weight=\"600\" fill=\"{FOREGROUND}\">{}</text>\n",
+        escape_html(&truncate(&data.page.title, 32))
+    );
+
+    if data.lists.is_empty() {
+        let text = data.page.description.as_deref().unwrap_or("No lists yet");
+        let _ = writeln!(
+            svg,
+            "<text x=\"80\" y=\"260\" font-size=\"32\" fill=\"{SECONDARY_FOREGROUND}\">{}</text>",
+            escape_html(&truncate(text, 60))
+        );
+    }
+
+    for (i, list) in data.lists.iter().take(MAX_LISTS).enumerate() {
+        let y = 270 + i * 85;
+        let _ = writeln!(
+            svg,
+            "<text x=\"80\" y=\"{y}\" font-size=\"34\" fill=\"{FOREGROUND}\">{}</text>",
+            escape_html(&truncate(&list.title, 44))
+        );
+
+        let label = match completion_percent(list) {
+            Some(percent) => {
+                let filled = (WIDTH as usize - 160) * percent / 100;
+                let _ = writeln!(
+                    svg,
+                    "<rect x=\"80\" y=\"{bar}\" width=\"{track}\" height=\"14\" rx=\"7\" fill=\"{SUBTLE}\"/>\n\
+                     <rect x=\"80\" y=\"{bar}\" width=\"{filled}\" height=\"14\" rx=\"7\" fill=\"{ACCENT}\"/>",
+                    bar = y + 18,
+                    track = WIDTH - 160,
+                );
+                format!("{}%", percent)
+            }
+            None => match list.items.len() {
+                1 => "1 item".to_string(),
+                n => format!("{} items", n),
+            },
+        };
+        let _ = writeln!(
+            svg,
+            "<text x=\"{x}\" y=\"{y}\" font-size=\"30\" fill=\"{SECONDARY_FOREGROUND}\" text-anchor=\"end\">{label}</text>",
+            x = WIDTH - 80,
+        );
+    }
+
+    if data.lists.len() > MAX_LISTS {
+        let more = data.lists.len() - MAX_LISTS;
+        let _ = writeln!(
+            svg,
+            "<text x=\"80\" y=\"{y}\" font-size=\"26\" fill=\"{MUTED_FOREGROUND}\">+{more} more {}</text>",
+            if more == 1 { "list" } else { "lists" },
+            y = HEIGHT - 40,
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// System fonts are scanned once; that's the slow part of setting up usvg.
+fn options() -> &'static usvg::Options<'static> {
+    static OPTIONS: OnceLock<usvg::Options<'static>> = OnceLock::new();
+    OPTIONS.get_or_init(|| {
+        let mut options = usvg::Options::default();
+        Arc::make_mut(&mut options.fontdb).load_system_fonts();
+        options
+    })
+}
+
+pub fn render_png(svg: &str) -> Result<Vec<u8>> {
+    let tree = usvg::Tree::from_str(svg, options())?;
+    let mut pixmap = tiny_skia::Pixmap::new(WIDTH, HEIGHT)
+        .ok_or_else(|| anyhow!("Invalid preview image size"))?;
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+    Ok(pixmap.encode_png()?)
+}
+
+/// A rendered PNG and the `updated_at` it was rendered for.
+type CachedPreview = (DateTime<Utc>, Arc<Vec<u8>>);
+
+/// Rendered previews by slug.
+#[derive(Default)]
+pub struct PreviewCache {
+    entries: Mutex<HashMap<String, CachedPreview>>,
+}
+
+impl PreviewCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, slug: &str, updated_at: DateTime<Utc>) -> Option<Arc<Vec<u8>>> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .get(slug)
+            .filter(|(rendered_for, _)| *rendered_for == updated_at)
+            .map(|(_, png)| png.clone())
+    }
+
+    pub fn insert(&self, slug: &str, updated_at: DateTime<Utc>, png: Arc<Vec<u8>>) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= MAX_CACHED && !entries.contains_key(slug) {
+            entries.clear();
+        }
+        entries.insert(slug.to_string(), (updated_at, png));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{PublicList, PublicPage, PublicProgress};
+    use uuid::Uuid;
+
+    #[test]
+    fn test_render_preview() {
+        let list = |title: &str, progress: Option<PublicProgress>| PublicList {
+            id: Uuid::new_v4(),
+            title: title.to_string(),
+            show_checkboxes: true,
+            show_progress: progress.is_some(),
+            public_checking: false,
+            public_suggestions: false,
+            progress,
+            items: Vec::new(),
+        };
+        let data = PublicPageData {
+            page: PublicPage {
+                title: "Camping <trip>".to_string(),
+                description: None,
+                public_slug: Some("camping".to_string()),
+                updated_at: Utc::now(),
+            },
+            lists: vec![
+                list("Gear", Some(PublicProgress { checked: 3, total: 4 })),
+                list("Food", None),
+                list("Car", None),
+                list("Tent", None),
+                list("Music", None),
+            ],
+        };
+
+        let svg = render_svg(&data);
+        assert!(svg.contains("Camping &lt;trip&gt;"));
+        assert!(svg.contains(">75%</text>"));
+        assert!(svg.contains("+1 more list"));
+        assert!(svg.contains("font-family=\"'IBM Plex Sans', sans-serif\""));
+        assert!(svg.contains("fill=\"#15803D\""));
+
+        let png = render_png(&svg).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+    }
+}
@@ -0,0 +1,201 @@
+//! Server-rendered HTML for public links, for visitors without JavaScript and for
+//! link previews in chat apps and search engines.
+//!
+//! Plain semantic markup only: the app's CSP blocks inline styles and scripts, and
+//! `<progress>` / disabled checkboxes render fine without either.
+
+use crate::models::{PublicList, PublicPageData};
+use std::fmt::Write;
+
+const SITE_NAME: &str = "Shared Lists";
+const DESCRIPTION_MAX: usize = 200;
+
+pub fn escape_html(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Completion as a whole percentage, `None` when the list hides its progress.
+pub fn completion_percent(list: &PublicList) -> Option<usize> {
+    list.progress
+        .as_ref()
+        .map(|p| (p.checked * 100).checked_div(p.total).unwrap_or(0))
+}
+
+/// The page description, or a summary of its lists when it has none.
+fn summary(data: &PublicPageData) -> String {
+    let text = match data.page.description.as_deref().map(str::trim) {
+        Some(description) if !description.is_empty() => description.to_string(),
+        _ => data
+            .lists
+            .iter()
+            .map(|list| match completion_percent(list) {
+                Some(percent) => format!("{} ({}%)", list.title, percent),
+                None => list.title.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join(" · "),
+    };
+    if text.chars().count() > DESCRIPTION_MAX {
+        let cut: String = text.chars().take(DESCRIPTION_MAX - 1).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        text
+    }
+}
+
+fn head(out: &mut String, title: &str, description: &str, url: &str, image_url: Option<&str>) {
+    let title = escape_html(title);
+    let description = escape_html(description);
+    let url = escape_html(url);
+
+    let _ = write!(
+        out,
+        "<!doctype html>\n<html>\n<head>\n\
+         <meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title}</title>\n\
+         <meta name=\"description\" content=\"{description}\">\n\
+         <link rel=\"canonical\" href=\"{url}\">\n\
+         <meta property=\"og:type\" content=\"website\">\n\
+         <meta property=\"og:site_name\" content=\"{SITE_NAME}\">\n\
+         <meta property=\"og:title\" content=\"{title}\">\n\
+         <meta property=\"og:description\" content=\"{description}\">\n\
+         <meta property=\"og:url\" content=\"{url}\">\n\
+         <meta name=\"twitter:title\" content=\"{title}\">\n\
+         <meta name=\"twitter:description\" content=\"{description}\">\n"
+    );
+    match image_url {
+        Some(image_url) => {
+            let image_url = escape_html(image_url);
+            let _ = write!(
+                out,
+                "<meta property=\"og:image\" content=\"{image_url}\">\n\
+                 <meta name=\"twitter:card\" content=\"summary_large_image\">\n\
+                 <meta name=\"twitter:image\" content=\"{image_url}\">\n"
+            );
+        }
+        None => out.push_str("<meta name=\"twitter:card\" content=\"summary\">\n"),
+    }
+    out.push_str("</head>\n<body>\n");
+}
+
+/// Full page for a public link. `url` is where the interactive app shows the page.
+pub fn render_public_page(data: &PublicPageData, url: &str, image_url: Option<&str>) -> String {
+    let mut out = String::new();
+    head(&mut out, &data.page.title, &summary(data), url, image_url);
+
+    let _ = writeln!(out, "<header>\n<h1>{}</h1>", escape_html(&data.page.title));
+    if let Some(description) = &data.page.description {
+        let _ = writeln!(out, "<p>{}</p>", escape_html(description));
+    }
+    out.push_str("</header>\n<main>\n");
+
+    if data.lists.is_empty() {
+        out.push_str("<p>No lists yet</p>\n");
+    }
+    for list in &data.lists {
+        let _ = writeln!(out, "<section>\n<h2>{}</h2>", escape_html(&list.title));
+        if let Some(progress) = &list.progress {
+            let _ = writeln!(
+                out,
+                "<p><progress value=\"{checked}\" max=\"{total}\">{checked}/{total}</progress> {checked}/{total}</p>",
+                checked = progress.checked,
+                total = progress.total,
+            );
+        }
+        if list.items.is_empty() {
+            out.push_str("<p>No items</p>\n");
+        } else {
+            out.push_str("<ul>\n");
+            for item in &list.items {
+                let content = escape_html(&item.content);
+                match item.checked {
+                    Some(true) => {
+                        let _ = writeln!(out, "<li><input type=\"checkbox\" checked disabled> <s>{}</s></li>", content);
+                    }
+                    Some(false) => {
+                        let _ = writeln!(out, "<li><input type=\"checkbox\" disabled> {}</li>", content);
+                    }
+                    None => {
+                        let _ = writeln!(out, "<li>{}</li>", content);
+                    }
+                }
+            }
+            out.push_str("</ul>\n");
+        }
+        out.push_str("</section>\n");
+    }
+
+    let _ = write!(
+        out,
+        "</main>\n<footer><p><a href=\"{}\">Open in {}</a></p></footer>\n</body>\n</html>\n",
+        escape_html(url),
+        SITE_NAME
+    );
+    out
+}
+
+/// Stand-in for links that can't be shown: missing, expired or password protected.
+pub fn render_public_error(title: &str, message: &str, url: &str) -> String {
+    let mut out = String::new();
+    head(&mut out, title, message, url, None);
+    let _ = write!(
+        out,
+        "<main>\n<h1>{}</h1>\n<p>{}</p>\n<p><a href=\"{}\">Open in {}</a></p>\n</main>\n</body>\n</html>\n",
+        escape_html(title),
+        escape_html(message),
+        escape_html(url),
+        SITE_NAME
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{PublicListItem, PublicPage, PublicProgress};
+    use uuid::Uuid;
+
+    #[test]
+    fn test_render_public_page() {
+        let data = PublicPageData {
+            page: PublicPage {
+                title: "Tom & Jerry's <party>".to_string(),
+                description: None,
+                public_slug: Some("party".to_string()),
+                updated_at: chrono::Utc::now(),
+            },
+            lists: vec![PublicList {
+                id: Uuid::new_v4(),
+                title: "Snacks".to_string(),
+                show_checkboxes: true,
+                show_progress: true,
+                public_checking: false,
+                public_suggestions: false,
+                progress: Some(PublicProgress { checked: 1, total: 3 }),
+                items: vec![PublicListItem {
+                    id: Uuid::new_v4(),
+                    content: "<script>alert(1)</script>".to_string(),
+                    checked: Some(true),
+                }],
+            }],
+        };
+
+        let html = render_public_page(&data, "https://lists.example/p/party", None);
+        assert!(html.contains("<title>Tom &amp; Jerry&#39;s &lt;party&gt;</title>"));
+        assert!(html.contains("<meta property=\"og:description\" content=\"Snacks (33%)\">"));
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(!html.contains("<script>"));
+    }
+}
@@ -0,0 +1,129 @@
+//! Password protection for public page links.
+//!
+//! Passwords are stored as PBKDF2-HMAC-SHA256 hashes. After a correct password the
+//! visitor gets a short-lived unlock token, `{expiry}.{hmac}`, keyed by the stored
+//! hash: changing or removing the password invalidates every token issued for it.
+
+use chrono::{Duration, TimeZone, Utc};
+use rand::RngCore;
+use ring::{hmac, pbkdf2};
+use std::num::NonZeroU32;
+
+const PBKDF2_ITERATIONS: u32 = 100_000;
+const SALT_LENGTH: usize = 16;
+const HASH_LENGTH: usize = 32;
+const HASH_SCHEME: &str = "pbkdf2-sha256";
+
+/// How long an unlocked public page stays readable without the password.
+pub const UNLOCK_TTL_MINUTES: i64 = 60;
+
+/// Per-slug name so unlocking one page doesn't replace another page's cookie.
+pub fn unlock_cookie_name(slug: &str) -> String {
+    format!("public_unlock_{}", slug)
+}
+
+/// `pbkdf2-sha256${iterations}${salt}${hash}`, hex encoded.
+pub fn hash_password(password: &str) -> String {
+    let mut salt = [0u8; SALT_LENGTH];
+    rand::thread_rng().fill_bytes(&mut salt);
+
+    let mut hash = [0u8; HASH_LENGTH];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        NonZeroU32::new(PBKDF2_ITERATIONS).unwrap(),
+        &salt,
+        password.as_bytes(),
+        &mut hash,
+    );
+
+    format!(
+        "{}${}${}${}",
+        HASH_SCHEME,
+        PBKDF2_ITERATIONS,
+        hex::encode(salt),
+        hex::encode(hash)
+    )
+}
+
+pub fn verify_password(password: &str, stored: &str) -> bool {
+    let mut parts = stored.split('$');
+    let (Some(HASH_SCHEME), Some(iterations), Some(salt), Some(hash), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return false;
+    };
+    let (Some(iterations), Ok(salt), Ok(hash)) = (
+        iterations.parse::<u32>().ok().and_then(NonZeroU32::new),
+        hex::decode(salt),
+        hex::decode(hash),
+    ) else {
+        return false;
+    };
+
+    pbkdf2::verify(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        &salt,
+        password.as_bytes(),
+        &hash,
+    )
+    .is_ok()
+}
+
+fn unlock_key(password_hash: &str) -> hmac::Key {
+    hmac::Key::new(hmac::HMAC_SHA256, password_hash.as_bytes())
+}
+
+fn unlock_message(slug: &str, expires: i64) -> String {
+    format!("{}:{}", slug, expires)
+}
+
+/// Token proving the password for `slug` was entered, valid for [`UNLOCK_TTL_MINUTES`].
+pub fn issue_unlock_token(slug: &str, password_hash: &str) -> String {
+    let expires = (Utc::now() + Duration::minutes(UNLOCK_TTL_MINUTES)).timestamp();
+    let tag = hmac::sign(&unlock_key(password_hash), unlock_message(slug, expires).as_bytes());
+    format!("{}.{}", expires, hex::encode(tag.as_ref()))
+}
+
+pub fn verify_unlock_token(token: &str, slug: &str, password_hash: &str) -> bool {
+    let Some((expires, tag)) = token.split_once('.') else {
+        return false;
+    };
+    let (Ok(expires), Ok(tag)) = (expires.parse::<i64>(), hex::decode(tag)) else {
+        return false;
+    };
+    if Utc.timestamp_opt(expires, 0).single().is_none_or(|t| t <= Utc::now()) {
+        return false;
+    }
+
+    hmac::verify(
+        &unlock_key(password_hash),
+        unlock_message(slug, expires).as_bytes(),
+        &tag,
+    )
+    .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password_and_unlock_token() {
+        let stored = hash_password("hunter22");
+        assert!(verify_password("hunter22", &stored));
+        assert!(!verify_password("hunter23", &stored));
+        assert!(!verify_password("hunter22", "plaintext"));
+
+        let token = issue_unlock_token("party", &stored);
+        assert!(verify_unlock_token(&token, "party", &stored));
+        // Bound to the slug and to the current password
+        assert!(!verify_unlock_token(&token, "other", &stored));
+        assert!(!verify_unlock_token(&token, "party", &hash_password("hunter22")));
+
+        // A forged expiry breaks the signature
+        let (_, tag) = token.split_once('.').unwrap();
+        let forged = format!("{}.{}", (Utc::now() + Duration::days(365)).timestamp(), tag);
+        assert!(!verify_unlock_token(&forged, "party", &stored));
+    }
+}
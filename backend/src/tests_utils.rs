@@ -5,17 +5,37 @@ pub async fn setup_db() -> sqlx::SqlitePool {
         .await
         .expect("Failed to create sqlite pool");
 
-    // Ensure foreign keys are enforced
-    sqlx::query("PRAGMA foreign_keys = ON;")
-        .execute(&pool)
+    // Run migrations from `migrations/` automatically (path is relative to crate root).
+    // Like in main, foreign keys are off while migrating so table rebuilds don't cascade.
+    let mut conn = pool.acquire().await.expect("Failed to acquire connection");
+    sqlx::query("PRAGMA foreign_keys = OFF;")
+        .execute(&mut *conn)
         .await
-        .expect("Failed to enable foreign keys");
-
-    // Run migrations from `migrations/` automatically (path is relative to crate root)
+        .expect("Failed to disable foreign keys");
     sqlx::migrate!("./migrations")
-        .run(&pool)
+        .run(&mut *conn)
         .await
         .expect("Failed to run migrations");
 
+    // Ensure foreign keys are enforced
+    sqlx::query("PRAGMA foreign_keys = ON;")
+        .execute(&mut *conn)
+        .await
+        .expect("Failed to enable foreign keys");
+    drop(conn);
+
     pool
 }
+
+/// Session id carried in the `sid` claim of a session's access token.
+#[cfg(test)]
+pub fn session_id(
+    auth_service: &crate::services::AuthService,
+    tokens: &crate::models::SessionTokens,
+) -> String {
+    auth_service
+        .verify_jwt(&tokens.access_token)
+        .expect("valid access token")
+        .sid
+        .expect("session access token")
+}
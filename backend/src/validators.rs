@@ -1,4 +1,6 @@
 use crate::error::{AppError, FieldError};
+use crate::models::{ApiKeyPage, Scope};
+use chrono::{DateTime, Utc};
 use regex::Regex;
 
 // Validation limits
@@ -11,6 +13,12 @@ pub const USERNAME_MIN: usize = 3;
 pub const USERNAME_MAX: usize = 32;
 pub const DISPLAY_NAME_MAX: usize = 64;
 pub const API_KEY_NAME_MAX: usize = 100;
+pub const RETURN_TO_MAX: usize = 1000;
+pub const TEAM_NAME_MIN: usize = 1;
+pub const TEAM_NAME_MAX: usize = 100;
+pub const ACCESS_REQUEST_MESSAGE_MAX: usize = 500;
+pub const PUBLIC_PASSWORD_MIN: usize = 4;
+pub const PUBLIC_PASSWORD_MAX: usize = 128;
 
 fn trim(s: &str) -> String {
     s.trim().to_string()
@@ -19,7 +27,7 @@ fn trim(s: &str) -> String {
 pub fn validate_title(input: &str) -> Result<String, AppError> {
     let v = trim(input);
     let len = v.chars().count();
-    if len < TITLE_MIN || len > TITLE_MAX {
+    if !(TITLE_MIN..=TITLE_MAX).contains(&len) {
         return Err(AppError::Validation(vec![FieldError {
             field: "title".to_string(),
             message: format!("Title must be between {} and {} characters", TITLE_MIN, TITLE_MAX),
@@ -43,10 +51,33 @@ pub fn validate_description(input: &Option<String>) -> Result<Option<String>, Ap
     Ok(None)
 }
 
+pub fn validate_team_name(input: &str) -> Result<String, AppError> {
+    let v = trim(input);
+    let len = v.chars().count();
+    if !(TEAM_NAME_MIN..=TEAM_NAME_MAX).contains(&len) {
+        return Err(AppError::Validation(vec![FieldError {
+            field: "name".to_string(),
+            message: format!("Team name must be between {} and {} characters", TEAM_NAME_MIN, TEAM_NAME_MAX),
+        }]));
+    }
+    Ok(v)
+}
+
+pub fn validate_access_request_message(input: &Option<String>) -> Result<Option<String>, AppError> {
+    let v = input.as_deref().map(trim).filter(|s| !s.is_empty());
+    if v.as_ref().is_some_and(|s| s.chars().count() > ACCESS_REQUEST_MESSAGE_MAX) {
+        return Err(AppError::Validation(vec![FieldError {
+            field: "message".to_string(),
+            message: format!("Message must be at most {} characters", ACCESS_REQUEST_MESSAGE_MAX),
+        }]));
+    }
+    Ok(v)
+}
+
 pub fn validate_item_content(input: &str) -> Result<String, AppError> {
     let v = trim(input);
     let len = v.chars().count();
-    if len < ITEM_CONTENT_MIN || len > ITEM_CONTENT_MAX {
+    if !(ITEM_CONTENT_MIN..=ITEM_CONTENT_MAX).contains(&len) {
         return Err(AppError::Validation(vec![FieldError {
             field: "content".to_string(),
             message: format!("Item content must be between {} and {} characters", ITEM_CONTENT_MIN, ITEM_CONTENT_MAX),
@@ -58,7 +89,7 @@ pub fn validate_item_content(input: &str) -> Result<String, AppError> {
 pub fn validate_username(input: &str) -> Result<String, AppError> {
     let v = trim(input);
     let len = v.chars().count();
-    if len < USERNAME_MIN || len > USERNAME_MAX {
+    if !(USERNAME_MIN..=USERNAME_MAX).contains(&len) {
         return Err(AppError::Validation(vec![FieldError {
             field: "username".to_string(),
             message: format!("Username must be between {} and {} characters", USERNAME_MIN, USERNAME_MAX),
@@ -89,6 +120,21 @@ pub fn validate_display_name(input: &Option<String>) -> Result<Option<String>, A
     Ok(None)
 }
 
+/// Optional name a visitor signs a suggestion with; blank counts as anonymous.
+pub fn validate_author_name(input: &Option<String>) -> Result<Option<String>, AppError> {
+    let Some(s) = input else {
+        return Ok(None);
+    };
+    let v = trim(s);
+    if v.chars().count() > DISPLAY_NAME_MAX {
+        return Err(AppError::Validation(vec![FieldError {
+            field: "author_name".to_string(),
+            message: format!("Name must be at most {} characters", DISPLAY_NAME_MAX),
+        }]));
+    }
+    Ok((!v.is_empty()).then_some(v))
+}
+
 pub fn validate_public_slug(input: &str) -> Result<String, AppError> {
     let v = trim(input);
     let re = Regex::new(r"^[a-z0-9-]{3,50}$").unwrap();
@@ -101,6 +147,38 @@ pub fn validate_public_slug(input: &str) -> Result<String, AppError> {
     Ok(v)
 }
 
+/// Not trimmed: surrounding spaces are part of a password.
+pub fn validate_public_password(input: &str) -> Result<String, AppError> {
+    let len = input.chars().count();
+    if !(PUBLIC_PASSWORD_MIN..=PUBLIC_PASSWORD_MAX).contains(&len) {
+        return Err(AppError::Validation(vec![FieldError {
+            field: "password".to_string(),
+            message: format!(
+                "Password must be between {} and {} characters",
+                PUBLIC_PASSWORD_MIN, PUBLIC_PASSWORD_MAX
+            ),
+        }]));
+    }
+    Ok(input.to_string())
+}
+
+pub fn validate_return_to(input: &str) -> Result<String, AppError> {
+    let v = trim(input);
+    // Only same-site paths: "//host" and "/\host" would be protocol-relative redirects.
+    let ok = v.starts_with('/')
+        && !v.starts_with("//")
+        && !v.starts_with("/\\")
+        && v.chars().count() <= RETURN_TO_MAX
+        && !v.chars().any(|c| c.is_control());
+    if !ok {
+        return Err(AppError::Validation(vec![FieldError {
+            field: "return_to".to_string(),
+            message: "Return path must be a relative path starting with '/'".to_string(),
+        }]));
+    }
+    Ok(v)
+}
+
 pub fn validate_api_key_name(input: &Option<String>) -> Result<Option<String>, AppError> {
     if let Some(s) = input {
         let v = trim(s);
@@ -116,24 +194,67 @@ pub fn validate_api_key_name(input: &Option<String>) -> Result<Option<String>, A
     Ok(None)
 }
 
-pub fn validate_scopes(scopes: &Vec<String>) -> Result<Vec<String>, AppError> {
+/// Expiry of an API key, public link or permission; none can start out already expired.
+pub fn validate_expiry(
+    input: &Option<DateTime<Utc>>,
+) -> Result<Option<DateTime<Utc>>, AppError> {
+    if let Some(t) = input {
+        if *t <= Utc::now() {
+            return Err(AppError::Validation(vec![FieldError {
+                field: "expires_at".to_string(),
+                message: "Expiry must be in the future".to_string(),
+            }]));
+        }
+    }
+    Ok(*input)
+}
+
+pub fn validate_api_key_pages(
+    input: &Option<Vec<ApiKeyPage>>,
+) -> Result<Vec<ApiKeyPage>, AppError> {
+    let pages = match input {
+        Some(pages) => pages,
+        None => return Ok(Vec::new()),
+    };
+    if pages.is_empty() {
+        return Err(AppError::Validation(vec![FieldError {
+            field: "pages".to_string(),
+            message: "Omit pages for an unrestricted key or list at least one page".to_string(),
+        }]));
+    }
+    let mut out: Vec<ApiKeyPage> = Vec::new();
+    for p in pages.iter() {
+        if out.iter().any(|o| o.page_id == p.page_id) {
+            return Err(AppError::Validation(vec![FieldError {
+                field: "pages".to_string(),
+                message: format!("Duplicate page: {}", p.page_id),
+            }]));
+        }
+        out.push(p.clone());
+    }
+    Ok(out)
+}
+
+pub fn validate_scopes(scopes: &[String]) -> Result<Vec<String>, AppError> {
     if scopes.is_empty() {
         return Err(AppError::Validation(vec![FieldError {
             field: "scopes".to_string(),
             message: "At least one scope must be provided".to_string(),
         }]));
     }
-    let re = Regex::new(r"^[a-z]+$").unwrap();
-    let mut out = Vec::new();
+    let mut out: Vec<String> = Vec::new();
     for s in scopes.iter() {
         let v = trim(s);
-        if !re.is_match(&v) {
+        if Scope::parse(&v).is_none() {
+            let allowed: Vec<&str> = Scope::ALL.iter().map(|s| s.as_str()).collect();
             return Err(AppError::Validation(vec![FieldError {
                 field: "scopes".to_string(),
-                message: format!("Invalid scope: {}", s),
+                message: format!("Invalid scope: {} (allowed: {})", s, allowed.join(", ")),
             }]));
         }
-        out.push(v);
+        if !out.contains(&v) {
+            out.push(v);
+        }
     }
     Ok(out)
 }
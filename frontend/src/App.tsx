@@ -1,4 +1,4 @@
-import { BrowserRouter, Routes, Route, Navigate } from "react-router-dom";
+import { BrowserRouter, Routes, Route, Navigate, useLocation } from "react-router-dom";
 import { QueryClient, QueryClientProvider } from "@tanstack/react-query";
 import { AuthProvider } from "@/contexts/AuthProvider";
 import { useAuth } from "@/hooks/useAuth";
@@ -21,6 +21,9 @@ const PageView = lazy(() =>
 const PublicPageView = lazy(() =>
   import("@/pages/PublicPageView").then((m) => ({ default: m.PublicPageView })),
 );
+const InviteAccept = lazy(() =>
+  import("@/pages/InviteAccept").then((m) => ({ default: m.InviteAccept })),
+);
 const Profile = lazy(() =>
   import("@/pages/Profile").then((m) => ({ default: m.Profile })),
 );
@@ -57,11 +60,16 @@ function PrivateRoute({ children }: { children: React.ReactElement }) {
     user,
   );
 
+  const location = useLocation();
+
   if (isLoading) {
     return <LoadingScreen />;
   }
 
-  return isAuthenticated ? children : <Navigate to="/login" />;
+  const returnTo = encodeURIComponent(location.pathname + location.search);
+  return isAuthenticated ? children : (
+    <Navigate to={`/login?return_to=${returnTo}`} />
+  );
 }
 
 function PublicRoute({ children }: { children: React.ReactElement }) {
@@ -121,6 +129,14 @@ function AppRoutes() {
           </PrivateRoute>
         }
       />
+      <Route
+        path="/invite/:token"
+        element={
+          <PrivateRoute>
+            <InviteAccept />
+          </PrivateRoute>
+        }
+      />
       <Route path="*" element={<Navigate to="/" />} />
     </Routes>
   );
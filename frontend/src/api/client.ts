@@ -2,10 +2,13 @@ import type {
   User,
   Page,
   PageWithPermission,
+  PagePermission,
   PagePermissionWithUser,
+  GrantableRole,
   List,
   ListItem,
   ListWithItems,
+  ListAccess,
   CreatePage,
   UpdatePage,
   CreateList,
@@ -14,11 +17,31 @@ import type {
   UpdateListItem,
   GrantPermission,
   UpdatePermission,
+  PageTransfer,
+  PendingPermission,
+  AccessRequest,
+  AccessRequestWithUser,
+  PageTransferWithPage,
+  PageInvite,
+  CreatePageInvite,
+  CreatedPageInvite,
   SetPublicSlug,
   PublicPageData,
+  PublicListItem,
+  ItemSuggestion,
   ApiKey,
   CreateApiKeyResponse,
+  RotateApiKeyResponse,
+  Session,
   UpdateUser,
+  IdentityProviderInfo,
+  UserIdentity,
+  Team,
+  TeamRole,
+  TeamWithRole,
+  TeamWithMembers,
+  TeamInvitation,
+  TeamInvitationWithTeam,
 } from "../types";
 
 const getApiBaseUrl = (): string => {
@@ -67,9 +90,29 @@ const getApiBaseUrl = (): string => {
 const API_BASE_URL = getApiBaseUrl();
 
 class ApiClient {
+  // Shared so concurrent 401s trigger a single refresh; replaying a rotated
+  // refresh token makes the backend revoke the whole session.
+  private refreshing: Promise<boolean> | null = null;
+
+  private refreshSession(): Promise<boolean> {
+    if (!this.refreshing) {
+      this.refreshing = fetch(`${API_BASE_URL}/auth/refresh`, {
+        method: "POST",
+        credentials: "include",
+      })
+        .then((r) => r.ok)
+        .catch(() => false)
+        .finally(() => {
+          this.refreshing = null;
+        });
+    }
+    return this.refreshing;
+  }
+
   private async request<T>(
     path: string,
     options: RequestInit & { params?: Record<string, string> } = {},
+    retried = false,
   ): Promise<T> {
     const url = new URL(`${API_BASE_URL}${path}`);
     if (options.params) {
@@ -90,6 +133,11 @@ class ApiClient {
       credentials: "include",
     });
 
+    if (response.status === 401 && !retried && (await this.refreshSession())) {
+      // Access token expired but the session is still alive; retry once.
+      return this.request<T>(path, options, true);
+    }
+
     if (response.status === 401) {
       // In cookie-based auth flow we don't store the token client-side; ensure any remnant is removed.
       try {
@@ -112,7 +160,9 @@ class ApiClient {
       if (!isAuthPage && !isPublicPage) {
         window.location.href = "/login";
       }
-      throw new Error("Unauthorized");
+      const e = new Error("Unauthorized") as Error & { status?: number };
+      e.status = 401;
+      throw e;
     }
 
     if (!response.ok) {
@@ -151,7 +201,9 @@ class ApiClient {
         throw e;
       }
 
-      throw new Error(msg);
+      const e = new Error(msg) as Error & { status?: number };
+      e.status = response.status;
+      throw e;
     }
 
     if (response.status === 204) {
@@ -198,8 +250,21 @@ class ApiClient {
   }
 
   // Auth methods
-  getLoginUrl(): string {
-    return `${API_BASE_URL}/auth/login`;
+  getLoginUrl(returnTo?: string | null, provider?: string | null): string {
+    const params = new URLSearchParams();
+    if (provider) params.set("provider", provider);
+    if (returnTo) params.set("return_to", returnTo);
+    const query = params.toString();
+    return query ? `${API_BASE_URL}/auth/login?${query}` : `${API_BASE_URL}/auth/login`;
+  }
+
+  // Starts the provider flow in link mode for the signed-in user
+  getLinkIdentityUrl(provider: string): string {
+    return `${API_BASE_URL}/auth/login?provider=${encodeURIComponent(provider)}&link=true`;
+  }
+
+  async getProviders(): Promise<IdentityProviderInfo[]> {
+    return this.request<IdentityProviderInfo[]>("/auth/providers");
   }
 
   // Users
@@ -220,6 +285,16 @@ class ApiClient {
     });
   }
 
+  async getIdentities(): Promise<UserIdentity[]> {
+    return this.request<UserIdentity[]>(`/users/me/identities`);
+  }
+
+  async unlinkIdentity(id: string): Promise<void> {
+    await this.request(`/users/me/identities/${id}`, {
+      method: "DELETE",
+    });
+  }
+
   // API Keys
   async getApiKeys(): Promise<ApiKey[]> {
     return this.request<ApiKey[]>(`/settings/api-keys`);
@@ -235,6 +310,12 @@ class ApiClient {
     });
   }
 
+  async rotateApiKey(id: string): Promise<RotateApiKeyResponse> {
+    return this.request<RotateApiKeyResponse>(`/settings/api-keys/${id}/rotate`, {
+      method: "POST",
+    });
+  }
+
   async revokeApiKey(id: string): Promise<void> {
     await this.request(`/settings/api-keys/${id}`, {
       method: "DELETE",
@@ -247,11 +328,41 @@ class ApiClient {
     });
   }
 
+  // Sessions
+  async getSessions(): Promise<Session[]> {
+    return this.request<Session[]>("/settings/sessions");
+  }
+
+  async revokeSession(id: string): Promise<void> {
+    await this.request(`/settings/sessions/${id}`, {
+      method: "DELETE",
+    });
+  }
+
+  async revokeOtherSessions(): Promise<void> {
+    await this.request("/settings/sessions", {
+      method: "DELETE",
+    });
+  }
+
   // Pages
   async getPages(): Promise<PageWithPermission[]> {
     return this.request<PageWithPermission[]>("/pages");
   }
 
+  async leavePage(pageId: string): Promise<void> {
+    await this.request(`/pages/${pageId}/membership`, {
+      method: "DELETE",
+    });
+  }
+
+  async setPageHidden(pageId: string, hidden: boolean): Promise<void> {
+    await this.request(`/pages/${pageId}/membership`, {
+      method: "PATCH",
+      body: JSON.stringify({ hidden }),
+    });
+  }
+
   async getPage(pageId: string): Promise<PageWithPermission> {
     return this.request<PageWithPermission>(`/pages/${pageId}`);
   }
@@ -283,11 +394,12 @@ class ApiClient {
     );
   }
 
+  // Granting by login returns a pending permission when that login has no account yet
   async grantPermission(
     pageId: string,
     data: GrantPermission,
-  ): Promise<PagePermissionWithUser> {
-    return this.request<PagePermissionWithUser>(
+  ): Promise<PagePermissionWithUser | PendingPermission> {
+    return this.request<PagePermissionWithUser | PendingPermission>(
       `/pages/${pageId}/permissions`,
       {
         method: "POST",
@@ -316,6 +428,184 @@ class ApiClient {
     });
   }
 
+  async getPendingPermissions(pageId: string): Promise<PendingPermission[]> {
+    return this.request<PendingPermission[]>(
+      `/pages/${pageId}/pending-permissions`,
+    );
+  }
+
+  async revokePendingPermission(pageId: string, pendingId: string): Promise<void> {
+    await this.request(`/pages/${pageId}/pending-permissions/${pendingId}`, {
+      method: "DELETE",
+    });
+  }
+
+  // Access requests
+  async requestAccess(pageId: string, message?: string): Promise<AccessRequest> {
+    return this.request<AccessRequest>(`/pages/${pageId}/access-requests`, {
+      method: "POST",
+      body: JSON.stringify({ message: message || null }),
+    });
+  }
+
+  async getAccessRequests(pageId: string): Promise<AccessRequestWithUser[]> {
+    return this.request<AccessRequestWithUser[]>(
+      `/pages/${pageId}/access-requests`,
+    );
+  }
+
+  async approveAccessRequest(
+    pageId: string,
+    requestId: string,
+    role: GrantableRole,
+  ): Promise<PagePermission> {
+    return this.request<PagePermission>(
+      `/pages/${pageId}/access-requests/${requestId}/approve`,
+      {
+        method: "POST",
+        body: JSON.stringify({ role }),
+      },
+    );
+  }
+
+  async denyAccessRequest(pageId: string, requestId: string): Promise<void> {
+    await this.request(`/pages/${pageId}/access-requests/${requestId}/deny`, {
+      method: "POST",
+    });
+  }
+
+  // Ownership transfer
+  async getPageTransfer(pageId: string): Promise<PageTransfer> {
+    return this.request<PageTransfer>(`/pages/${pageId}/transfer`);
+  }
+
+  async transferPage(pageId: string, userId: string): Promise<PageTransfer> {
+    return this.request<PageTransfer>(`/pages/${pageId}/transfer`, {
+      method: "POST",
+      body: JSON.stringify({ user_id: userId }),
+    });
+  }
+
+  async cancelPageTransfer(pageId: string): Promise<void> {
+    await this.request(`/pages/${pageId}/transfer`, {
+      method: "DELETE",
+    });
+  }
+
+  async acceptPageTransfer(pageId: string): Promise<PageWithPermission> {
+    return this.request<PageWithPermission>(
+      `/pages/${pageId}/transfer/accept`,
+      { method: "POST" },
+    );
+  }
+
+  async getIncomingTransfers(): Promise<PageTransferWithPage[]> {
+    return this.request<PageTransferWithPage[]>("/page-transfers");
+  }
+
+  // Invite links
+  async getPageInvites(pageId: string): Promise<PageInvite[]> {
+    return this.request<PageInvite[]>(`/pages/${pageId}/invites`);
+  }
+
+  async createPageInvite(
+    pageId: string,
+    data: CreatePageInvite,
+  ): Promise<CreatedPageInvite> {
+    return this.request<CreatedPageInvite>(`/pages/${pageId}/invites`, {
+      method: "POST",
+      body: JSON.stringify(data),
+    });
+  }
+
+  async revokePageInvite(pageId: string, inviteId: string): Promise<void> {
+    await this.request(`/pages/${pageId}/invites/${inviteId}`, {
+      method: "DELETE",
+    });
+  }
+
+  async acceptInvite(token: string): Promise<PageWithPermission> {
+    return this.request<PageWithPermission>(
+      `/invites/${encodeURIComponent(token)}/accept`,
+      { method: "POST" },
+    );
+  }
+
+  // Teams
+  async getTeams(): Promise<TeamWithRole[]> {
+    return this.request<TeamWithRole[]>("/teams");
+  }
+
+  async getTeam(teamId: string): Promise<TeamWithMembers> {
+    return this.request<TeamWithMembers>(`/teams/${teamId}`);
+  }
+
+  async createTeam(name: string): Promise<Team> {
+    return this.request<Team>("/teams", {
+      method: "POST",
+      body: JSON.stringify({ name }),
+    });
+  }
+
+  async renameTeam(teamId: string, name: string): Promise<Team> {
+    return this.request<Team>(`/teams/${teamId}`, {
+      method: "PATCH",
+      body: JSON.stringify({ name }),
+    });
+  }
+
+  async deleteTeam(teamId: string): Promise<void> {
+    await this.request(`/teams/${teamId}`, {
+      method: "DELETE",
+    });
+  }
+
+  // The user joins once they accept the invitation
+  async inviteTeamMember(
+    teamId: string,
+    userId: string,
+    role?: Exclude<TeamRole, "owner">,
+  ): Promise<TeamInvitation> {
+    return this.request<TeamInvitation>(`/teams/${teamId}/members`, {
+      method: "POST",
+      body: JSON.stringify({ user_id: userId, role }),
+    });
+  }
+
+  async updateTeamMember(
+    teamId: string,
+    userId: string,
+    role: Exclude<TeamRole, "owner">,
+  ): Promise<void> {
+    await this.request(`/teams/${teamId}/members/${userId}`, {
+      method: "PATCH",
+      body: JSON.stringify({ role }),
+    });
+  }
+
+  async removeTeamMember(teamId: string, userId: string): Promise<void> {
+    await this.request(`/teams/${teamId}/members/${userId}`, {
+      method: "DELETE",
+    });
+  }
+
+  // Admins withdraw an invitation; the invited user declines their own
+  async cancelTeamInvitation(teamId: string, userId: string): Promise<void> {
+    await this.request(`/teams/${teamId}/invitations/${userId}`, {
+      method: "DELETE",
+    });
+  }
+
+  async acceptTeamInvitation(teamId: string): Promise<TeamWithRole> {
+    return this.request<TeamWithRole>(`/teams/${teamId}/invitations/accept`, {
+      method: "POST",
+    });
+  }
+
+  async getTeamInvitations(): Promise<TeamInvitationWithTeam[]> {
+    return this.request<TeamInvitationWithTeam[]>("/team-invitations");
+  }
+
   // Lists
   async getLists(pageId: string): Promise<List[]> {
     return this.request<List[]>(`/pages/${pageId}/lists`);
@@ -349,6 +639,43 @@ class ApiClient {
     });
   }
 
+  async getListAccess(pageId: string, listId: string): Promise<ListAccess> {
+    return this.request<ListAccess>(`/pages/${pageId}/lists/${listId}/access`);
+  }
+
+  async setListAccess(
+    pageId: string,
+    listId: string,
+    data: ListAccess,
+  ): Promise<List> {
+    return this.request<List>(`/pages/${pageId}/lists/${listId}/access`, {
+      method: "PUT",
+      body: JSON.stringify(data),
+    });
+  }
+
+  // Visitor suggestions waiting for review
+  async getSuggestions(pageId: string): Promise<ItemSuggestion[]> {
+    return this.request<ItemSuggestion[]>(`/pages/${pageId}/suggestions`);
+  }
+
+  async approveSuggestion(pageId: string, suggestionId: string): Promise<ListItem> {
+    return this.request<ListItem>(
+      `/pages/${pageId}/suggestions/${suggestionId}/approve`,
+      { method: "POST" },
+    );
+  }
+
+  async rejectSuggestion(
+    pageId: string,
+    suggestionId: string,
+  ): Promise<ItemSuggestion> {
+    return this.request<ItemSuggestion>(
+      `/pages/${pageId}/suggestions/${suggestionId}/reject`,
+      { method: "POST" },
+    );
+  }
+
   // List items
   async getListItems(listId: string): Promise<ListItem[]> {
     return this.request<ListItem[]>(`/lists/${listId}/items`);
@@ -396,6 +723,38 @@ class ApiClient {
   async getPublicPage(slug: string): Promise<PublicPageData> {
     return this.request<PublicPageData>(`/public/${slug}`);
   }
+
+  // Sets a short-lived cookie that lets getPublicPage through
+  async unlockPublicPage(slug: string, password: string): Promise<void> {
+    await this.request(`/public/${slug}/unlock`, {
+      method: "POST",
+      body: JSON.stringify({ password }),
+    });
+  }
+
+  async setPublicItemChecked(
+    slug: string,
+    listId: string,
+    itemId: string,
+    checked: boolean,
+  ): Promise<PublicListItem> {
+    return this.request<PublicListItem>(
+      `/public/${slug}/lists/${listId}/items/${itemId}`,
+      { method: "PATCH", body: JSON.stringify({ checked }) },
+    );
+  }
+
+  async suggestPublicItem(
+    slug: string,
+    listId: string,
+    content: string,
+    authorName?: string,
+  ): Promise<ItemSuggestion> {
+    return this.request<ItemSuggestion>(`/public/${slug}/lists/${listId}/suggestions`, {
+      method: "POST",
+      body: JSON.stringify({ content, author_name: authorName }),
+    });
+  }
 }
 
 export const apiClient = new ApiClient();
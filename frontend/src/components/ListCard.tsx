@@ -38,6 +38,8 @@ import { cn } from '@/lib/utils';
 interface ListCardProps {
   list: List;
   canEdit: boolean;
+  /** whether items can be ticked; defaults to canEdit */
+  canCheck?: boolean;
   onUpdate: (listId: string, data: Partial<UpdateList>) => Promise<void>;
   onDelete: (listId: string) => Promise<void>;
   dragHandleProps?: React.HTMLAttributes<HTMLDivElement>;
@@ -47,7 +49,7 @@ interface ListCardProps {
   onClearField?: (field: string) => (e: React.ChangeEvent<HTMLInputElement | HTMLTextAreaElement | HTMLSelectElement>) => void;
 }
 
-export function ListCard({ list, canEdit, onUpdate, onDelete, dragHandleProps, error, onClearField }: ListCardProps) {
+export function ListCard({ list, canEdit, canCheck = canEdit, onUpdate, onDelete, dragHandleProps, error, onClearField }: ListCardProps) {
   const { t } = useTranslation();
   const queryClient = useQueryClient();
   const [isEditing, setIsEditing] = useState(false);
@@ -271,6 +273,7 @@ export function ListCard({ list, canEdit, onUpdate, onDelete, dragHandleProps, e
         <ListItem
           item={item}
           canEdit={canEdit}
+          canCheck={canCheck}
           showCheckbox={showCheckBoxes}
           onUpdate={handleUpdateItem}
           onDelete={handleDeleteItem}
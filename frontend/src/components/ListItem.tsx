@@ -20,6 +20,8 @@ import { cn } from '@/lib/utils';
 interface ListItemProps {
   item: ListItemType;
   canEdit: boolean;
+  /** checkers may tick items without editing them; defaults to canEdit */
+  canCheck?: boolean;
   showCheckbox?: boolean;
   onUpdate: (itemId: string, data: UpdateListItem) => Promise<void>;
   onDelete: (itemId: string) => Promise<void>;
@@ -27,7 +29,7 @@ interface ListItemProps {
   error?: string | null;
 }
 
-export function ListItem({ item, canEdit, showCheckbox = true, onUpdate, onDelete, dragHandleProps, error }: ListItemProps) {
+export function ListItem({ item, canEdit, canCheck = canEdit, showCheckbox = true, onUpdate, onDelete, dragHandleProps, error }: ListItemProps) {
   const { t } = useTranslation();
   const [isEditing, setIsEditing] = useState(false);
   const [editContent, setEditContent] = useState(item.content);
@@ -35,7 +37,7 @@ export function ListItem({ item, canEdit, showCheckbox = true, onUpdate, onDelet
   const [isDeleteOpen, setIsDeleteOpen] = useState(false);
 
   const handleCheckToggle = async (checked: boolean) => {
-    if (!canEdit) return;
+    if (!canCheck) return;
     await onUpdate(item.id, { checked });
   };
 
@@ -97,7 +99,7 @@ export function ListItem({ item, canEdit, showCheckbox = true, onUpdate, onDelet
           </div>
         )}
 
-        {canEdit && showCheckbox && (
+        {canCheck && showCheckbox && (
           <Checkbox
             checked={item.checked}
             onCheckedChange={handleCheckToggle}
@@ -105,7 +107,7 @@ export function ListItem({ item, canEdit, showCheckbox = true, onUpdate, onDelet
           />
         )}
 
-        {!canEdit && item.checked && (
+        {!canCheck && item.checked && (
           <Check className="size-4 shrink-0 text-accent" />
         )}
 
@@ -0,0 +1,69 @@
+import { useState } from 'react';
+import { useNavigate } from 'react-router-dom';
+import { useMutation } from '@tanstack/react-query';
+import { useTranslation } from 'react-i18next';
+import { apiClient } from '@/api/client';
+import { Button } from '@/components/ui/button';
+import { Textarea } from '@/components/ui/textarea';
+import { Card, CardContent } from '@/components/ui/card';
+import { toast } from 'sonner';
+import { Check, Loader2, Lock } from 'lucide-react';
+
+interface RequestAccessProps {
+  pageId: string;
+}
+
+// Shown instead of a page the user has no access to
+export function RequestAccess({ pageId }: RequestAccessProps) {
+  const { t } = useTranslation();
+  const navigate = useNavigate();
+  const [message, setMessage] = useState('');
+
+  const requestMutation = useMutation({
+    mutationFn: () => apiClient.requestAccess(pageId, message.trim()),
+    onError: () => {
+      toast.error(t('access_request.failed'));
+    },
+  });
+
+  return (
+    <main className="grid min-h-screen place-items-center bg-background px-4">
+      <Card className="w-full max-w-md">
+        <CardContent className="space-y-4 pt-6">
+          <div className="flex items-center gap-2">
+            <Lock className="size-4 text-accent" />
+            <h1 className="text-lg font-semibold">{t('access_request.title')}</h1>
+          </div>
+          {requestMutation.isSuccess ? (
+            <p className="flex items-center gap-2 text-sm text-muted-foreground">
+              <Check className="size-4 text-accent" />
+              {t('access_request.sent')}
+            </p>
+          ) : (
+            <>
+              <p className="text-sm text-muted-foreground">{t('access_request.description')}</p>
+              <Textarea
+                value={message}
+                onChange={(e) => setMessage(e.target.value)}
+                placeholder={t('access_request.message_placeholder')}
+                maxLength={500}
+              />
+              <Button
+                variant="primary"
+                className="w-full"
+                onClick={() => requestMutation.mutate()}
+                disabled={requestMutation.isPending}
+              >
+                {requestMutation.isPending && <Loader2 className="size-4 animate-spin" />}
+                {t('access_request.submit')}
+              </Button>
+            </>
+          )}
+          <Button variant="ghost" className="w-full" onClick={() => navigate('/')}>
+            {t('access_request.back')}
+          </Button>
+        </CardContent>
+      </Card>
+    </main>
+  );
+}
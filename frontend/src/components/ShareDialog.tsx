@@ -1,7 +1,7 @@
 import { useState, useEffect, useMemo } from 'react';
 import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query';
 import { useTranslation } from 'react-i18next';
-import type { Page, User } from '@/types';
+import type { GrantableRole, Page, User } from '@/types';
 import { apiClient } from '@/api/client';
 import {
   Dialog,
@@ -41,6 +41,8 @@ interface ShareDialogProps {
 
 type Tab = 'link' | 'collaborators';
 
+const GRANTABLE_ROLES: GrantableRole[] = ['viewer', 'checker', 'editor', 'manager'];
+
 export function ShareDialog({ page }: ShareDialogProps) {
   const { t } = useTranslation();
   const queryClient = useQueryClient();
@@ -53,6 +55,9 @@ export function ShareDialog({ page }: ShareDialogProps) {
   const [copied, setCopied] = useState(false);
 
   // Collaborators States
+  const [inviteRole, setInviteRole] = useState<GrantableRole>('viewer');
+  const [requestRoles, setRequestRoles] = useState<Record<string, GrantableRole>>({});
+  const [inviteUrl, setInviteUrl] = useState<string | null>(null);
   const [searchQuery, setSearchQuery] = useState('');
   const [searchResults, setSearchResults] = useState<User[]>([]);
   const [isSearching, setIsSearching] = useState(false);
@@ -64,6 +69,24 @@ export function ShareDialog({ page }: ShareDialogProps) {
     enabled: open && activeTab === 'collaborators',
   });
 
+  const { data: accessRequests = [] } = useQuery({
+    queryKey: ['access-requests', page.id],
+    queryFn: () => apiClient.getAccessRequests(page.id),
+    enabled: open && activeTab === 'collaborators',
+  });
+
+  const { data: teams = [] } = useQuery({
+    queryKey: ['teams'],
+    queryFn: () => apiClient.getTeams(),
+    enabled: open && activeTab === 'collaborators',
+  });
+
+  const { data: pendingPermissions = [] } = useQuery({
+    queryKey: ['pending-permissions', page.id],
+    queryFn: () => apiClient.getPendingPermissions(page.id),
+    enabled: open && activeTab === 'collaborators',
+  });
+
   // Mutations
   const setPublicSlugMutation = useMutation({
     mutationFn: (newSlug: string | null) => apiClient.setPublicSlug(page.id, { public_slug: newSlug }),
@@ -90,7 +113,7 @@ export function ShareDialog({ page }: ShareDialogProps) {
   });
 
   const grantPermissionMutation = useMutation({
-    mutationFn: (userId: string) => apiClient.grantPermission(page.id, { user_id: userId, can_edit: true }),
+    mutationFn: (userId: string) => apiClient.grantPermission(page.id, { user_id: userId, role: 'editor' }),
     onSuccess: () => {
       queryClient.invalidateQueries({ queryKey: ['permissions', page.id] });
       setSearchQuery('');
@@ -101,9 +124,67 @@ export function ShareDialog({ page }: ShareDialogProps) {
     },
   });
 
+  const decideRequestMutation = useMutation({
+    mutationFn: ({ requestId, role }: { requestId: string; role: GrantableRole | null }) =>
+      role
+        ? apiClient.approveAccessRequest(page.id, requestId, role)
+        : apiClient.denyAccessRequest(page.id, requestId),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ['access-requests', page.id] });
+      queryClient.invalidateQueries({ queryKey: ['permissions', page.id] });
+    },
+    onError: () => {
+      toast.error(t('share.update_perm_failed'));
+    },
+  });
+
+  const grantTeamMutation = useMutation({
+    mutationFn: (teamId: string) => apiClient.grantPermission(page.id, { team_id: teamId, role: 'editor' }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ['permissions', page.id] });
+    },
+    onError: () => {
+      toast.error(t('share.grant_failed'));
+    },
+  });
+
+  // For people who have never signed in: kept pending until their first login
+  const grantByLoginMutation = useMutation({
+    mutationFn: (login: string) => apiClient.grantPermission(page.id, { login, role: 'editor' }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ['permissions', page.id] });
+      queryClient.invalidateQueries({ queryKey: ['pending-permissions', page.id] });
+      setSearchQuery('');
+      setSearchResults([]);
+    },
+    onError: () => {
+      toast.error(t('share.grant_failed'));
+    },
+  });
+
+  const revokePendingMutation = useMutation({
+    mutationFn: (pendingId: string) => apiClient.revokePendingPermission(page.id, pendingId),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ['pending-permissions', page.id] });
+    },
+    onError: () => {
+      toast.error(t('share.revoke_failed'));
+    },
+  });
+
+  const createInviteMutation = useMutation({
+    mutationFn: (role: GrantableRole) => apiClient.createPageInvite(page.id, { role }),
+    onSuccess: (invite) => {
+      setInviteUrl(`${window.location.origin}/invite/${invite.token}`);
+    },
+    onError: () => {
+      toast.error(t('share.invite_failed'));
+    },
+  });
+
   const updatePermissionMutation = useMutation({
-    mutationFn: ({ permId, canEdit }: { permId: string; canEdit: boolean }) =>
-      apiClient.updatePermission(page.id, permId, { can_edit: canEdit }),
+    mutationFn: ({ permId, role }: { permId: string; role: GrantableRole }) =>
+      apiClient.updatePermission(page.id, permId, { role }),
     onSuccess: () => {
       queryClient.invalidateQueries({ queryKey: ['permissions', page.id] });
     },
@@ -128,12 +209,13 @@ export function ShareDialog({ page }: ShareDialogProps) {
       setLinkError(null);
       setSearchQuery('');
       setSearchResults([]);
+      setInviteUrl(null);
     }
   }, [open, page.public_slug]);
 
   // Memoize existing user IDs to avoid re-runs
   const existingUserIdsKey = useMemo(
-    () => permissions.map((p) => p.user_id).sort().join(','),
+    () => permissions.flatMap((p) => (p.user_id ? [p.user_id] : [])).sort().join(','),
     [permissions]
   );
 
@@ -207,12 +289,22 @@ export function ShareDialog({ page }: ShareDialogProps) {
     }
   };
 
+  const handleCopyInvite = async () => {
+    if (!inviteUrl) return;
+    try {
+      await navigator.clipboard.writeText(inviteUrl);
+      toast.success(t('common.copied'));
+    } catch {
+      toast.error(t('share.copy_failed'));
+    }
+  };
+
   const handleGrant = (userId: string) => {
     grantPermissionMutation.mutate(userId);
   };
 
-  const handleUpdatePerm = (permId: string, canEdit: boolean) => {
-    updatePermissionMutation.mutate({ permId, canEdit });
+  const handleUpdatePerm = (permId: string, role: GrantableRole) => {
+    updatePermissionMutation.mutate({ permId, role });
   };
 
   const handleRevoke = (permId: string) => {
@@ -396,11 +488,143 @@ export function ShareDialog({ page }: ShareDialogProps) {
             )}
 
             {!isSearching && searchQuery.length >= 2 && searchResults.length === 0 && (
-              <p className="py-4 text-center text-sm text-muted-foreground">
-                {t('share.no_users_found')}
-              </p>
+              <div className="space-y-2 py-4 text-center">
+                <p className="text-sm text-muted-foreground">{t('share.no_users_found')}</p>
+                <Button
+                  size="sm"
+                  variant="outline"
+                  onClick={() => grantByLoginMutation.mutate(searchQuery.trim())}
+                  disabled={grantByLoginMutation.isPending}
+                >
+                  <UserPlus className="size-3" />
+                  {t('share.share_with_login', { login: searchQuery.trim().replace(/^@/, '') })}
+                </Button>
+              </div>
+            )}
+
+            {/* Access Requests */}
+            {accessRequests.length > 0 && (
+              <div className="space-y-2">
+                <Label className="text-sm text-muted-foreground">{t('share.access_requests')}</Label>
+                <div className="max-h-[200px] space-y-1 overflow-y-auto">
+                  {accessRequests.map((request) => (
+                    <div key={request.id} className="space-y-2 rounded-md border border-border p-2">
+                      <div className="flex flex-col">
+                        <span className="text-sm font-medium">
+                          {request.user.display_name || request.user.username}
+                        </span>
+                        {request.message && (
+                          <span className="text-xs text-muted-foreground">{request.message}</span>
+                        )}
+                      </div>
+                      <div className="flex gap-1">
+                        <select
+                          aria-label={t('share.role')}
+                          value={requestRoles[request.id] ?? 'viewer'}
+                          onChange={(e) =>
+                            setRequestRoles((prev) => ({ ...prev, [request.id]: e.target.value as GrantableRole }))
+                          }
+                          className="h-8 appearance-none rounded-md border border-input-border bg-surface px-2 text-sm text-foreground focus-visible:outline-none focus-visible:border-accent"
+                        >
+                          {GRANTABLE_ROLES.map((role) => (
+                            <option key={role} value={role}>
+                              {t(`share.role_${role}`)}
+                            </option>
+                          ))}
+                        </select>
+                        <Button
+                          size="sm"
+                          variant="primary"
+                          onClick={() =>
+                            decideRequestMutation.mutate({
+                              requestId: request.id,
+                              role: requestRoles[request.id] ?? 'viewer',
+                            })
+                          }
+                          disabled={decideRequestMutation.isPending}
+                        >
+                          <Check className="size-3" />
+                          {t('share.approve_request')}
+                        </Button>
+                        <Button
+                          size="sm"
+                          variant="ghost-destructive"
+                          onClick={() => decideRequestMutation.mutate({ requestId: request.id, role: null })}
+                          disabled={decideRequestMutation.isPending}
+                        >
+                          <X className="size-3" />
+                          {t('share.deny_request')}
+                        </Button>
+                      </div>
+                    </div>
+                  ))}
+                </div>
+              </div>
+            )}
+
+            {/* Teams */}
+            {teams.some((team) => !permissions.some((p) => p.team_id === team.id)) && (
+              <div className="space-y-2">
+                <Label className="text-sm text-muted-foreground">{t('share.teams')}</Label>
+                <div className="flex flex-wrap gap-2">
+                  {teams
+                    .filter((team) => !permissions.some((p) => p.team_id === team.id))
+                    .map((team) => (
+                      <Button
+                        key={team.id}
+                        size="sm"
+                        variant="outline"
+                        onClick={() => grantTeamMutation.mutate(team.id)}
+                        disabled={grantTeamMutation.isPending}
+                      >
+                        <Users className="size-3" />
+                        {t('share.team_name', { name: team.name })}
+                      </Button>
+                    ))}
+                </div>
+              </div>
             )}
 
+            {/* Invite Link */}
+            <div className="space-y-2">
+              <Label className="text-sm text-muted-foreground">{t('share.invite_link')}</Label>
+              <div className="flex gap-2">
+                <select
+                  aria-label={t('share.role')}
+                  value={inviteRole}
+                  onChange={(e) => setInviteRole(e.target.value as GrantableRole)}
+                  className="h-9 appearance-none rounded-md border border-input-border bg-surface px-2 text-sm text-foreground focus-visible:outline-none focus-visible:border-accent"
+                >
+                  {GRANTABLE_ROLES.map((role) => (
+                    <option key={role} value={role}>
+                      {t(`share.role_${role}`)}
+                    </option>
+                  ))}
+                </select>
+                <Button
+                  variant="outline"
+                  className="flex-1"
+                  onClick={() => createInviteMutation.mutate(inviteRole)}
+                  disabled={createInviteMutation.isPending}
+                >
+                  {createInviteMutation.isPending ? (
+                    <Loader2 className="size-4 animate-spin" />
+                  ) : (
+                    <Link className="size-4" />
+                  )}
+                  {t('share.create_invite')}
+                </Button>
+              </div>
+              {inviteUrl && (
+                <div className="flex gap-2">
+                  <Input value={inviteUrl} readOnly className="font-mono text-[13px]" />
+                  <Button size="icon" variant="outline" onClick={handleCopyInvite}>
+                    <Copy className="size-4" />
+                  </Button>
+                </div>
+              )}
+            </div>
+
             {/* Current Collaborators */}
             <div className="space-y-2">
               <Label className="text-sm text-muted-foreground">{t('share.current_collaborators')}</Label>
@@ -408,7 +632,7 @@ export function ShareDialog({ page }: ShareDialogProps) {
                 <div className="flex items-center justify-center py-4">
                   <Loader2 className="size-5 animate-spin text-accent" />
                 </div>
-              ) : permissions.length === 0 ? (
+              ) : permissions.length === 0 && pendingPermissions.length === 0 ? (
                 <p className="py-4 text-center text-sm text-muted-foreground">
                   {t('share.no_collaborators')}
                 </p>
@@ -420,7 +644,7 @@ export function ShareDialog({ page }: ShareDialogProps) {
                       className="flex items-center justify-between rounded-md bg-subtle p-2"
                     >
                       <div className="flex items-center gap-2">
-                        {perm.user.profile_image_url ? (
+                        {perm.user?.profile_image_url ? (
                           <img
                             src={perm.user.profile_image_url}
                             alt={perm.user.username}
@@ -428,39 +652,50 @@ export function ShareDialog({ page }: ShareDialogProps) {
                           />
                         ) : (
                           <div className="flex h-8 w-8 items-center justify-center rounded-full bg-accent-subtle">
-                            <span className="text-xs font-medium text-accent">
-                              {avatarFallback(perm.user.display_name, perm.user.username)}
-                            </span>
+                            {perm.team ? (
+                              <Users className="size-4 text-accent" />
+                            ) : (
+                              <span className="text-xs font-medium text-accent">
+                                {avatarFallback(perm.user?.display_name, perm.user?.username)}
+                              </span>
+                            )}
                           </div>
                         )}
                         <div className="flex flex-col">
                           <span className="text-sm font-medium">
-                            {perm.user.display_name || perm.user.username}
+                            {perm.team
+                              ? t('share.team_name', { name: perm.team.name })
+                              : perm.user?.display_name || perm.user?.username}
                           </span>
                           <div className="flex items-center gap-1">
-                            {perm.can_edit ? (
-                              <Badge variant="editor" className="text-xs gap-1">
-                                <ShieldCheck className="size-3" />
-                                {t('share.can_edit')}
-                              </Badge>
-                            ) : (
+                            {perm.role === 'viewer' ? (
                               <Badge variant="viewer" className="text-xs gap-1">
                                 <Shield className="size-3" />
-                                {t('share.can_view')}
+                                {t('share.role_viewer')}
+                              </Badge>
+                            ) : (
+                              <Badge variant="editor" className="text-xs gap-1">
+                                <ShieldCheck className="size-3" />
+                                {t(`share.role_${perm.role}`)}
                               </Badge>
                             )}
                           </div>
                         </div>
                       </div>
                       <div className="flex gap-1">
-                        <Button
-                          size="sm"
-                          variant="ghost"
-                          onClick={() => handleUpdatePerm(perm.id, !perm.can_edit)}
+                        <select
+                          aria-label={t('share.role')}
+                          value={perm.role}
+                          onChange={(e) => handleUpdatePerm(perm.id, e.target.value as GrantableRole)}
                           disabled={updatePermissionMutation.isPending}
+                          className="h-8 appearance-none rounded-md border border-input-border bg-surface px-2 text-sm text-foreground focus-visible:outline-none focus-visible:border-accent"
                         >
-                          {perm.can_edit ? t('share.can_view') : t('share.can_edit')}
-                        </Button>
+                          {GRANTABLE_ROLES.map((role) => (
+                            <option key={role} value={role}>
+                              {t(`share.role_${role}`)}
+                            </option>
+                          ))}
+                        </select>
                         <Button
                           size="icon-dense"
                           variant="ghost-destructive"
@@ -473,6 +708,28 @@ export function ShareDialog({ page }: ShareDialogProps) {
                       </div>
                     </div>
                   ))}
+                  {pendingPermissions.map((pending) => (
+                    <div
+                      key={pending.id}
+                      className="flex items-center justify-between rounded-md border border-dashed border-border p-2"
+                    >
+                      <div className="flex flex-col">
+                        <span className="text-sm font-medium">@{pending.login}</span>
+                        <span className="text-xs text-muted-foreground">
+                          {t('share.pending_signup')} · {t(`share.role_${pending.role}`)}
+                        </span>
+                      </div>
+                      <Button
+                        size="icon-dense"
+                        variant="ghost-destructive"
+                        onClick={() => revokePendingMutation.mutate(pending.id)}
+                        disabled={revokePendingMutation.isPending}
+                        aria-label={t('share.revoke_access')}
+                      >
+                        <Trash2 className="size-4" />
+                      </Button>
+                    </div>
+                  ))}
                 </div>
               )}
             </div>
@@ -5,6 +5,14 @@ import { useTranslation } from "react-i18next";
 import { useAuth } from "@/hooks/useAuth";
 import { Loader2 } from "lucide-react";
 
+// Only same-site paths are followed after login
+function safeReturnTo(value: string | null): string {
+  if (!value || !value.startsWith("/") || value.startsWith("//") || value.startsWith("/\\")) {
+    return "/";
+  }
+  return value;
+}
+
 export function AuthCallback() {
   const { t } = useTranslation();
   const navigate = useNavigate();
@@ -22,8 +30,9 @@ export function AuthCallback() {
         );
         console.debug("[AuthCallback] calling login()");
         await login();
-        console.debug("[AuthCallback] login() succeeded — navigating to /");
-        navigate("/", { replace: true });
+        const returnTo = safeReturnTo(searchParams.get("return_to"));
+        console.debug("[AuthCallback] login() succeeded — navigating to", returnTo);
+        navigate(returnTo, { replace: true });
       } catch (e) {
         console.error("[AuthCallback] login() failed:", e);
         navigate("/login", { replace: true });
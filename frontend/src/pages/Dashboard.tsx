@@ -14,7 +14,7 @@ const DeleteConfirmDialog = lazy(
   () => import("@/components/DeleteConfirmDialog"),
 );
 import { toast } from "sonner";
-import { Plus, Trash2, Loader2, ListTodo } from "lucide-react";
+import { Plus, Trash2, Loader2, ListTodo, EyeOff, LogOut } from "lucide-react";
 
 export function Dashboard() {
   const { t, i18n } = useTranslation();
@@ -51,6 +51,26 @@ export function Dashboard() {
     },
   });
 
+  const hidePageMutation = useMutation({
+    mutationFn: (pageId: string) => apiClient.setPageHidden(pageId, true),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["pages"] });
+    },
+    onError: () => {
+      toast.error(t("dashboard.hide_error"));
+    },
+  });
+
+  const leavePageMutation = useMutation({
+    mutationFn: (pageId: string) => apiClient.leavePage(pageId),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["pages"] });
+    },
+    onError: () => {
+      toast.error(t("dashboard.leave_error"));
+    },
+  });
+
   const [isCreateOpen, setIsCreateOpen] = useState(false);
   const [newPageTitle, setNewPageTitle] = useState("");
   const [newPageDesc, setNewPageDesc] = useState("");
@@ -202,6 +222,32 @@ export function Dashboard() {
                               i18n.language,
                             )}
                           </span>
+                          <div className="flex gap-1 opacity-0 transition-opacity focus-within:opacity-100 group-hover:opacity-100 [@media(hover:none)]:opacity-100">
+                            <Button
+                              variant="ghost"
+                              size="icon-dense"
+                              aria-label={t("dashboard.hide")}
+                              title={t("dashboard.hide")}
+                              onClick={(e) => {
+                                e.stopPropagation();
+                                hidePageMutation.mutate(page.id);
+                              }}
+                            >
+                              <EyeOff className="size-4" />
+                            </Button>
+                            <Button
+                              variant="ghost-destructive"
+                              size="icon-dense"
+                              aria-label={t("dashboard.leave")}
+                              title={t("dashboard.leave")}
+                              onClick={(e) => {
+                                e.stopPropagation();
+                                leavePageMutation.mutate(page.id);
+                              }}
+                            >
+                              <LogOut className="size-4" />
+                            </Button>
+                          </div>
                         </div>
                       </div>
                     </div>
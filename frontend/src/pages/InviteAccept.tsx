@@ -0,0 +1,52 @@
+import { useEffect, useRef, useState } from "react";
+import { Link, useNavigate, useParams } from "react-router-dom";
+import { useTranslation } from "react-i18next";
+import { useQueryClient } from "@tanstack/react-query";
+import { apiClient } from "@/api/client";
+import { Loader2 } from "lucide-react";
+
+export function InviteAccept() {
+  const { t } = useTranslation();
+  const navigate = useNavigate();
+  const queryClient = useQueryClient();
+  const { token } = useParams<{ token: string }>();
+  const [failed, setFailed] = useState(false);
+  // Guard against the effect running twice in StrictMode and using up the invite
+  const started = useRef(false);
+
+  useEffect(() => {
+    if (!token || started.current) return;
+    started.current = true;
+    (async () => {
+      try {
+        const page = await apiClient.acceptInvite(token);
+        queryClient.invalidateQueries({ queryKey: ["pages"] });
+        navigate(`/pages/${page.id}`, { replace: true });
+      } catch (e) {
+        console.error("[InviteAccept] acceptInvite failed:", e);
+        setFailed(true);
+      }
+    })();
+  }, [token, navigate, queryClient]);
+
+  return (
+    <main className="grid min-h-screen place-items-center px-4">
+      <div className="space-y-3 text-center">
+        {failed ? (
+          <>
+            <p className="text-sm font-medium">{t("invite.failed_title")}</p>
+            <p className="text-xs text-muted-foreground">{t("invite.failed_desc")}</p>
+            <Link to="/" className="text-sm text-accent hover:underline">
+              {t("invite.back_home")}
+            </Link>
+          </>
+        ) : (
+          <>
+            <Loader2 className="mx-auto size-6 animate-spin text-accent" />
+            <p className="text-sm font-medium">{t("invite.accepting")}</p>
+          </>
+        )}
+      </div>
+    </main>
+  );
+}
@@ -1,15 +1,27 @@
+import { useEffect, useState } from 'react';
 import { useTranslation } from 'react-i18next';
+import { useSearchParams } from 'react-router-dom';
 import { apiClient } from '@/api/client';
 import { Button } from '@/components/ui/button';
+import type { IdentityProviderInfo } from '@/types';
 import { Check, ListTodo } from 'lucide-react';
 
 export function Login() {
   const { t } = useTranslation();
+  const [searchParams] = useSearchParams();
+  const [providers, setProviders] = useState<IdentityProviderInfo[]>([]);
 
-  const handleLogin = () => {
-    window.location.href = apiClient.getLoginUrl();
+  useEffect(() => {
+    apiClient.getProviders().then(setProviders).catch(() => setProviders([]));
+  }, []);
+
+  const handleLogin = (provider?: string) => {
+    window.location.href = apiClient.getLoginUrl(searchParams.get('return_to'), provider);
   };
 
+  // Twitch has its own branded button; anything else configured gets a plain one
+  const otherProviders = providers.filter((p) => p.id !== 'twitch');
+
   return (
     <main className="grid min-h-screen place-items-center bg-background px-4 py-10">
       <div className="w-full max-w-[400px] rounded-lg border border-border bg-surface p-6">
@@ -47,7 +59,7 @@ export function Login() {
             className="mt-6 w-full gap-3"
             size="lg"
             variant="twitch"
-            onClick={handleLogin}
+            onClick={() => handleLogin('twitch')}
           >
             <svg className="size-5" viewBox="0 0 24 24" fill="currentColor">
               <path d="M11.571 4.714h1.715v5.143H11.57zm4.715 0H18v5.143h-1.714zM6 0L1.714 4.286v15.428h5.143V24l4.286-4.286h3.428L22.286 12V0zm14.571 11.143l-3.428 3.428h-3.429l-3 3v-3H6.857V1.714h13.714Z" />
@@ -55,6 +67,18 @@ export function Login() {
             {t('login.twitch_login')}
           </Button>
 
+          {otherProviders.map((provider) => (
+            <Button
+              key={provider.id}
+              className="mt-2 w-full"
+              size="lg"
+              variant="outline"
+              onClick={() => handleLogin(provider.id)}
+            >
+              {t('login.provider_login', { name: provider.name })}
+            </Button>
+          ))}
+
           <div className="mt-3 space-y-1 text-center">
             <p className="text-xs text-muted-foreground">
               {t('login.twitch_disclaimer')}
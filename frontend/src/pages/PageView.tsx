@@ -7,6 +7,7 @@ import { apiClient } from '@/api/client';
 import useServerErrors from '@/hooks/useServerErrors';
 import { ListCard } from '@/components/ListCard';
 import { ShareDialog } from '@/components/ShareDialog';
+import { RequestAccess } from '@/components/RequestAccess';
 import { UserMenu } from '@/components/UserMenu';
 import { Button } from '@/components/ui/button';
 import { Input } from '@/components/ui/input';
@@ -65,13 +66,16 @@ export function PageView() {
     }
   }, [page]);
 
+  // No access: offer to ask for it instead of bouncing back to the dashboard
+  const isForbidden = (pageError as (Error & { status?: number }) | null)?.status === 403;
+
   useEffect(() => {
-    if (pageError) {
+    if (pageError && !isForbidden) {
       console.error('Failed to load page:', pageError);
       toast.error(t('page.load_error'));
       navigate('/');
     }
-  }, [pageError, navigate, t]);
+  }, [pageError, isForbidden, navigate, t]);
 
   // Watch global pages list
   const { data: pages = [], isFetched: pagesFetched } = useQuery<PageWithPermission[]>({
@@ -231,6 +235,7 @@ export function PageView() {
         <ListCard
           list={list}
           canEdit={page!.can_edit}
+          canCheck={page!.can_edit || page!.role === 'checker'}
           onUpdate={handleUpdateList}
           onDelete={handleDeleteList}
           dragHandleProps={listeners}
@@ -302,6 +307,10 @@ export function PageView() {
     }
   };
 
+  if (isForbidden && pageId) {
+    return <RequestAccess pageId={pageId} />;
+  }
+
   if (!page) {
     return (
       <div className="flex items-center justify-center h-screen bg-background">
@@ -354,8 +363,12 @@ export function PageView() {
               )}
               {page.is_creator ? (
                 <Badge variant="creator">{t('page.role_creator')}</Badge>
+              ) : page.role === 'manager' ? (
+                <Badge variant="editor">{t('share.role_manager')}</Badge>
               ) : page.can_edit ? (
                 <Badge variant="editor">{t('page.role_editor')}</Badge>
+              ) : page.role === 'checker' ? (
+                <Badge variant="viewer">{t('share.role_checker')}</Badge>
               ) : (
                 <Badge variant="viewer">{t('page.role_viewer')}</Badge>
               )}
@@ -394,7 +407,7 @@ export function PageView() {
             )}
           </div>
           <div className="flex items-center gap-2 shrink-0">
-            {page.is_creator && <ShareDialog page={page} />}
+            {(page.role === 'owner' || page.role === 'manager') && <ShareDialog page={page} />}
             {page.is_creator && (
               <AlertDialog>
                 <AlertDialogTrigger asChild>
@@ -448,11 +448,22 @@ export function Profile() {
                       </div>
                       <p className="text-xs text-muted-foreground mt-0.5">
                         {t('profile.created', { date: new Date(k.created_at).toLocaleString() })}
+                        {' · '}
+                        {k.last_used_at
+                          ? t('profile.last_used', {
+                              date: new Date(k.last_used_at * 1000).toLocaleString(),
+                              ip: k.last_used_ip || '—',
+                            })
+                          : t('profile.never_used')}
                       </p>
                     </div>
                     <div className="flex items-center gap-2 shrink-0">
-                      <Badge variant={k.revoked ? 'revoked' : 'active'}>
-                        {k.revoked ? t('profile.revoked', 'Revoked') : t('profile.active', 'Active')}
+                      <Badge variant={k.revoked || k.expired ? 'revoked' : 'active'}>
+                        {k.revoked
+                          ? t('profile.revoked', 'Revoked')
+                          : k.expired
+                            ? t('profile.expired')
+                            : t('profile.active', 'Active')}
                       </Badge>
                       {!k.revoked && (
                         <Button variant="outline" size="sm" onClick={() => setRevokeId(k.id)}>
@@ -1,29 +1,54 @@
-import { useState } from 'react';
-import { useParams, Link } from 'react-router-dom';
-import { useQuery } from '@tanstack/react-query';
+import { useEffect, useState } from 'react';
+import { useParams, Link, useNavigate } from 'react-router-dom';
+import { useMutation, useQuery } from '@tanstack/react-query';
 import { useTranslation } from 'react-i18next';
-import type { ListWithItems, ListItem, PublicPageData } from '@/types';
+import type { PublicList, PublicListItem, PublicPageData } from '@/types';
 import { apiClient } from '@/api/client';
 import { cn } from '@/lib/utils';
 import { Card, CardContent, CardHeader, CardTitle } from '@/components/ui/card';
 import { Badge } from '@/components/ui/badge';
 import { Checkbox } from '@/components/ui/checkbox';
 import { Button } from '@/components/ui/button';
+import { Input } from '@/components/ui/input';
 import { toast } from 'sonner';
-import { ArrowLeft, AlertCircle, Copy, Check, ListTodo, Globe } from 'lucide-react';
+import { ArrowLeft, AlertCircle, Copy, Check, ListTodo, Globe, Lock, Plus } from 'lucide-react';
 
 export function PublicPageView() {
   const { t } = useTranslation();
   const { slug } = useParams<{ slug: string }>();
+  const navigate = useNavigate();
   const [copied, setCopied] = useState(false);
 
-  const { data, isLoading, error } = useQuery<PublicPageData>({
+  const [password, setPassword] = useState('');
+
+  const { data, isLoading, error, refetch } = useQuery<PublicPageData>({
     queryKey: ['public-page', slug],
     queryFn: () => apiClient.getPublicPage(slug!),
     enabled: !!slug,
     retry: false,
   });
 
+  const unlockMutation = useMutation({
+    mutationFn: () => apiClient.unlockPublicPage(slug!, password),
+    onSuccess: () => {
+      setPassword('');
+      refetch();
+    },
+    onError: () => {
+      toast.error(t('public.wrong_password'));
+    },
+  });
+
+  // Old slugs are redirected by the API; show the current one in the address bar
+  const currentSlug = data?.page.public_slug;
+  useEffect(() => {
+    if (currentSlug && currentSlug !== slug) {
+      navigate(`/p/${currentSlug}`, { replace: true });
+    }
+  }, [currentSlug, slug, navigate]);
+
+  const errorStatus = (error as (Error & { status?: number }) | null)?.status;
+
   const handleCopyLink = async () => {
     const url = window.location.href;
     try {
@@ -61,15 +86,46 @@ export function PublicPageView() {
     );
   }
 
+  if (errorStatus === 401) {
+    return (
+      <div className="min-h-screen bg-background flex items-center justify-center px-4 py-12">
+        <form
+          className="w-full max-w-xs space-y-3 text-center"
+          onSubmit={(e) => {
+            e.preventDefault();
+            if (password) unlockMutation.mutate();
+          }}
+        >
+          <Lock className="mx-auto size-8 text-muted-foreground" />
+          <h2 className="text-base font-semibold">{t('public.password_required')}</h2>
+          <Input
+            type="password"
+            autoFocus
+            value={password}
+            onChange={(e) => setPassword(e.target.value)}
+            placeholder={t('public.password_placeholder')}
+          />
+          <Button type="submit" variant="primary" className="w-full" disabled={!password || unlockMutation.isPending}>
+            {t('public.unlock')}
+          </Button>
+        </form>
+      </div>
+    );
+  }
+
   if (error) {
     return (
       <div className="min-h-screen bg-background flex items-center justify-center px-4 py-12">
         <div className="text-center space-y-3">
           <AlertCircle className="mx-auto size-8 text-muted-foreground" />
           <h2 className="text-base font-semibold">
-            {error instanceof Error ? error.message : t('public.not_found')}
+            {errorStatus === 410
+              ? t('public.expired')
+              : error instanceof Error ? error.message : t('public.not_found')}
           </h2>
-          <p className="text-sm text-secondary-foreground">{t('public.not_found_desc')}</p>
+          <p className="text-sm text-secondary-foreground">
+            {errorStatus === 410 ? t('public.expired_desc') : t('public.not_found_desc')}
+          </p>
           <Button asChild variant="outline">
             <Link to="/login">
               <ArrowLeft className="size-4" />
@@ -83,6 +139,8 @@ export function PublicPageView() {
 
   if (!data) return null;
 
+  const readOnly = !data.lists.some((l) => l.public_checking || l.public_suggestions);
+
   return (
     <div className="min-h-screen bg-background">
       {/* Header Section */}
@@ -93,8 +151,12 @@ export function PublicPageView() {
               <div className="flex items-center gap-2 text-xs text-muted-foreground">
                 <Globe className="size-3" />
                 <span>{t('public.public_page', 'Public page')}</span>
-                <span>·</span>
-                <span>{t('public.read_only', 'Read only')}</span>
+                {readOnly && (
+                  <>
+                    <span>·</span>
+                    <span>{t('public.read_only', 'Read only')}</span>
+                  </>
+                )}
               </div>
               <h1 className="mt-1 truncate text-xl font-semibold tracking-[-0.01em]">
                 {data.page.title}
@@ -139,7 +201,12 @@ export function PublicPageView() {
         ) : (
           <div className="flex items-start gap-3 overflow-x-auto">
             {data.lists.map((listWithItems) => (
-              <ListCard key={listWithItems.id} list={listWithItems} />
+              <ListCard
+                key={listWithItems.id}
+                slug={slug!}
+                list={listWithItems}
+                onChanged={() => refetch()}
+              />
             ))}
           </div>
         )}
@@ -149,13 +216,44 @@ export function PublicPageView() {
 }
 
 interface ListCardProps {
-  list: ListWithItems;
+  slug: string;
+  list: PublicList;
+  onChanged: () => void;
+}
+
+// Too many anonymous writes from this visitor get a 429
+function publicErrorMessage(t: (key: string) => string, error: unknown, fallback: string) {
+  return (error as { status?: number } | null)?.status === 429
+    ? t('public.rate_limited')
+    : t(fallback);
 }
 
-function ListCard({ list }: ListCardProps) {
+function ListCard({ slug, list, onChanged }: ListCardProps) {
   const { t } = useTranslation();
-  const completedCount = list.items.filter((item: ListItem) => item.checked).length;
-  const totalCount = list.items.length;
+  const [suggestion, setSuggestion] = useState('');
+
+  const checkMutation = useMutation({
+    mutationFn: ({ itemId, checked }: { itemId: string; checked: boolean }) =>
+      apiClient.setPublicItemChecked(slug, list.id, itemId, checked),
+    onSuccess: onChanged,
+    onError: (error) => {
+      toast.error(publicErrorMessage(t, error, 'public.check_error'));
+    },
+  });
+
+  const suggestMutation = useMutation({
+    mutationFn: () => apiClient.suggestPublicItem(slug, list.id, suggestion.trim()),
+    onSuccess: () => {
+      setSuggestion('');
+      toast.success(t('public.suggest_sent'));
+    },
+    onError: (error) => {
+      toast.error(publicErrorMessage(t, error, 'public.suggest_error'));
+    },
+  });
+
+  const completedCount = list.progress?.checked ?? 0;
+  const totalCount = list.progress?.total ?? list.items.length;
   const completionPercent = totalCount > 0 ? (completedCount / totalCount) * 100 : 0;
 
   return (
@@ -200,13 +298,20 @@ function ListCard({ list }: ListCardProps) {
           </p>
         ) : (
           <ul className="space-y-0.5">
-            {list.items.map((item: ListItem) => (
+            {list.items.map((item: PublicListItem) => (
               <li
                 key={item.id}
                 className="flex min-h-9 items-center gap-2 rounded-md px-1.5 py-1"
               >
                 {list.show_checkboxes && (
-                  <Checkbox checked={item.checked} disabled aria-label={item.content} />
+                  <Checkbox
+                    checked={item.checked ?? false}
+                    disabled={!list.public_checking || checkMutation.isPending}
+                    onCheckedChange={(checked) =>
+                      checkMutation.mutate({ itemId: item.id, checked: checked === true })
+                    }
+                    aria-label={item.content}
+                  />
                 )}
                 <span
                   className={cn(
@@ -221,6 +326,31 @@ function ListCard({ list }: ListCardProps) {
             ))}
           </ul>
         )}
+        {list.public_suggestions && (
+          <form
+            className="mt-1 flex items-center gap-1.5 px-1.5 py-1"
+            onSubmit={(e) => {
+              e.preventDefault();
+              if (suggestion.trim()) suggestMutation.mutate();
+            }}
+          >
+            <Input
+              value={suggestion}
+              onChange={(e) => setSuggestion(e.target.value)}
+              placeholder={t('public.suggest_placeholder')}
+              maxLength={2000}
+            />
+            <Button
+              type="submit"
+              variant="ghost"
+              size="sm"
+              disabled={!suggestion.trim() || suggestMutation.isPending}
+              aria-label={t('public.suggest')}
+            >
+              <Plus className="size-4" />
+            </Button>
+          </form>
+        )}
       </CardContent>
     </Card>
   );
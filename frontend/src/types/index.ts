@@ -1,6 +1,7 @@
 export interface User {
   id: string;
-  twitch_id: string;
+  // Null for accounts without a linked Twitch login
+  twitch_id: string | null;
   username: string;
   display_name?: string;
   profile_image_url?: string;
@@ -17,26 +18,42 @@ export interface Page {
   public_slug?: string;
   created_at: string;
   updated_at: string;
+  public_password_protected: boolean;
+  public_expires_at: string | null;
 }
 
+// Each role includes everything the previous one allows; "owner" is the page creator
+export type PageRole = "viewer" | "checker" | "editor" | "manager" | "owner";
+
+export type GrantableRole = Exclude<PageRole, "owner">;
+
 export interface PageWithPermission extends Page {
   is_creator: boolean;
   can_edit: boolean;
+  role: PageRole;
+  hidden: boolean;
 }
 
+// Granted to exactly one of a user or a team
 export interface PagePermission {
   id: string;
   page_id: string;
-  user_id: string;
-  can_edit: boolean;
+  user_id: string | null;
+  team_id: string | null;
+  role: GrantableRole;
   granted_by: string;
   created_at: string;
+  expires_at: string | null;
 }
 
 export interface PagePermissionWithUser extends PagePermission {
-  user: User;
+  user: User | null;
+  team: Team | null;
 }
 
+// "page" follows the page and its public link; "private" is owner-only
+export type ListVisibility = "page" | "restricted" | "private";
+
 export interface List {
   id: string;
   page_id: string;
@@ -46,6 +63,18 @@ export interface List {
   show_progress: boolean;
   created_at: string;
   updated_at: string;
+  visibility: ListVisibility;
+  min_role: PageRole | null;
+  /** Visitors of the public link may tick items. */
+  public_checking: boolean;
+  /** Visitors of the public link may suggest items. */
+  public_suggestions: boolean;
+}
+
+export interface ListAccess {
+  visibility: ListVisibility;
+  min_role: PageRole | null;
+  user_ids: string[];
 }
 
 export interface ListItem {
@@ -84,6 +113,8 @@ export interface UpdateList {
   position?: number;
   show_checkboxes?: boolean;
   show_progress?: boolean;
+  public_checking?: boolean;
+  public_suggestions?: boolean;
 }
 
 export interface CreateListItem {
@@ -97,13 +128,84 @@ export interface UpdateListItem {
   position?: number;
 }
 
+// An existing user, a team, or a provider login (Twitch by default) that
+// may not have signed in yet
 export interface GrantPermission {
-  user_id: string;
-  can_edit: boolean;
+  user_id?: string;
+  team_id?: string;
+  login?: string;
+  provider?: string;
+  role: GrantableRole;
+  expires_at?: string;
+}
+
+export interface PendingPermission {
+  id: string;
+  page_id: string;
+  provider: string;
+  login: string;
+  role: GrantableRole;
+  granted_by: string;
+  created_at: string;
+  expires_at?: string | null;
 }
 
 export interface UpdatePermission {
-  can_edit: boolean;
+  role: GrantableRole;
+  /** Omit to keep the current expiry, `null` to make it permanent. */
+  expires_at?: string | null;
+}
+
+export type AccessRequestStatus = "pending" | "approved" | "denied";
+
+export interface AccessRequest {
+  id: string;
+  page_id: string;
+  user_id: string;
+  message: string | null;
+  status: AccessRequestStatus;
+  role: GrantableRole | null;
+  decided_by: string | null;
+  decided_at: string | null;
+  created_at: string;
+}
+
+export interface AccessRequestWithUser extends AccessRequest {
+  user: User;
+}
+
+export interface PageTransfer {
+  id: string;
+  page_id: string;
+  from_user_id: string;
+  to_user_id: string;
+  created_at: string;
+}
+
+export interface PageTransferWithPage extends PageTransfer {
+  page: Page;
+}
+
+export interface PageInvite {
+  id: string;
+  page_id: string;
+  role: GrantableRole;
+  max_uses: number | null;
+  uses: number;
+  expires_at: string | null;
+  created_by: string;
+  created_at: string;
+}
+
+export interface CreatePageInvite {
+  role: GrantableRole;
+  max_uses?: number | null;
+  expires_at?: string | null;
+}
+
+// The token is only returned when the invite is created
+export interface CreatedPageInvite extends PageInvite {
+  token: string;
 }
 
 export interface AuthResponse {
@@ -120,11 +222,56 @@ export interface UpdateUser {
 
 export interface SetPublicSlug {
   public_slug: string | null;
+  /** Omit to keep the current password, `null` to remove it. */
+  password?: string | null;
+  /** Omit to keep the current expiry, `null` to never expire. */
+  expires_at?: string | null;
+}
+
+// Public link payloads carry only what the page shows to visitors
+export interface PublicPage {
+  title: string;
+  description: string | null;
+  public_slug: string | null;
+  updated_at: string;
+}
+
+export interface PublicListItem {
+  id: string;
+  content: string;
+  /** Absent when the list hides its checkboxes. */
+  checked?: boolean;
+}
+
+export interface PublicList {
+  id: string;
+  title: string;
+  show_checkboxes: boolean;
+  show_progress: boolean;
+  public_checking: boolean;
+  public_suggestions: boolean;
+  /** Absent when the list hides its progress. */
+  progress?: { checked: number; total: number };
+  items: PublicListItem[];
 }
 
 export interface PublicPageData {
-  page: Page;
-  lists: ListWithItems[];
+  page: PublicPage;
+  lists: PublicList[];
+}
+
+export type SuggestionStatus = "pending" | "approved" | "rejected";
+
+export interface ItemSuggestion {
+  id: string;
+  list_id: string;
+  content: string;
+  author_name: string | null;
+  status: SuggestionStatus;
+  item_id: string | null;
+  decided_by: string | null;
+  decided_at: string | null;
+  created_at: string;
 }
 
 export interface ApiKey {
@@ -133,14 +280,105 @@ export interface ApiKey {
   scopes: string[];
   revoked: boolean;
   created_at: string;
+  // Unix timestamps (seconds)
+  expires_at?: number | null;
+  expired?: boolean;
+  last_used_at?: number | null;
+  last_used_ip?: string | null;
+  previous_token_expires_at?: number | null;
+  // Restricted to `pages`, even once every bound page has been deleted
+  page_bound?: boolean;
+  pages?: ApiKeyPage[];
+}
+
+export interface ApiKeyPage {
+  page_id: string;
+  read_only: boolean;
 }
 
 export interface CreateApiKeyRequest {
   name?: string | null;
   scopes: string[];
+  expires_at?: number | null;
+  pages?: ApiKeyPage[] | null;
 }
 
 export interface CreateApiKeyResponse {
   id: string;
   token: string;
-}
\ No newline at end of file
+  expires_at?: number | null;
+}
+
+export interface RotateApiKeyResponse {
+  id: string;
+  token: string;
+  // Unix timestamp (seconds) until which the previous token is still accepted
+  previous_token_expires_at: number | null;
+}
+
+export interface Session {
+  id: string;
+  user_agent: string | null;
+  ip: string | null;
+  created_at: string;
+  last_seen_at: string;
+  expires_at: string;
+  // True for the session making the request
+  current: boolean;
+}
+
+export interface IdentityProviderInfo {
+  id: string;
+  name: string;
+}
+
+export interface UserIdentity {
+  id: string;
+  user_id: string;
+  provider: string;
+  subject: string;
+  username: string | null;
+  email: string | null;
+  created_at: string;
+}
+
+export type TeamRole = "member" | "admin" | "owner";
+
+export interface Team {
+  id: string;
+  name: string;
+  created_by: string;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface TeamWithRole extends Team {
+  role: TeamRole;
+}
+
+export interface TeamMember {
+  id: string;
+  team_id: string;
+  user_id: string;
+  role: TeamRole;
+  created_at: string;
+  user: User;
+}
+
+export interface TeamWithMembers extends Team {
+  members: TeamMember[];
+  invitations: TeamInvitation[];
+}
+
+export interface TeamInvitation {
+  id: string;
+  team_id: string;
+  user_id: string;
+  role: TeamRole;
+  invited_by: string;
+  created_at: string;
+}
+
+export interface TeamInvitationWithTeam extends TeamInvitation {
+  team: Team;
+}